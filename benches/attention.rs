@@ -0,0 +1,82 @@
+use std::time::Instant;
+
+use dfdx::prelude::*;
+
+#[cfg(feature = "cuda")]
+type Dev = Cuda;
+
+#[cfg(not(feature = "cuda"))]
+type Dev = Cpu;
+
+type Dtype = f32;
+
+const M: usize = 512;
+const H: usize = 8;
+const BATCH: usize = 4;
+const SEQ_LENS: [usize; 2] = [128, 512];
+const ITERS: usize = 5;
+
+type Model = MultiHeadAttention<M, H>;
+
+fn main() {
+    println!("Benchmarking `MultiHeadAttention<{M}, {H}>`");
+    println!("Device {}", std::any::type_name::<Dev>());
+    println!("Dtype {}", std::any::type_name::<Dtype>());
+    println!();
+
+    let dev: Dev = Default::default();
+    let m = dev.build_module::<Model, Dtype>();
+
+    for s in SEQ_LENS {
+        println!("Unbatched, shape ({s}, {M})");
+        for _ in 0..ITERS {
+            let x: Tensor<(usize, Const<M>), Dtype, _> = dev.sample_normal_like(&(s, Const));
+
+            let start = Instant::now();
+            let _ = m.forward(x.clone());
+            dev.synchronize();
+            let infer_dur = start.elapsed();
+
+            let start = Instant::now();
+            let y = m.forward(x.leaky_traced());
+            dev.synchronize();
+            let fwd_dur = start.elapsed();
+
+            let start = Instant::now();
+            let _ = y.sum().backward();
+            dev.synchronize();
+            let bwd_dur = start.elapsed();
+
+            println!("infer={infer_dur:?}, fwd={fwd_dur:?} bwd={bwd_dur:?}");
+        }
+
+        println!("Batched, shape ({BATCH}, {s}, {M})");
+        for _ in 0..ITERS {
+            let x: Tensor<(Const<BATCH>, usize, Const<M>), Dtype, _> =
+                dev.sample_normal_like(&(Const, s, Const));
+
+            let start = Instant::now();
+            let _ = m.forward(x.clone());
+            dev.synchronize();
+            let infer_dur = start.elapsed();
+
+            let start = Instant::now();
+            let _ = m.forward_inference((x.clone(), x.clone(), x.clone()));
+            dev.synchronize();
+            let inference_dur = start.elapsed();
+
+            let start = Instant::now();
+            let y = m.forward(x.leaky_traced());
+            dev.synchronize();
+            let fwd_dur = start.elapsed();
+
+            let start = Instant::now();
+            let _ = y.sum().backward();
+            dev.synchronize();
+            let bwd_dur = start.elapsed();
+
+            println!("infer={infer_dur:?} (forward_inference={inference_dur:?}), fwd={fwd_dur:?} bwd={bwd_dur:?}");
+        }
+        println!();
+    }
+}
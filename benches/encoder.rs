@@ -0,0 +1,41 @@
+use std::time::Instant;
+
+use dfdx::prelude::*;
+
+#[cfg(feature = "cuda")]
+type Dev = Cuda;
+
+#[cfg(not(feature = "cuda"))]
+type Dev = Cpu;
+
+type Dtype = f32;
+
+const M: usize = 512;
+const H: usize = 8;
+const F: usize = 2048;
+const L: usize = 6;
+
+type Model = TransformerEncoder<M, H, F, L>;
+type InputShape = Rank2<128, M>;
+
+fn main() {
+    println!("Benchmarking `TransformerEncoder<{M}, {H}, {F}, {L}>` inference");
+    println!("Device {}", std::any::type_name::<Dev>());
+    println!("Dtype {}", std::any::type_name::<Dtype>());
+    println!("Input shape {}", std::any::type_name::<InputShape>());
+    println!();
+
+    let dev: Dev = Default::default();
+    let m = dev.build_module::<Model, Dtype>();
+
+    loop {
+        let x: Tensor<InputShape, Dtype, _> = dev.sample_normal();
+
+        let start = Instant::now();
+        let _ = m.forward(x);
+        dev.synchronize();
+        let infer_dur = start.elapsed();
+
+        println!("infer={infer_dur:?}");
+    }
+}
@@ -0,0 +1,64 @@
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::time::Instant;
+
+use dfdx::prelude::*;
+use dfdx::tensor::{Endian, NumpyDtype};
+
+type Dtype = f32;
+
+const NUMEL: usize = 1_000_000;
+
+fn main() {
+    println!("Benchmarking `load_from_npy`");
+    println!("Dtype {}", std::any::type_name::<Dtype>());
+    println!("Num elements {NUMEL}");
+    println!();
+
+    let dev: Cpu = Default::default();
+    let path = std::env::temp_dir().join("dfdx_bench_npy.npy");
+    let x: Tensor<(usize,), Dtype, _> = dev.sample_normal_like(&(NUMEL,));
+    x.save_to_npy(&path).unwrap();
+    let data_start = std::fs::metadata(&path).unwrap().len() - (NUMEL * 4) as u64;
+
+    let mut y: Tensor<(usize,), Dtype, _> = dev.zeros_like(&(NUMEL,));
+
+    loop {
+        let start = Instant::now();
+        y.load_from_npy(&path).unwrap();
+        let load_dur = start.elapsed();
+
+        // the values only, one element at a time
+        let start = Instant::now();
+        let mut f = BufReader::new(std::fs::File::open(&path).unwrap());
+        f.seek(SeekFrom::Start(data_start)).unwrap();
+        let mut buf = Vec::with_capacity(NUMEL);
+        for _ in 0..NUMEL {
+            buf.push(Dtype::read_endian(&mut f, Endian::Little).unwrap());
+        }
+        assert_eq!(f.read(&mut [0]).unwrap(), 0);
+        let per_element_dur = start.elapsed();
+
+        // the values only, one element at a time without a BufReader (e.g. a file in an npz)
+        let start = Instant::now();
+        let mut f = std::fs::File::open(&path).unwrap();
+        f.seek(SeekFrom::Start(data_start)).unwrap();
+        let mut buf = Vec::with_capacity(NUMEL);
+        for _ in 0..NUMEL {
+            buf.push(Dtype::read_endian(&mut f, Endian::Little).unwrap());
+        }
+        let unbuffered_dur = start.elapsed();
+
+        // the values only, in bulk
+        let start = Instant::now();
+        let mut f = BufReader::new(std::fs::File::open(&path).unwrap());
+        f.seek(SeekFrom::Start(data_start)).unwrap();
+        let mut buf = vec![0.0; NUMEL];
+        Dtype::read_endian_slice(&mut f, Endian::Little, &mut buf).unwrap();
+        assert_eq!(f.read(&mut [0]).unwrap(), 0);
+        let bulk_dur = start.elapsed();
+
+        println!(
+            "load={load_dur:?} per_element={per_element_dur:?} per_element_unbuffered={unbuffered_dur:?} bulk={bulk_dur:?}"
+        );
+    }
+}
@@ -12,6 +12,16 @@ type Dtype = f32;
 type InputShape = Rank4<32, 64, 128, 256>;
 type Ax = Axis<3>;
 
+/// The separate max, exp, sum, and div ops that softmax is made of, for comparing against
+/// the fused kernel used for the last axis.
+fn unfused_softmax(t: Tensor<InputShape, Dtype, Dev>) -> Tensor<InputShape, Dtype, Dev> {
+    let shape = *t.shape();
+    let max = t.clone().max::<_, Ax>().broadcast_like(&shape);
+    let t_exp = (t - max).exp();
+    let t_expsum = t_exp.clone().sum::<_, Ax>().broadcast_like(&shape);
+    t_exp / t_expsum
+}
+
 fn main() {
     println!("Benchmarking `softmax` {}", std::any::type_name::<Ax>());
     println!("Device {}", std::any::type_name::<Dev>());
@@ -31,6 +41,13 @@ fn main() {
 
         let img: Tensor<InputShape, Dtype, _> = dev.sample_normal();
 
+        let start = Instant::now();
+        let _ = unfused_softmax(img);
+        dev.synchronize();
+        let unfused_dur = start.elapsed();
+
+        let img: Tensor<InputShape, Dtype, _> = dev.sample_normal();
+
         let start = Instant::now();
         let y = img.leaky_traced().softmax::<Ax>();
         dev.synchronize();
@@ -41,6 +58,6 @@ fn main() {
         dev.synchronize();
         let bwd_dur = start.elapsed();
 
-        println!("infer={infer_dur:?}, fwd={fwd_dur:?} bwd={bwd_dur:?}");
+        println!("infer={infer_dur:?} (unfused={unfused_dur:?}), fwd={fwd_dur:?} bwd={bwd_dur:?}");
     }
 }
@@ -0,0 +1,127 @@
+use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
+use std::vec::Vec;
+
+use crate::tensor_ops::TryStack;
+
+/// Iterator returned by [batches()].
+pub struct Batches<'a, T> {
+    data: &'a [T],
+    indices: Vec<usize>,
+    batch_size: usize,
+    pos: usize,
+}
+
+/// Iterates over `data` in minibatches of `batch_size` samples, stacking each
+/// minibatch with [TryStack] along a new [usize] dimension.
+///
+/// If `shuffle` is true, the samples are visited in an order determined by `seed`,
+/// so the same seed always yields the same batches. Otherwise `seed` is ignored and
+/// samples are visited in order. Each sample appears exactly once per iteration.
+///
+/// **If `data.len()` is not a multiple of `batch_size`, the last batch is smaller than
+/// `batch_size`**. It contains the remaining samples, and is never empty.
+///
+/// **Panics** if `batch_size` is 0.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, data::batches};
+/// # let dev: Cpu = Default::default();
+/// let samples: Vec<Tensor<Rank2<3, 4>, f32, _>> = (0..10).map(|_| dev.sample_normal()).collect();
+/// let sizes: Vec<usize> = batches(&samples, 4, true, 0)
+///     .map(|b: Tensor<(usize, Const<3>, Const<4>), f32, _>| b.shape().0)
+///     .collect();
+/// assert_eq!(sizes, [4, 4, 2]);
+/// ```
+pub fn batches<T: Clone>(data: &[T], batch_size: usize, shuffle: bool, seed: u64) -> Batches<'_, T>
+where
+    Vec<T>: TryStack,
+{
+    assert!(batch_size > 0, "batch_size must be greater than 0");
+    let mut indices: Vec<usize> = (0..data.len()).collect();
+    if shuffle {
+        indices.shuffle(&mut StdRng::seed_from_u64(seed));
+    }
+    Batches {
+        data,
+        indices,
+        batch_size,
+        pos: 0,
+    }
+}
+
+impl<'a, T: Clone> Iterator for Batches<'a, T>
+where
+    Vec<T>: TryStack,
+{
+    type Item = <Vec<T> as TryStack>::Stacked;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.indices.len() {
+            return None;
+        }
+        let end = (self.pos + self.batch_size).min(self.indices.len());
+        let batch: Vec<T> = self.indices[self.pos..end]
+            .iter()
+            .map(|&i| self.data[i].clone())
+            .collect();
+        self.pos = end;
+        Some(batch.stack())
+    }
+}
+
+impl<'a, T: Clone> ExactSizeIterator for Batches<'a, T>
+where
+    Vec<T>: TryStack,
+{
+    fn len(&self) -> usize {
+        let remaining = self.indices.len() - self.pos;
+        (remaining + self.batch_size - 1) / self.batch_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tests::*};
+    use num_traits::ToPrimitive;
+
+    #[test]
+    fn test_batches_each_sample_once() {
+        let dev: TestDevice = Default::default();
+        let samples: Vec<Tensor<Rank2<1, 2>, TestDtype, _>> = (0..10)
+            .map(|i| dev.tensor([[i as f64, 0.0]]).to_dtype::<TestDtype>())
+            .collect();
+
+        let mut seen = Vec::new();
+        let mut sizes = Vec::new();
+        let iter = batches(&samples, 3, true, 0);
+        assert_eq!(iter.len(), 4);
+        for batch in iter {
+            let batch: Tensor<(usize, Const<1>, Const<2>), TestDtype, _> = batch;
+            sizes.push(batch.shape().0);
+            for row in batch.as_vec().chunks(2) {
+                seen.push(row[0].to_usize().unwrap());
+            }
+        }
+        assert_eq!(sizes, [3, 3, 3, 1]);
+        assert_ne!(seen, (0..10).collect::<Vec<_>>());
+        seen.sort();
+        assert_eq!(seen, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_batches_seeded() {
+        let dev: TestDevice = Default::default();
+        let samples: Vec<Tensor<Rank1<3>, TestDtype, _>> =
+            (0..8).map(|_| dev.sample_normal()).collect();
+
+        let a: Vec<_> = batches(&samples, 4, true, 1).map(|b| b.as_vec()).collect();
+        let b: Vec<_> = batches(&samples, 4, true, 1).map(|b| b.as_vec()).collect();
+        let c: Vec<_> = batches(&samples, 4, true, 2).map(|b| b.as_vec()).collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let unshuffled: Vec<_> = batches(&samples, 8, false, 1).map(|b| b.as_vec()).collect();
+        let expected: Vec<TestDtype> = samples.iter().flat_map(|s| s.as_vec()).collect();
+        assert_eq!(unshuffled, [expected]);
+    }
+}
@@ -1,15 +1,23 @@
 //! A collection of useful data utilities such as [ExactSizeDataset], [OneHotEncode], [Arange],
-//! and iterator extension traits!
+//! [batches()], and iterator extension traits!
+//!
+//! With the `"numpy"` feature, `NpyRowReader` streams minibatches from a 2d `.npy` file.
 mod arange;
 mod batch;
+mod batches;
 mod collate;
 mod dataset;
+#[cfg(feature = "numpy")]
+mod npy_rows;
 mod one_hot_encode;
 mod stack;
 
 pub use arange::Arange;
 pub use batch::IteratorBatchExt;
+pub use batches::{batches, Batches};
 pub use collate::{Collate, IteratorCollateExt};
 pub use dataset::ExactSizeDataset;
+#[cfg(feature = "numpy")]
+pub use npy_rows::NpyRowReader;
 pub use one_hot_encode::OneHotEncode;
 pub use stack::IteratorStackExt;
@@ -0,0 +1,160 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    marker::PhantomData,
+    path::Path,
+};
+
+use crate::{
+    shapes::{Const, Unit},
+    tensor::{
+        numpy::{read_header_rows, Endian, NpyError, NumpyDtype},
+        Tensor, TensorFromVec,
+    },
+};
+
+/// Streams the rows of a 2d `.npy` file with `F` columns in minibatches of
+/// `batch_size` rows, without loading the whole file into memory.
+///
+/// Each item is a `(usize, Const<F>)` tensor. **If the number of rows is not a multiple
+/// of `batch_size`, the last batch is smaller than `batch_size`**.
+///
+/// The file must have been saved in C (not fortran) order with a dtype matching `E`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, data::NpyRowReader};
+/// # let dev: Cpu = Default::default();
+/// # let file = tempfile::NamedTempFile::new().unwrap();
+/// # let path = file.path();
+/// dev.tensor([[1.0f32, 2.0], [3.0, 4.0], [5.0, 6.0]]).save_to_npy(path).unwrap();
+/// let reader = NpyRowReader::<_, f32, _, 2>::open(path, &dev, 2).unwrap();
+/// assert_eq!(reader.num_rows(), 3);
+/// let batches: Vec<Vec<f32>> = reader.map(|b| b.unwrap().as_vec()).collect();
+/// assert_eq!(batches, [vec![1.0, 2.0, 3.0, 4.0], vec![5.0, 6.0]]);
+/// ```
+pub struct NpyRowReader<R, E, D, const F: usize> {
+    reader: R,
+    device: D,
+    endian: Endian,
+    batch_size: usize,
+    num_rows: usize,
+    pos: usize,
+    marker: PhantomData<E>,
+}
+
+impl<E: Unit + NumpyDtype, D: TensorFromVec<E>, const F: usize>
+    NpyRowReader<BufReader<File>, E, D, F>
+{
+    /// Opens the `.npy` file at `path` and reads its header.
+    pub fn open<P: AsRef<Path>>(path: P, device: &D, batch_size: usize) -> Result<Self, NpyError> {
+        Self::new(BufReader::new(File::open(path)?), device, batch_size)
+    }
+}
+
+impl<R: Read, E: Unit + NumpyDtype, D: TensorFromVec<E>, const F: usize> NpyRowReader<R, E, D, F> {
+    /// Reads the header from `reader`, which must be at the start of a `.npy` file.
+    ///
+    /// **Panics** if `batch_size` is 0.
+    pub fn new(mut reader: R, device: &D, batch_size: usize) -> Result<Self, NpyError> {
+        assert!(batch_size > 0, "batch_size must be greater than 0");
+        let (endian, num_rows) = read_header_rows::<R, E>(&mut reader, F)?;
+        Ok(Self {
+            reader,
+            device: device.clone(),
+            endian,
+            batch_size,
+            num_rows,
+            pos: 0,
+            marker: PhantomData,
+        })
+    }
+
+    /// The total number of rows in the file.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+}
+
+impl<R: Read, E: Unit + NumpyDtype, D: TensorFromVec<E>, const F: usize> Iterator
+    for NpyRowReader<R, E, D, F>
+{
+    type Item = Result<Tensor<(usize, Const<F>), E, D>, NpyError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.num_rows {
+            return None;
+        }
+        let n = self.batch_size.min(self.num_rows - self.pos);
+        let mut buf = std::vec![Default::default(); n * F];
+        if let Err(err) = E::read_endian_slice(&mut self.reader, self.endian, &mut buf) {
+            // the stream is in an unknown position, so stop iterating
+            self.pos = self.num_rows;
+            return Some(Err(err.into()));
+        }
+        self.pos += n;
+        Some(Ok(self.device.tensor_from_vec(buf, (n, Const))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.num_rows - self.pos + self.batch_size - 1) / self.batch_size;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<R: Read, E: Unit + NumpyDtype, D: TensorFromVec<E>, const F: usize> ExactSizeIterator
+    for NpyRowReader<R, E, D, F>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::HasShape, tensor::*, tests::TestDevice};
+
+    #[test]
+    fn test_npy_row_reader() {
+        let dev: TestDevice = Default::default();
+        let data: Vec<f32> = (0..100 * 3).map(|i| i as f32).collect();
+        let x = dev.tensor_from_vec(data.clone(), (100, Const::<3>));
+        let bytes = x.save_to_npy_bytes();
+
+        let reader = NpyRowReader::<_, f32, _, 3>::new(bytes.as_slice(), &dev, 32).unwrap();
+        assert_eq!(reader.num_rows(), 100);
+        assert_eq!(reader.len(), 4);
+
+        let mut sizes = Vec::new();
+        let mut found = Vec::new();
+        for batch in reader {
+            let batch = batch.unwrap();
+            sizes.push(batch.shape().0);
+            found.extend(batch.as_vec());
+        }
+        assert_eq!(sizes, [32, 32, 32, 4]);
+        assert_eq!(found, data);
+    }
+
+    #[test]
+    fn test_npy_row_reader_errors() {
+        let dev: TestDevice = Default::default();
+        let x = dev.tensor([[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let bytes = x.save_to_npy_bytes();
+
+        assert!(NpyRowReader::<_, f32, _, 2>::new(bytes.as_slice(), &dev, 1).is_err());
+        assert!(NpyRowReader::<_, f64, _, 3>::new(bytes.as_slice(), &dev, 1).is_err());
+        assert!(NpyRowReader::<_, f32, _, 3>::new(
+            dev.tensor([1.0f32, 2.0, 3.0])
+                .save_to_npy_bytes()
+                .as_slice(),
+            &dev,
+            1
+        )
+        .is_err());
+
+        // truncated data is reported once, then iteration stops
+        let truncated = &bytes[..bytes.len() - 4];
+        let mut reader = NpyRowReader::<_, f32, _, 3>::new(truncated, &dev, 1).unwrap();
+        assert_eq!(reader.next().unwrap().unwrap().as_vec(), [1.0, 2.0, 3.0]);
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+    }
+}
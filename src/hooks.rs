@@ -0,0 +1,210 @@
+//! Callbacks that run on module forwards and backward operations, for profiling and
+//! debugging without instrumenting each layer.
+//!
+//! Hooks are registered per thread with [add_forward_hook()] and [add_backward_hook()],
+//! and removed with [clear_hooks()]. They only see work done on the thread that
+//! registered them.
+//!
+//! Forward hooks run after a module's forward succeeds, and receive the type names of
+//! the module, its input, and its output. For tensors these include the shape, dtype,
+//! device, and tape. They run for:
+//! 1. Every [crate::nn::Module::forward()] and [crate::nn::ModuleMut::forward_mut()] call.
+//! 2. The sub-modules run by the container modules in [crate::nn], such as tuples,
+//!    [crate::nn::modules::Repeated], [crate::nn::modules::Residual], and the transformer
+//!    modules. Calling `try_forward` directly on a module does not run hooks for that module.
+//!
+//! Backward hooks run before each recorded backward operation executes, and receive the
+//! type name of the operation's closure, which identifies the op that recorded it.
+//!
+//! ```rust
+//! # use dfdx::prelude::*;
+//! # use std::{cell::Cell, rc::Rc};
+//! # let dev: Cpu = Default::default();
+//! let model = dev.build_module::<(Linear<2, 3>, ReLU, Linear<3, 1>), f32>();
+//! let count = Rc::new(Cell::new(0));
+//! let c = count.clone();
+//! dfdx::hooks::add_forward_hook(move |info| {
+//!     println!("{} -> {}", info.module, info.output);
+//!     c.set(c.get() + 1);
+//! });
+//! let _ = model.forward(dev.zeros::<Rank1<2>>());
+//! dfdx::hooks::clear_hooks();
+//! // the three layers and the tuple holding them
+//! assert_eq!(count.get(), 4);
+//! ```
+//!
+//! Hooks are not available with the `no-std` feature.
+
+use crate::nn::{Module, ModuleMut};
+
+/// Describes a completed forward, see [add_forward_hook()].
+#[derive(Debug, Clone, Copy)]
+pub struct ForwardHookInfo {
+    /// Type name of the module.
+    pub module: &'static str,
+    /// Type name of the module's input.
+    pub input: &'static str,
+    /// Type name of the module's output.
+    pub output: &'static str,
+}
+
+/// Describes a backward operation about to run, see [add_backward_hook()].
+#[derive(Debug, Clone, Copy)]
+pub struct BackwardHookInfo {
+    /// Type name of the backward operation's closure.
+    pub op: &'static str,
+}
+
+#[cfg(feature = "std")]
+mod registry {
+    use super::*;
+    use std::{boxed::Box, cell::RefCell, vec::Vec};
+
+    type ForwardHook = Box<dyn Fn(&ForwardHookInfo)>;
+    type BackwardHook = Box<dyn Fn(&BackwardHookInfo)>;
+
+    #[derive(Default)]
+    pub(super) struct Hooks {
+        pub(super) forward: Vec<ForwardHook>,
+        pub(super) backward: Vec<BackwardHook>,
+    }
+
+    std::thread_local! {
+        pub(super) static HOOKS: RefCell<Hooks> = RefCell::new(Default::default());
+    }
+}
+
+/// Registers `hook` to run after module forwards on the current thread.
+///
+/// **Panics** if called from inside a hook.
+#[cfg(feature = "std")]
+pub fn add_forward_hook<F: Fn(&ForwardHookInfo) + 'static>(hook: F) {
+    registry::HOOKS.with(|h| h.borrow_mut().forward.push(std::boxed::Box::new(hook)));
+}
+
+/// Registers `hook` to run before each backward operation on the current thread.
+///
+/// **Panics** if called from inside a hook.
+#[cfg(feature = "std")]
+pub fn add_backward_hook<F: Fn(&BackwardHookInfo) + 'static>(hook: F) {
+    registry::HOOKS.with(|h| h.borrow_mut().backward.push(std::boxed::Box::new(hook)));
+}
+
+/// Removes all forward and backward hooks registered on the current thread.
+///
+/// **Panics** if called from inside a hook.
+#[cfg(feature = "std")]
+pub fn clear_hooks() {
+    registry::HOOKS.with(|h| *h.borrow_mut() = Default::default());
+}
+
+fn run_forward_hooks<M: ?Sized, I, O>() {
+    #[cfg(feature = "std")]
+    registry::HOOKS.with(|h| {
+        let hooks = h.borrow();
+        if hooks.forward.is_empty() {
+            return;
+        }
+        let info = ForwardHookInfo {
+            module: std::any::type_name::<M>(),
+            input: std::any::type_name::<I>(),
+            output: std::any::type_name::<O>(),
+        };
+        for hook in hooks.forward.iter() {
+            hook(&info);
+        }
+    });
+}
+
+pub(crate) fn run_backward_hooks(op: &'static str) {
+    #[cfg(feature = "std")]
+    registry::HOOKS.with(|h| {
+        let info = BackwardHookInfo { op };
+        for hook in h.borrow().backward.iter() {
+            hook(&info);
+        }
+    });
+    #[cfg(not(feature = "std"))]
+    let _ = op;
+}
+
+/// [Module::try_forward()] followed by the forward hooks.
+pub(crate) fn try_forward<M: ?Sized + Module<I>, I>(m: &M, x: I) -> Result<M::Output, M::Error> {
+    let y = m.try_forward(x)?;
+    run_forward_hooks::<M, I, M::Output>();
+    Ok(y)
+}
+
+/// [ModuleMut::try_forward_mut()] followed by the forward hooks.
+pub(crate) fn try_forward_mut<M: ?Sized + ModuleMut<I>, I>(
+    m: &mut M,
+    x: I,
+) -> Result<M::Output, M::Error> {
+    let y = m.try_forward_mut(x)?;
+    run_forward_hooks::<M, I, M::Output>();
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::{builders::*, modules},
+        shapes::*,
+        tensor::*,
+        tensor_ops::*,
+        tests::*,
+    };
+    use std::{cell::RefCell, rc::Rc, string::String, vec::Vec};
+
+    #[test]
+    fn test_forward_hooks_transformer_block() {
+        let dev: TestDevice = Default::default();
+        let block = dev.build_module::<TransformerEncoderBlock<8, 2, 16>, TestDtype>();
+
+        let modules: Rc<RefCell<Vec<String>>> = Default::default();
+        let m = modules.clone();
+        add_forward_hook(move |info| m.borrow_mut().push(info.module.into()));
+        let _ = block.forward(dev.zeros::<Rank2<3, 8>>());
+        clear_hooks();
+
+        let modules = modules.borrow();
+        let count = |name: &str| {
+            let name = std::format!("dfdx::nn::{name}<");
+            modules.iter().filter(|m| m.starts_with(&name)).count()
+        };
+        // 4 attention projections + 2 feedforward layers
+        assert_eq!(count("linear::Linear"), 6);
+        assert_eq!(count("transformer::mha::MultiHeadAttention"), 1);
+        assert_eq!(count("layer_norm::LayerNorm1D"), 2);
+        assert_eq!(count("residual::Residual"), 1);
+        assert_eq!(
+            modules.last().unwrap(),
+            std::any::type_name::<modules::TransformerEncoderBlock<8, 2, 16, TestDtype, TestDevice>>(
+            )
+        );
+        // relu, and the tuple holding the feedforward layers
+        assert_eq!(modules.len(), 13);
+
+        // hooks are removed
+        let _ = block.forward(dev.zeros::<Rank2<3, 8>>());
+        assert_eq!(modules.len(), 13);
+    }
+
+    #[test]
+    fn test_backward_hooks() {
+        let dev: TestDevice = Default::default();
+        let count = Rc::new(RefCell::new(0));
+        let c = count.clone();
+        add_backward_hook(move |_| *c.borrow_mut() += 1);
+
+        let x: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let y = x.leaky_trace().exp().sum();
+        // backward also records the op that fills the loss gradient with 1
+        let num_operations = y.tape_stats().num_operations + 1;
+        assert_eq!(*count.borrow(), 0);
+        let _ = y.backward();
+        clear_hooks();
+        assert_eq!(*count.borrow(), num_operations);
+    }
+}
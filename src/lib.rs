@@ -184,12 +184,14 @@ extern crate no_std_compat as std;
 
 pub mod data;
 pub mod feature_flags;
+pub mod hooks;
 pub mod losses;
 pub mod nn;
 pub mod optim;
 pub mod shapes;
 pub mod tensor;
 pub mod tensor_ops;
+pub mod train;
 
 /// Contains subset of all public exports.
 pub mod prelude {
@@ -430,4 +432,60 @@ pub(crate) mod tests {
     }
 
     pub(crate) use assert_close;
+
+    struct ParamValues<E>(std::vec::Vec<(std::string::String, std::vec::Vec<E>)>);
+
+    impl<E: crate::shapes::Dtype, D: crate::tensor_ops::Device<E>>
+        crate::nn::tensor_collection::TensorVisitor<E, D> for ParamValues<E>
+    {
+        type Viewer = (
+            crate::nn::tensor_collection::ViewTensorRef,
+            crate::nn::tensor_collection::ViewTensorName,
+        );
+        type Err = D::Err;
+        type E2 = E;
+        type D2 = D;
+
+        fn visit<S: crate::shapes::Shape>(
+            &mut self,
+            opts: crate::nn::tensor_collection::TensorOptions<S, E, D>,
+            (t, name): (&crate::tensor::Tensor<S, E, D>, std::string::String),
+        ) -> Result<Option<crate::tensor::Tensor<S, E, D>>, Self::Err> {
+            if opts.do_gradient_update {
+                self.0.push((name, t.as_vec()));
+            }
+            Ok(None)
+        }
+    }
+
+    /// Asserts that every trainable parameter of `after` is different from the same
+    /// parameter in `before`, e.g. to check that an optimizer updated all of a module's
+    /// parameters. Parameters whose names end with one of `except` aren't checked.
+    pub fn assert_params_changed<E, D, M>(before: &M, after: &M, except: &[&str])
+    where
+        E: crate::shapes::Dtype,
+        D: crate::tensor_ops::Device<E>,
+        M: crate::nn::tensor_collection::TensorCollection<E, D>,
+    {
+        use crate::nn::tensor_collection::RecursiveWalker;
+        let values = |m: &M| {
+            let mut op = ParamValues(Default::default());
+            M::iter_tensors(&mut RecursiveWalker {
+                m: (m, Default::default()),
+                f: &mut op,
+            })
+            .unwrap();
+            op.0
+        };
+        let before = values(before);
+        let after = values(after);
+        assert!(!before.is_empty());
+        assert_eq!(before.len(), after.len());
+        for ((name, b), (_, a)) in before.iter().zip(after.iter()) {
+            if except.iter().any(|e| name.ends_with(e)) {
+                continue;
+            }
+            assert_ne!(b, a, "`{name}` was not updated");
+        }
+    }
 }
@@ -1,9 +1,10 @@
 //! Standard loss functions such as [mse_loss()], [cross_entropy_with_logits_loss()], and more.
 
 use crate::{
+    data::OneHotEncode,
     shapes::*,
-    tensor::{Tape, Tensor},
-    tensor_ops::*,
+    tensor::{PutTape, SplitTape, Tape, Tensor},
+    tensor_ops::{axpy::AxpyKernel, *},
 };
 
 /// [Mean Squared Error](https://en.wikipedia.org/wiki/Mean_squared_error).
@@ -88,6 +89,80 @@ pub fn cross_entropy_with_logits_loss<S: Shape, E: Dtype, D: Device<E>, T: Tape<
     (probs * target_probs).mean().negate() / inv_last_axis_numel
 }
 
+/// Fused version of [cross_entropy_with_logits_loss()], which records a single operation
+/// on the tape instead of one for each of the intermediate steps. During backprop the
+/// gradient `(softmax(logits) - target_probs) / num_rows` is added to the logits directly,
+/// where `num_rows` is the number of elements excluding the last axis.
+///
+/// Unlike [cross_entropy_with_logits_loss()], no gradient is computed for `target_probs`.
+/// If `logits` doesn't have standard strides this falls back to
+/// [cross_entropy_with_logits_loss()].
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::*};
+/// # let dev: Cpu = Default::default();
+/// let logits: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+/// let target_probs = dev.tensor([[1.0, 0.0, 0.0], [0.0, 0.5, 0.5]]);
+/// let loss = softmax_cross_entropy_loss(logits.leaky_trace(), target_probs);
+/// assert_eq!(loss.tape_stats().num_operations, 1);
+/// ```
+pub fn softmax_cross_entropy_loss<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    logits: Tensor<S, E, D, T>,
+    target_probs: Tensor<S, E, D>,
+) -> Tensor<Rank0, E, D, T> {
+    if logits.strides != logits.shape.strides() {
+        return cross_entropy_with_logits_loss(logits, target_probs);
+    }
+
+    let shape = *logits.shape();
+    let num_rows = shape.num_elements() / <S as HasAxes<S::LastAxis>>::size(&shape);
+    let inv_num_rows = E::from_f64(1.0 / num_rows as f64).unwrap();
+
+    let (logits, mut tape) = logits.split_tape();
+    let log_probs = logits.clone().log_softmax::<S::LastAxis>();
+    let loss = (log_probs.clone() * target_probs.clone()).sum().negate() * inv_num_rows;
+
+    let logits_ghost = logits.ghost();
+    let device = logits.device.clone();
+    let loss_clone = loss.clone();
+    tape.add_backward_op(move |grads| {
+        grads.try_alloc_for(&loss_clone.ghost())?;
+        let grad_loss = grads.get(&loss_clone).try_broadcast_like(&shape)?;
+        let grad = log_probs
+            .try_exp()?
+            .try_sub(target_probs)?
+            .try_mul(inv_num_rows)?
+            .try_mul(grad_loss)?;
+        grads.try_alloc_for(&logits_ghost)?;
+        let grad_logits = grads.get_mut(&logits_ghost);
+        AxpyKernel::forward(&device, grad_logits, E::ONE, grad.data.as_ref(), E::ONE)
+    });
+    loss.put_tape(tape)
+}
+
+/// [softmax_cross_entropy_loss()] with class indices as targets instead of probability
+/// vectors. `labels` contains the index of the target class for each row of `logits`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::*};
+/// # let dev: Cpu = Default::default();
+/// let logits: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+/// let loss = sparse_softmax_cross_entropy_loss(logits.leaky_trace(), &[0, 2]);
+/// ```
+pub fn sparse_softmax_cross_entropy_loss<B: Dim, C: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    logits: Tensor<(B, C), E, D, T>,
+    labels: &[usize],
+) -> Tensor<Rank0, E, D, T> {
+    let (b, c) = *logits.shape();
+    assert_eq!(b.size(), labels.len(), "expected one label per row");
+    assert!(labels.iter().all(|&l| l < c.size()), "label out of bounds");
+    let target_probs = logits
+        .device
+        .one_hot_encode(c, labels.to_vec())
+        .reshape_like(&(b, c));
+    softmax_cross_entropy_loss(logits, target_probs)
+}
+
 /// [KL Divergence loss](https://en.wikipedia.org/wiki/Kullback%E2%80%93Leibler_divergence).
 /// This computes `(target_probs * (target_probs.log() - logits.log_softmax())).sum(-1).mean()`
 ///
@@ -198,6 +273,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cross_entropy_large_logits() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[50.0, -50.0, 0.0], [-50.0, 50.0, 49.0]])
+            .to_dtype::<TestDtype>();
+        let y = dev
+            .tensor([[1.0, 0.0, 0.0], [0.0, 0.0, 1.0]])
+            .to_dtype::<TestDtype>();
+
+        // exp(-100) underflows, so softmax().ln() would give -inf here
+        assert_close_to_literal!(
+            x.clone().log_softmax::<Axis<1>>(),
+            [
+                [0.0, -100.0, -50.0],
+                [-100.31326169, -0.31326169, -1.31326169]
+            ]
+        );
+
+        let loss = cross_entropy_with_logits_loss(x.leaky_trace(), y.clone());
+        assert_close_to_literal!(loss, 0.65663084);
+
+        // (softmax(x) - y) / batch size
+        let g = loss.backward();
+        assert_close_to_literal!(g.get(&x), [[0.0, 0.0, 0.0], [0.0, 0.36552929, -0.36552929]]);
+    }
+
+    #[test]
+    fn test_softmax_cross_entropy_matches_unfused() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<2, 3, 5>, TestDtype, _> = dev.sample_normal();
+        let y = x.retaped::<NoneTape>().exp().softmax::<Axis<2>>();
+
+        let unfused = cross_entropy_with_logits_loss(x.leaky_trace(), y.clone()) * 3.0;
+        let fused = softmax_cross_entropy_loss(x.leaky_trace(), y) * 3.0;
+        assert_close_to_tensor!(fused, unfused);
+        assert!(fused.tape_stats().num_operations < unfused.tape_stats().num_operations);
+
+        let g_unfused = unfused.backward();
+        let g_fused = fused.backward();
+        assert_close_to_tensor!(g_fused.get(&x), g_unfused.get(&x));
+    }
+
+    #[test]
+    fn test_sparse_softmax_cross_entropy() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[50.0, -50.0, 0.0], [-50.0, 50.0, 49.0]])
+            .to_dtype::<TestDtype>();
+        let loss = sparse_softmax_cross_entropy_loss(x.leaky_trace(), &[0, 2]);
+        assert_close_to_literal!(loss, 0.65663084);
+        let g = loss.backward();
+        assert_close_to_literal!(g.get(&x), [[0.0, 0.0, 0.0], [0.0, 0.36552929, -0.36552929]]);
+    }
+
     #[test]
     fn test_hard_crossentropy() {
         let dev: TestDevice = Default::default();
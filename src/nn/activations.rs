@@ -25,6 +25,7 @@ macro_rules! activation_impls {
 }
 
 activation_impls!(ReLU, try_relu, #[doc="Calls [relu()]."]);
+activation_impls!(InPlaceReLU, try_relu_in_place, #[doc="Calls [relu_in_place()]. Can be used in place of [ReLU] to avoid allocating a new buffer for the output."]);
 activation_impls!(GeLU, try_gelu, #[doc="Calls [gelu()]."]);
 activation_impls!(Sin, try_sin, #[doc="Calls [sin()]."]);
 activation_impls!(Cos, try_cos, #[doc="Calls [cos()]."]);
@@ -62,7 +63,7 @@ impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Module<Tensor<S, E, D, T>>
 
 #[cfg(test)]
 mod tests {
-    use crate::{nn::*, tests::TestDevice};
+    use crate::{nn::*, tests::*};
 
     use super::*;
 
@@ -75,6 +76,15 @@ mod tests {
         assert_eq!(r1.array(), r2.array());
     }
 
+    #[test]
+    fn test_nn_activations_relu_in_place() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([-2.0, -1.0, 0.0, 1.0, 2.0]);
+        let r1 = InPlaceReLU.forward_mut(t.clone());
+        let r2 = relu(t);
+        assert_eq!(r1.array(), r2.array());
+    }
+
     #[test]
     fn test_nn_activations_gelu() {
         let dev: TestDevice = Default::default();
@@ -84,6 +94,16 @@ mod tests {
         assert_eq!(r1.array(), r2.array());
     }
 
+    #[test]
+    fn test_nn_activations_gelu_backward() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]).to_dtype::<TestDtype>();
+        let r = GeLU.forward(t.leaky_trace());
+        assert_close_to_literal!(r, [-0.15880801, 0.0, 0.84119199, 1.95459769]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [-0.08296408, 0.5, 1.08296408, 1.08609926]);
+    }
+
     #[test]
     fn test_nn_activations_sin() {
         let dev: TestDevice = Default::default();
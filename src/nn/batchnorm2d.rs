@@ -86,12 +86,11 @@ where
         .try_add(E::from_f64(epsilon).unwrap())?
         .try_sqrt()?;
 
-    let scale = scale.clone().try_div(std)?.try_broadcast_like(&shape)?;
+    let scale = scale.clone().try_div(std)?;
 
     // normalize & affine
     let x = x.try_sub(mean.clone().try_broadcast_like(&shape)?)?;
-    let x = x.try_mul(scale)?;
-    x.try_add(bias.clone().try_broadcast_like(&shape)?)
+    x.try_affine(&scale, bias)
 }
 
 /// Batch normalization for images as described in
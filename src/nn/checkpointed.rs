@@ -0,0 +1,147 @@
+use crate::{
+    shapes::*,
+    tensor::*,
+    tensor_ops::{axpy::AxpyKernel, Device},
+};
+
+use super::*;
+
+/// Gradient checkpointing around `M`: the forward pass of `M` is run without recording
+/// anything on the tape, and is re-run during the backward pass to get the gradients.
+///
+/// This trades time for memory. The tape only holds on to the input of `M`, instead of every
+/// intermediate activation inside of it, at the cost of running the forward pass of `M` twice
+/// when training. It is most useful around large blocks, e.g. wrapping each block of a deep
+/// transformer with `Repeated<Checkpointed<TransformerEncoderBlock<...>>, N>`, so only the
+/// activations in between blocks are kept, plus the ones of a single block during backward.
+///
+/// Since the forward pass is re-run, `M` must be deterministic. Only [Module] is implemented,
+/// so this never applies dropout.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = Repeated<Checkpointed<(Linear<5, 5>, ReLU)>, 4>;
+/// let model = dev.build_module::<Model, f32>();
+/// let x: Tensor<Rank1<5>, f32, _> = dev.sample_normal();
+/// let grads = model.forward(x.trace(model.alloc_grads())).sum().backward();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Checkpointed<M>(pub M);
+
+impl<D: Device<E>, E: Dtype, M: BuildOnDevice<D, E>> BuildOnDevice<D, E> for Checkpointed<M> {
+    type Built = Checkpointed<M::Built>;
+}
+
+impl<E: Dtype, D: Device<E>, M: TensorCollection<E, D>> TensorCollection<E, D> for Checkpointed<M> {
+    type To<E2: Dtype, D2: Device<E2>> = Checkpointed<M::To<E2, D2>>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(Self::module("0", |s| &s.0, |s| &mut s.0), Checkpointed)
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, M> Module<Tensor<S, E, D>> for Checkpointed<M>
+where
+    M: Module<Tensor<S, E, D>, Error = D::Err>,
+{
+    type Output = M::Output;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<S, E, D>) -> Result<Self::Output, D::Err> {
+        crate::hooks::try_forward(&self.0, x)
+    }
+}
+
+impl<S: Shape, S2: Shape, E: Dtype, D: Device<E>, M> Module<Tensor<S, E, D, OwnedTape<E, D>>>
+    for Checkpointed<M>
+where
+    M: 'static + Clone + TensorCollection<E, D>,
+    M: Module<Tensor<S, E, D>, Output = Tensor<S2, E, D>, Error = D::Err>,
+    M: Module<
+        Tensor<S, E, D, OwnedTape<E, D>>,
+        Output = Tensor<S2, E, D, OwnedTape<E, D>>,
+        Error = D::Err,
+    >,
+{
+    type Output = Tensor<S2, E, D, OwnedTape<E, D>>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<S, E, D, OwnedTape<E, D>>) -> Result<Self::Output, D::Err> {
+        let (x, mut tape) = x.split_tape();
+        let y: Tensor<S2, E, D> = crate::hooks::try_forward(&self.0, x.clone())?;
+        let y_ghost = y.ghost();
+        let m = self.0.clone();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&y_ghost)?;
+
+            // re-run the forward pass on a fresh tape, only keeping the gradients of the
+            // input & parameters
+            let mut inner = m.try_alloc_grads()?;
+            inner.retain_leafs(&[x.id]);
+            let y = m.try_forward(x.clone().traced(inner))?;
+            let (y, mut inner_tape) = y.split_tape();
+            inner_tape
+                .gradients
+                .insert(&y, grads.get_ref(&y_ghost).clone());
+            let mut inner = inner_tape.execute()?;
+            inner.drop_non_leafs();
+
+            grads.try_accumulate(inner, |grad, inner_grad| {
+                AxpyKernel::forward(&x.device, grad, E::ONE, inner_grad, E::ONE)
+            })
+        });
+        Ok(y.put_tape(tape))
+    }
+}
+
+impl<M> NonMutableModule for Checkpointed<M> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_checkpointed_matches_uncheckpointed() {
+        let dev: TestDevice = Default::default();
+        type Block = TransformerEncoderBlock<8, 2, 16>;
+        let model = dev.build_module::<Repeated<Block, 3>, TestDtype>();
+        let checkpointed = Repeated::<Checkpointed<_>, 3> {
+            modules: model.modules.iter().cloned().map(Checkpointed).collect(),
+        };
+
+        let x: Tensor<Rank3<2, 4, 8>, TestDtype, _> = dev.sample_normal();
+        // keep the gradient of the input along with the parameters'
+        let mut grads = model.alloc_grads();
+        grads.retain_leafs(&[x.id]);
+        let mut grads_ckpt = checkpointed.alloc_grads();
+        grads_ckpt.retain_leafs(&[x.id]);
+        let y = model.forward(x.trace(grads));
+        let y_ckpt = checkpointed.forward(x.trace(grads_ckpt));
+        assert_close_to_tensor!(y, y_ckpt);
+
+        // only the inputs of each block are kept on the tape
+        assert!(y_ckpt.tape_stats().num_operations < y.tape_stats().num_operations);
+
+        let g = y.square().mean().backward();
+        let g_ckpt = y_ckpt.square().mean().backward();
+        assert_close_to_tensor!(g.get(&x), g_ckpt.get(&x));
+        for (block, block_ckpt) in model.modules.iter().zip(checkpointed.modules.iter()) {
+            let (attn, attn_ckpt) = (&block.self_attn, &block_ckpt.0.self_attn);
+            assert_close_to_tensor!(g.get(&attn.w_q.weight), g_ckpt.get(&attn_ckpt.w_q.weight));
+            assert_close_to_tensor!(g.get(&attn.w_o.bias), g_ckpt.get(&attn_ckpt.w_o.bias));
+            assert_close_to_tensor!(
+                g.get(&block.ff.0 .0.weight),
+                g_ckpt.get(&block_ckpt.0.ff.0 .0.weight)
+            );
+            assert_close_to_tensor!(
+                g.get(&block.norm2.gamma),
+                g_ckpt.get(&block_ckpt.0.norm2.gamma)
+            );
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use num_traits::Float;
+use rand_distr::uniform::SampleUniform;
+
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::*;
+
+pub mod builder {
+    #[derive(Debug)]
+    pub struct DepthwiseConv1D<const M: usize, const KERNEL_SIZE: usize>;
+}
+
+impl<const M: usize, const K: usize, E: Dtype, D: Device<E>> BuildOnDevice<D, E>
+    for builder::DepthwiseConv1D<M, K>
+where
+    DepthwiseConv1D<M, K, E, D>: BuildModule<D, E>,
+{
+    type Built = DepthwiseConv1D<M, K, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, D::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// An *unbiased* depthwise 1d convolution over the sequence axis of `(B, S, M)` inputs,
+/// where each of the `M` channels is convolved with its own length `KERNEL_SIZE` kernel.
+///
+/// The sequence is zero padded so the output has the same length as the input. With an
+/// even `KERNEL_SIZE` the extra padding goes on the right, so output `s` sees inputs
+/// `s - (KERNEL_SIZE - 1) / 2 ..= s + KERNEL_SIZE / 2`.
+///
+/// This is computed as a sum of `KERNEL_SIZE` shifted copies of the input (see [Roll]),
+/// so it is only meant for small kernels.
+///
+/// **Pytorch Equivalent**: `torch.nn.Conv1d(M, M, K, padding="same", groups=M, bias=False)`
+/// on channel first inputs.
+///
+/// Generics:
+/// - `M`: The number of channels.
+/// - `KERNEL_SIZE`: The length of each channel's kernel.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<DepthwiseConv1D<8, 3>, f32>();
+/// let x: Tensor<Rank3<2, 10, 8>, f32, _> = dev.sample_normal();
+/// let _: Tensor<Rank3<2, 10, 8>, f32, _> = model.forward(x);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DepthwiseConv1D<const M: usize, const KERNEL_SIZE: usize, E: Dtype, D: Storage<E>> {
+    /// One kernel per channel, shape (M, KERNEL_SIZE).
+    pub weight: Tensor<Rank2<M, KERNEL_SIZE>, E, D>,
+}
+
+impl<const M: usize, const K: usize, E: Dtype, D: Storage<E>> NonMutableModule
+    for DepthwiseConv1D<M, K, E, D>
+{
+}
+
+impl<const M: usize, const K: usize, E, D: Device<E>> TensorCollection<E, D>
+    for DepthwiseConv1D<M, K, E, D>
+where
+    E: Dtype + Float + SampleUniform,
+{
+    type To<E2: Dtype, D2: Device<E2>> = DepthwiseConv1D<M, K, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            Self::tensor(
+                "weight",
+                |s| &s.weight,
+                |s| &mut s.weight,
+                TensorOptions::reset_with(|t| {
+                    let b = E::ONE / E::from_usize(K).unwrap().sqrt();
+                    t.try_fill_with_distr(rand_distr::Uniform::new(-b, b))
+                }),
+            ),
+            |weight| DepthwiseConv1D { weight },
+        )
+    }
+}
+
+impl<const M: usize, const K: usize, B: Dim, S: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Module<Tensor<(B, S, Const<M>), E, D, T>> for DepthwiseConv1D<M, K, E, D>
+{
+    type Output = Tensor<(B, S, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<(B, S, Const<M>), E, D, T>) -> Result<Self::Output, D::Err> {
+        let shape = *x.shape();
+        let seq = shape.1.size();
+        let pad = (K - 1) / 2;
+
+        let (x, tape) = x.split_tape();
+        let mut out = x.device.try_zeros_like(&shape)?.put_tape(tape);
+        for k in 0..K {
+            // out[s] += x[s + k - pad] * w[k], where x is zero outside of 0..seq
+            let mut mask = std::vec![E::default(); seq];
+            for (s, m) in mask.iter_mut().enumerate() {
+                if s + k >= pad && s + k - pad < seq {
+                    *m = E::ONE;
+                }
+            }
+            let mask = x.device.try_tensor_from_vec(mask, (shape.1,))?;
+            let mask = mask.try_broadcast_like::<_, Axes2<0, 2>>(&shape)?;
+
+            let idx = x.device.try_tensor(k)?;
+            let w = self.weight.retaped::<T>().try_permute::<Rank2<K, M>, _>()?;
+            let w = w.try_select(idx)?;
+            let w = w.try_broadcast_like::<_, Axes2<0, 1>>(&shape)?;
+
+            let amount = (seq + pad - k % seq) % seq;
+            let shifted = x.retaped::<T>().try_roll::<Axis<1>>(amount)?;
+            out = out.try_add(shifted.try_mul(mask)?.try_mul(w)?)?;
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_depthwise_conv1d_matches_manual() {
+        let dev: TestDevice = Default::default();
+        let mut m = dev.build_module::<builder::DepthwiseConv1D<2, 3>, TestDtype>();
+        m.weight.copy_from(
+            &dev.tensor([[1.0, 2.0, 3.0], [-1.0, 0.5, 0.0]])
+                .to_dtype::<TestDtype>()
+                .as_vec(),
+        );
+
+        let x = dev
+            .tensor([[[1.0, 5.0], [2.0, 6.0], [3.0, 7.0], [4.0, 8.0]]])
+            .to_dtype::<TestDtype>();
+        let y = m.forward(x.leaky_trace());
+
+        // channel 0: [0,1,2,3,4,0] * [1,2,3], channel 1: [0,5,6,7,8,0] * [-1,0.5,0]
+        assert_close_to_literal!(y, [[[8.0, 2.5], [14.0, -2.0], [20.0, -2.5], [11.0, -3.0]]]);
+
+        // each kernel tap sees the sum of the inputs it's applied to
+        let g = y.sum().backward();
+        assert_close_to_literal!(g.get(&m.weight), [[6.0, 10.0, 9.0], [18.0, 26.0, 21.0]]);
+        assert_close_to_literal!(
+            g.get(&x),
+            [[[3.0, -0.5], [6.0, -0.5], [6.0, -0.5], [5.0, 0.5]]]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     shapes::*,
-    tensor::{NoneTape, OwnedTape, Tensor},
+    tensor::{NoneTape, OwnedTape, Tape, Tensor},
     tensor_ops::*,
 };
 
@@ -129,6 +129,41 @@ impl Default for Dropout {
     }
 }
 
+impl Dropout {
+    /// Applies dropout with a fixed `mask` instead of a random one, which makes models
+    /// with dropout deterministic in tests. Elements where `mask` is `0` are dropped, and
+    /// elements where it is `1` are scaled by `1 / (1 - p)`, the same as [Module::forward_mut()].
+    ///
+    /// Unlike the module impls, this applies the mask whether or not `input` has a tape.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let dropout = Dropout { p: 0.5 };
+    /// let x: Tensor<Rank1<4>, f32, _> = dev.ones();
+    /// let mask = dev.tensor([1.0, 0.0, 0.0, 1.0]);
+    /// let y = dropout.forward_with_mask(x, &mask);
+    /// assert_eq!(y.array(), [2.0, 0.0, 0.0, 2.0]);
+    /// ```
+    pub fn forward_with_mask<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+        &self,
+        input: Tensor<S, E, D, T>,
+        mask: &Tensor<S, E, D>,
+    ) -> Tensor<S, E, D, T> {
+        self.try_forward_with_mask(input, mask).unwrap()
+    }
+
+    /// Fallible version of [Dropout::forward_with_mask]
+    pub fn try_forward_with_mask<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+        &self,
+        input: Tensor<S, E, D, T>,
+        mask: &Tensor<S, E, D>,
+    ) -> Result<Tensor<S, E, D, T>, D::Err> {
+        let scale = E::from_f32(1.0 / (1.0 - self.p)).unwrap();
+        input.try_mul(mask.clone())?.try_mul(scale)
+    }
+}
+
 impl ZeroSizedModule for Dropout {}
 
 impl<S: Shape, E: Dtype, D: Device<E>> Module<Tensor<S, E, D, NoneTape>> for Dropout {
@@ -158,7 +193,7 @@ impl<S: Shape, E: Dtype, D: Device<E>> ModuleMut<Tensor<S, E, D, OwnedTape<E, D>
 mod tests {
     use crate::{
         shapes::Rank1,
-        tensor::{AsArray, OnesTensor, Trace},
+        tensor::{AsArray, OnesTensor, TensorFrom, Trace},
         tests::*,
     };
 
@@ -177,6 +212,23 @@ mod tests {
         assert_ne!(r1.array(), r1_2.array());
     }
 
+    #[test]
+    fn test_dropout_with_mask() {
+        let dev: TestDevice = Default::default();
+        let dropout = Dropout { p: 0.75 };
+        let t = dev.tensor([1.0, 2.0, 3.0, 4.0]).to_dtype::<TestDtype>();
+        let mask = dev.tensor([0.0, 1.0, 1.0, 0.0]).to_dtype::<TestDtype>();
+        let r = dropout.forward_with_mask(t.leaky_trace(), &mask);
+        assert_close_to_literal!(r, [0.0, 8.0, 12.0, 0.0]);
+
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [0.0, 4.0, 4.0, 0.0]);
+
+        // the same mask gives the same output
+        let r2 = dropout.forward_with_mask(t, &mask);
+        assert_close_to_literal!(r2, [0.0, 8.0, 12.0, 0.0]);
+    }
+
     #[test]
     fn test_dropout_no_tape() {
         let dev: TestDevice = Default::default();
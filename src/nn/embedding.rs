@@ -117,6 +117,103 @@ impl<
     }
 }
 
+/// A sequence of `S` token ids, e.g. the output of a tokenizer, that can be passed
+/// directly to [Embedding::forward()]. Use [BatchedTokenIds] for a batch of sequences.
+///
+/// Converting from a [Vec] checks the length:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<Embedding<7, 2>, f32>();
+/// let ids: TokenIds<3> = std::vec![4, 0, 6].try_into().unwrap();
+/// let _: Tensor<Rank2<3, 2>, f32, _> = model.forward(ids);
+/// assert!(TokenIds::<3>::try_from(std::vec![4, 0]).is_err());
+/// ```
+///
+/// The output has no tape. To train the embedding, convert the ids with
+/// [TokenIds::to_tensor()] and trace that instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenIds<const S: usize>(pub [usize; S]);
+
+/// A batch of `B` sequences of `S` token ids. See [TokenIds].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchedTokenIds<const B: usize, const S: usize>(pub [[usize; S]; B]);
+
+impl<const S: usize> TryFrom<std::vec::Vec<usize>> for TokenIds<S> {
+    /// The ids are given back if there are not exactly `S` of them.
+    type Error = std::vec::Vec<usize>;
+    fn try_from(ids: std::vec::Vec<usize>) -> Result<Self, Self::Error> {
+        ids.try_into().map(Self)
+    }
+}
+
+impl<const B: usize, const S: usize> TryFrom<std::vec::Vec<usize>> for BatchedTokenIds<B, S> {
+    /// The ids are given back if there are not exactly `B * S` of them.
+    type Error = std::vec::Vec<usize>;
+    /// Converts `B * S` ids in row major order.
+    fn try_from(ids: std::vec::Vec<usize>) -> Result<Self, Self::Error> {
+        if ids.len() != B * S {
+            return Err(ids);
+        }
+        Ok(Self(std::array::from_fn(|b| {
+            std::array::from_fn(|s| ids[b * S + s])
+        })))
+    }
+}
+
+impl<const S: usize> TokenIds<S> {
+    /// Puts the ids in a tensor on `device`.
+    pub fn to_tensor<D: TensorFromVec<usize>>(&self, device: &D) -> Tensor<Rank1<S>, usize, D> {
+        self.try_to_tensor(device).unwrap()
+    }
+
+    /// Fallible version of [TokenIds::to_tensor]
+    pub fn try_to_tensor<D: TensorFromVec<usize>>(
+        &self,
+        device: &D,
+    ) -> Result<Tensor<Rank1<S>, usize, D>, D::Err> {
+        device.try_tensor_from_vec(self.0.to_vec(), (Const,))
+    }
+}
+
+impl<const B: usize, const S: usize> BatchedTokenIds<B, S> {
+    /// Puts the ids in a tensor on `device`.
+    pub fn to_tensor<D: TensorFromVec<usize>>(&self, device: &D) -> Tensor<Rank2<B, S>, usize, D> {
+        self.try_to_tensor(device).unwrap()
+    }
+
+    /// Fallible version of [BatchedTokenIds::to_tensor]
+    pub fn try_to_tensor<D: TensorFromVec<usize>>(
+        &self,
+        device: &D,
+    ) -> Result<Tensor<Rank2<B, S>, usize, D>, D::Err> {
+        let ids = self.0.iter().flat_map(|seq| seq.iter().copied()).collect();
+        device.try_tensor_from_vec(ids, (Const, Const))
+    }
+}
+
+impl<const V: usize, const M: usize, const S: usize, E: Dtype, D: Device<E>> Module<TokenIds<S>>
+    for Embedding<V, M, E, D>
+{
+    type Output = Tensor<Rank2<S, M>, E, D>;
+    type Error = D::Err;
+
+    fn try_forward(&self, ids: TokenIds<S>) -> Result<Self::Output, D::Err> {
+        self.try_forward(ids.try_to_tensor(&self.weight.device)?)
+    }
+}
+
+impl<const V: usize, const M: usize, const B: usize, const S: usize, E: Dtype, D: Device<E>>
+    Module<BatchedTokenIds<B, S>> for Embedding<V, M, E, D>
+{
+    type Output = Tensor<Rank3<B, S, M>, E, D>;
+    type Error = D::Err;
+
+    fn try_forward(&self, ids: BatchedTokenIds<B, S>) -> Result<Self::Output, D::Err> {
+        self.try_forward(ids.try_to_tensor(&self.weight.device)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,4 +321,43 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_repeated_ids_accumulate_grad() {
+        let dev: TestDevice = Default::default();
+        let model = Embedding {
+            weight: dev.tensor(W),
+        }
+        .to_dtype::<TestDtype>();
+
+        let ids = TokenIds([1, 0, 1]).to_tensor(&dev);
+        let y = model.forward(ids.leaky_trace());
+        let g = y.sum().backward();
+        assert_close_to_literal!(g.get(&model.weight), [[1.0; 5], [2.0; 5]]);
+    }
+
+    #[test]
+    fn test_forward_token_ids() {
+        let dev: TestDevice = Default::default();
+        let model = Embedding {
+            weight: dev.tensor(W),
+        }
+        .to_dtype::<TestDtype>();
+
+        assert_eq!(
+            TokenIds::<3>::try_from(std::vec![0, 1, 1, 0]),
+            Err(std::vec![0, 1, 1, 0])
+        );
+        assert!(BatchedTokenIds::<2, 2>::try_from(std::vec![0, 1, 1]).is_err());
+
+        let ids = TokenIds::<3>::try_from(std::vec![0, 0, 1]).unwrap();
+        assert_close_to_tensor!(model.forward(ids), model.forward(dev.tensor([0, 0, 1])));
+
+        let ids = BatchedTokenIds::<2, 2>::try_from(std::vec![0, 0, 0, 1]).unwrap();
+        assert_eq!(ids, BatchedTokenIds([[0, 0], [0, 1]]));
+        assert_close_to_tensor!(
+            model.forward(ids),
+            model.forward(dev.tensor([[0, 0], [0, 1]]))
+        );
+    }
 }
@@ -0,0 +1,67 @@
+use crate::tensor::HasErr;
+
+use super::module::{Module, NonMutableModule};
+
+/// Wraps a closure `Fn(X) -> X` as a module, so quick experiments can be composed in
+/// tuples with other modules without defining a struct.
+///
+/// The closure is meant to be parameter free, e.g. a combination of tensor ops. Tensors
+/// captured by the closure are treated as constants: they are not visited by
+/// [super::TensorCollection], so they are not reset, updated by optimizers, or saved.
+/// For the same reason `FnModule` doesn't implement [super::TensorCollection] itself,
+/// so it can be used in [Module::forward()], but not with [super::ResetParams],
+/// [super::ToDevice], or an optimizer. Build the rest of the model separately:
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let linear = dev.build_module::<Linear<2, 3>, f32>();
+/// let model = (linear, FnModule(|x: Tensor<Rank1<3>, f32, Cpu>| x.relu() * 2.0));
+/// let y = model.forward(dev.tensor([1.0, 2.0]));
+/// assert_eq!(y.array(), (model.0.forward(dev.tensor([1.0, 2.0])).relu() * 2.0).array());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FnModule<F>(pub F);
+
+impl<F> NonMutableModule for FnModule<F> {}
+
+impl<X: HasErr, F: Fn(X) -> X> Module<X> for FnModule<F> {
+    type Output = X;
+    type Error = X::Err;
+
+    fn try_forward(&self, x: X) -> Result<Self::Output, Self::Error> {
+        Ok((self.0)(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::builders::*, nn::DeviceBuildExt, shapes::*, tensor::*, tensor_ops::*, tests::*,
+    };
+
+    #[test]
+    fn test_fn_module_after_linear() {
+        let dev: TestDevice = Default::default();
+        let linear = dev.build_module::<Linear<2, 3>, TestDtype>();
+        let x: Tensor<Rank1<2>, TestDtype, _> = dev.sample_normal();
+
+        let scale = dev.tensor(3.0).to_dtype::<TestDtype>();
+        let scale_by = |x: Tensor<Rank1<3>, TestDtype, _, OwnedTape<_, _>>| {
+            let s = scale.clone().broadcast_like(x.shape());
+            x * s
+        };
+        let model = (linear.clone(), FnModule(scale_by));
+        let y = model.forward(x.leaky_trace());
+        let y0 = linear.forward(x.leaky_trace());
+        assert_close_to_tensor!(y, y0.retaped::<NoneTape>() * 3.0);
+
+        // the tape threads through the closure, so linear gets 3x the gradients
+        let g = y.exp().sum().backward();
+        let g0 = (y0 * 3.0).exp().sum().backward();
+        assert_close_to_tensor!(g.get(&linear.weight), g0.get(&linear.weight));
+        assert_close_to_tensor!(g.get(&linear.bias), g0.get(&linear.bias));
+        assert_close_to_tensor!(g.get(&x), g0.get(&x));
+    }
+}
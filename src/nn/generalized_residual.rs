@@ -62,9 +62,8 @@ where
     type Error = F::Error;
 
     fn try_forward(&self, x: T) -> Result<Self::Output, F::Error> {
-        self.f
-            .try_forward(x.with_empty_tape())?
-            .try_add(self.r.try_forward(x)?)
+        crate::hooks::try_forward(&self.f, x.with_empty_tape())?
+            .try_add(crate::hooks::try_forward(&self.r, x)?)
     }
 }
 
@@ -77,9 +76,8 @@ where
     type Error = F::Error;
 
     fn try_forward_mut(&mut self, x: T) -> Result<Self::Output, F::Error> {
-        self.f
-            .try_forward_mut(x.with_empty_tape())?
-            .try_add(self.r.try_forward_mut(x)?)
+        crate::hooks::try_forward_mut(&mut self.f, x.with_empty_tape())?
+            .try_add(crate::hooks::try_forward_mut(&mut self.r, x)?)
     }
 }
 
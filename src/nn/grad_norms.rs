@@ -0,0 +1,339 @@
+use num_traits::Float;
+
+use super::tensor_collection::*;
+
+use crate::{
+    shapes::*,
+    tensor::*,
+    tensor_ops::{BroadcastTo, Device, MeanTo, ReshapeTo, TryMul, TrySub},
+};
+
+use std::{string::String, vec::Vec};
+
+struct GradNorms<'a, E: Unit, D: Storage<E>> {
+    gradients: &'a Gradients<E, D>,
+    norms: Vec<(String, f32)>,
+}
+
+impl<'a, E: Dtype + Float, D: Device<E>> TensorVisitor<E, D> for GradNorms<'a, E, D> {
+    type Viewer = (ViewTensorRef, ViewTensorName);
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        (t, name): (&Tensor<S, E, D>, String),
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if opts.do_gradient_update && self.gradients.get_ref_checked(t).is_some() {
+            let sum_sq: f64 = self
+                .gradients
+                .get(t)
+                .as_vec()
+                .into_iter()
+                .map(|g| g.to_f64().unwrap().powi(2))
+                .sum();
+            self.norms.push((name, sum_sq.sqrt() as f32));
+        }
+        Ok(None)
+    }
+}
+
+/// Returns the L2 norm of the gradient of each of `model`'s trainable parameters,
+/// along with the parameter's dotted name (the same names used by
+/// [crate::nn::SaveToNpz]).
+///
+/// Parameters without a gradient in `gradients` are skipped. This is useful for spotting
+/// vanishing or exploding gradients in specific layers.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<(Linear<2, 3>, Linear<3, 1>), f32>();
+/// let x: Tensor<Rank1<2>, f32, _> = dev.sample_normal();
+/// let grads = model.forward(x.trace(model.alloc_grads())).sum().backward();
+/// let norms = grad_norms_by_name(&model, &grads);
+/// let names: Vec<&str> = norms.iter().map(|(name, _)| name.as_str()).collect();
+/// assert_eq!(names, ["0.weight", "0.bias", "1.weight", "1.bias"]);
+/// ```
+pub fn grad_norms_by_name<E: Dtype + Float, D: Device<E>, M: TensorCollection<E, D>>(
+    model: &M,
+    gradients: &Gradients<E, D>,
+) -> Vec<(String, f32)> {
+    try_grad_norms_by_name(model, gradients).unwrap()
+}
+
+/// Fallible version of [grad_norms_by_name]
+pub fn try_grad_norms_by_name<E: Dtype + Float, D: Device<E>, M: TensorCollection<E, D>>(
+    model: &M,
+    gradients: &Gradients<E, D>,
+) -> Result<Vec<(String, f32)>, D::Err> {
+    let mut op = GradNorms {
+        gradients,
+        norms: Vec::new(),
+    };
+    M::iter_tensors(&mut RecursiveWalker {
+        m: (model, String::new()),
+        f: &mut op,
+    })?;
+    Ok(op.norms)
+}
+
+struct ScaleGrads<'a, E: Unit, D: Storage<E>> {
+    gradients: &'a mut Gradients<E, D>,
+    scale: E,
+}
+
+impl<'a, E: Dtype, D: Device<E>> TensorVisitor<E, D> for ScaleGrads<'a, E, D> {
+    type Viewer = ViewTensorRef;
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        t: &Tensor<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if !opts.do_gradient_update {
+            return Ok(None);
+        }
+        if let Some(g) = self.gradients.take(t) {
+            // `g` is the only owner of its buffer, so the mul scales it in place
+            let g = g.try_mul(self.scale)?;
+            let g = std::sync::Arc::try_unwrap(g.data).unwrap_or_else(|g| (*g).clone());
+            self.gradients.insert(t, g);
+        }
+        Ok(None)
+    }
+}
+
+/// Clips the gradients of `model`'s trainable parameters so that their global L2 norm,
+/// computed as if all of the gradients were concatenated, is at most `max_norm`. If the
+/// norm is above `max_norm`, every gradient is scaled by `max_norm / (norm + 1e-6)`,
+/// otherwise the gradients are untouched.
+///
+/// Returns the global norm from before clipping. Call this between `backward()` and
+/// [crate::optim::Optimizer::update()].
+///
+/// **Pytorch equivalent**: `torch.nn.utils.clip_grad_norm_(model.parameters(), max_norm)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<Linear<2, 3>, f32>();
+/// let x: Tensor<Rank1<2>, f32, _> = dev.sample_normal();
+/// let mut grads = model.forward(x.trace(model.alloc_grads())).sum().backward();
+/// let norm = clip_grad_norm(&model, &mut grads, 1.0);
+/// ```
+pub fn clip_grad_norm<E: Dtype + Float, D: Device<E>, M: TensorCollection<E, D>>(
+    model: &M,
+    gradients: &mut Gradients<E, D>,
+    max_norm: f64,
+) -> f64 {
+    try_clip_grad_norm(model, gradients, max_norm).unwrap()
+}
+
+/// Fallible version of [clip_grad_norm]
+pub fn try_clip_grad_norm<E: Dtype + Float, D: Device<E>, M: TensorCollection<E, D>>(
+    model: &M,
+    gradients: &mut Gradients<E, D>,
+    max_norm: f64,
+) -> Result<f64, D::Err> {
+    let norm = try_grad_norms_by_name(model, gradients)?
+        .into_iter()
+        .map(|(_, n)| (n as f64).powi(2))
+        .sum::<f64>()
+        .sqrt();
+    if norm > max_norm {
+        let mut op = ScaleGrads {
+            gradients,
+            scale: E::from_f64(max_norm / (norm + 1e-6)).unwrap(),
+        };
+        M::iter_tensors(&mut RecursiveWalker {
+            m: model,
+            f: &mut op,
+        })?;
+    }
+    Ok(norm)
+}
+
+struct CentralizeGrads<'a, E: Unit, D: Storage<E>> {
+    gradients: &'a mut Gradients<E, D>,
+}
+
+impl<'a, E: Dtype, D: Device<E>> TensorVisitor<E, D> for CentralizeGrads<'a, E, D> {
+    type Viewer = ViewTensorRef;
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        t: &Tensor<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if S::NUM_DIMS >= 2
+            && opts.do_gradient_update
+            && self.gradients.get_ref_checked(t).is_some()
+        {
+            // view the gradient as (rows, everything else), so each output row is centered
+            let rows = t.shape.concrete()[0];
+            let shape = (rows, t.shape.num_elements() / rows);
+            let g = self.gradients.get(t).try_reshape_like(&shape)?;
+            let mean = g.clone().try_mean::<_, Axis<1>>()?;
+            let g = g.try_sub(mean.try_broadcast_like::<_, Axis<1>>(&shape)?)?;
+            self.gradients.insert(t, g.data.as_ref().clone());
+        }
+        Ok(None)
+    }
+}
+
+/// Applies [gradient centralization](https://arxiv.org/abs/2004.01461) to the gradients of
+/// `model`'s trainable parameters, subtracting the mean of each output row from the
+/// gradient. A row is everything but the first axis, so for a [crate::nn::modules::Linear]
+/// weight of shape `(O, I)` each of the `O` rows of the gradient ends up with zero mean.
+///
+/// This only applies to parameters with at least 2 dimensions, i.e. weight matrices and
+/// convolution kernels. Biases and other 1d parameters like layer norm gains are untouched.
+/// Call this between `backward()` and [crate::optim::Optimizer::update()].
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<Linear<2, 3>, f32>();
+/// let x: Tensor<Rank1<2>, f32, _> = dev.sample_normal();
+/// let mut grads = model.forward(x.trace(model.alloc_grads())).sum().backward();
+/// centralize_grads(&model, &mut grads);
+/// ```
+pub fn centralize_grads<E: Dtype, D: Device<E>, M: TensorCollection<E, D>>(
+    model: &M,
+    gradients: &mut Gradients<E, D>,
+) {
+    try_centralize_grads(model, gradients).unwrap()
+}
+
+/// Fallible version of [centralize_grads]
+pub fn try_centralize_grads<E: Dtype, D: Device<E>, M: TensorCollection<E, D>>(
+    model: &M,
+    gradients: &mut Gradients<E, D>,
+) -> Result<(), D::Err> {
+    M::iter_tensors(&mut RecursiveWalker {
+        m: model,
+        f: &mut CentralizeGrads { gradients },
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_grad_norms_linear() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<Linear<2, 2>, TestDtype>();
+        let x = dev.tensor([3.0, -4.0]).to_dtype::<TestDtype>();
+        let grads = model.forward(x.trace(model.alloc_grads())).sum().backward();
+        let norms = grad_norms_by_name(&model, &grads);
+        assert_eq!(norms.len(), 2);
+        assert_eq!(norms[0].0, "weight");
+        assert_eq!(norms[1].0, "bias");
+        // each row of the weight gradient is x, and the bias gradient is all ones
+        assert!((norms[0].1 - 50.0f32.sqrt()).abs() < 1e-2);
+        assert!((norms[1].1 - 2.0f32.sqrt()).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_clip_grad_norm() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<Linear<2, 2>, TestDtype>();
+        let x = dev.tensor([3.0, -4.0]).to_dtype::<TestDtype>();
+        let grads = model.forward(x.trace(model.alloc_grads())).sum().backward();
+
+        // the weight gradient has norm sqrt(50) and the bias gradient sqrt(2)
+        let mut clipped = grads.clone();
+        let norm = clip_grad_norm(&model, &mut clipped, 2.0);
+        assert!((norm - 52.0f64.sqrt()).abs() < 1e-2);
+        let scale = 2.0 / (52.0f64.sqrt() + 1e-6);
+        assert_close_to_tensor!(
+            clipped.get(&model.weight),
+            grads.get(&model.weight) * TestDtype::from_f64(scale).unwrap()
+        );
+        assert_close_to_tensor!(
+            clipped.get(&model.bias),
+            grads.get(&model.bias) * TestDtype::from_f64(scale).unwrap()
+        );
+
+        // under the threshold nothing changes
+        let mut unclipped = grads.clone();
+        let norm = clip_grad_norm(&model, &mut unclipped, 10.0);
+        assert!((norm - 52.0f64.sqrt()).abs() < 1e-2);
+        assert_eq!(
+            unclipped.get(&model.weight).array(),
+            grads.get(&model.weight).array()
+        );
+        assert_eq!(
+            unclipped.get(&model.bias).array(),
+            grads.get(&model.bias).array()
+        );
+    }
+
+    #[test]
+    fn test_centralize_grads() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<Linear<3, 2>, TestDtype>();
+        let x = dev.tensor([1.0, 2.0, 6.0]).to_dtype::<TestDtype>();
+        let grads = model
+            .forward(x.trace(model.alloc_grads()))
+            .square()
+            .sum()
+            .backward();
+
+        let mut centralized = grads.clone();
+        centralize_grads(&model, &mut centralized);
+        let w = centralized.get(&model.weight);
+        assert_close_to_literal!(w.clone().mean::<Rank1<2>, _>(), [0.0; 2]);
+        // x is centered and scaled by 2 * y in each row
+        let y = model.forward(x.clone()).array();
+        let scale = y.map(|y_i| 2.0 * <f64 as NumCast>::from(y_i).unwrap());
+        assert_close_to_literal!(
+            w,
+            [
+                [-2.0 * scale[0], -scale[0], 3.0 * scale[0]],
+                [-2.0 * scale[1], -scale[1], 3.0 * scale[1]],
+            ]
+        );
+
+        // the bias is 1d, so it's left as is
+        assert_eq!(
+            centralized.get(&model.bias).array(),
+            grads.get(&model.bias).array()
+        );
+    }
+
+    #[test]
+    fn test_grad_norms_encoder() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<TransformerEncoder<8, 2, 16, 2>, TestDtype>();
+        let x: Tensor<Rank2<3, 8>, TestDtype, _> = dev.sample_normal();
+        let grads = model
+            .forward(x.trace(model.alloc_grads()))
+            .square()
+            .mean()
+            .backward();
+        let norms = grad_norms_by_name(&model, &grads);
+        for block in ["0.", "1."] {
+            let block_norms: Vec<f32> = norms
+                .iter()
+                .filter(|(name, _)| name.starts_with(block))
+                .map(|(_, norm)| *norm)
+                .collect();
+            assert!(!block_norms.is_empty());
+            assert!(block_norms.iter().all(|n| n.is_finite()));
+            assert!(block_norms.iter().any(|&n| n > 0.0));
+        }
+    }
+}
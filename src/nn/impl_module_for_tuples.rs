@@ -88,7 +88,7 @@ macro_rules! tuple_impls {
 
             /// Calls forward sequentially on each module in the tuple.
             fn try_forward(&self, x: Input) -> Result<Self::Output, Self::Error> {
-                $(let x = self.$idx.try_forward(x)?;)+
+                $(let x = crate::hooks::try_forward(&self.$idx, x)?;)+
                 Ok(x)
             }
         }
@@ -104,7 +104,7 @@ macro_rules! tuple_impls {
 
             /// Calls forward sequentially on each module in the tuple.
             fn try_forward_mut(&mut self, x: Input) -> Result<Self::Output, Self::Error> {
-                $(let x = self.$idx.try_forward_mut(x)?;)+
+                $(let x = crate::hooks::try_forward_mut(&mut self.$idx, x)?;)+
                 Ok(x)
             }
         }
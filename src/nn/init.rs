@@ -0,0 +1,149 @@
+use num_traits::Float;
+use rand_distr::{uniform::SampleUniform, Distribution, Normal, StandardNormal, Uniform};
+
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{SampleTensor, Tensor},
+};
+
+/// Which fan to use when computing the scale of [InitScheme::Kaiming].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FanMode {
+    /// Preserves the variance of activations in the forward pass.
+    #[default]
+    FanIn,
+    /// Preserves the variance of gradients in the backward pass.
+    FanOut,
+}
+
+/// The nonlinearity that follows a layer, used to compute the gain of [InitScheme::Kaiming].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Nonlinearity {
+    Linear,
+    Sigmoid,
+    Tanh,
+    #[default]
+    ReLU,
+    /// Contains the negative slope.
+    LeakyReLU(f64),
+}
+
+impl Nonlinearity {
+    /// The recommended gain, matching `torch.nn.init.calculate_gain`.
+    pub fn gain(&self) -> f64 {
+        match self {
+            Self::Linear | Self::Sigmoid => 1.0,
+            Self::Tanh => 5.0 / 3.0,
+            Self::ReLU => 2.0.sqrt(),
+            Self::LeakyReLU(slope) => (2.0 / (1.0 + slope * slope)).sqrt(),
+        }
+    }
+}
+
+/// A distribution to initialize parameters from, parameterized by the
+/// fan-in and fan-out of the layer.
+///
+/// The fans are passed explicitly to [InitScheme::try_fill()], so they can differ
+/// from the nominal dimensions of a layer (e.g. for grouped layers).
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum InitScheme {
+    /// Uniform between `[-1 / sqrt(fan_in), 1 / sqrt(fan_in)]`. This is the default
+    /// initialization of [crate::nn::modules::Linear].
+    #[default]
+    Uniform,
+    /// Normal with mean 0 and standard deviation `std`.
+    Normal { std: f64 },
+    /// He initialization, normal with mean 0 and standard deviation `gain / sqrt(fan)`.
+    Kaiming {
+        mode: FanMode,
+        nonlinearity: Nonlinearity,
+    },
+    /// Glorot initialization, uniform between `[-b, b]` where `b = sqrt(6 / (fan_in + fan_out))`.
+    Xavier,
+}
+
+impl InitScheme {
+    /// Fills `t` with values sampled according to this scheme.
+    pub fn fill<S: Shape, E, D: SampleTensor<E>, T>(
+        &self,
+        t: &mut Tensor<S, E, D, T>,
+        fan_in: usize,
+        fan_out: usize,
+    ) where
+        E: Dtype + Float + SampleUniform,
+        StandardNormal: Distribution<E>,
+    {
+        self.try_fill(t, fan_in, fan_out).unwrap()
+    }
+
+    /// Fallible version of [InitScheme::fill()].
+    pub fn try_fill<S: Shape, E, D: SampleTensor<E>, T>(
+        &self,
+        t: &mut Tensor<S, E, D, T>,
+        fan_in: usize,
+        fan_out: usize,
+    ) -> Result<(), D::Err>
+    where
+        E: Dtype + Float + SampleUniform,
+        StandardNormal: Distribution<E>,
+    {
+        let fan_in = fan_in as f64;
+        let fan_out = fan_out as f64;
+        match self {
+            Self::Uniform => {
+                let b = E::from_f64(1.0 / fan_in.sqrt()).unwrap();
+                t.try_fill_with_distr(Uniform::new(-b, b))
+            }
+            Self::Normal { std } => normal(t, *std),
+            Self::Kaiming { mode, nonlinearity } => {
+                let fan = match mode {
+                    FanMode::FanIn => fan_in,
+                    FanMode::FanOut => fan_out,
+                };
+                normal(t, nonlinearity.gain() / fan.sqrt())
+            }
+            Self::Xavier => {
+                let b = E::from_f64((6.0 / (fan_in + fan_out)).sqrt()).unwrap();
+                t.try_fill_with_distr(Uniform::new(-b, b))
+            }
+        }
+    }
+}
+
+fn normal<S: Shape, E, D: SampleTensor<E>, T>(
+    t: &mut Tensor<S, E, D, T>,
+    std: f64,
+) -> Result<(), D::Err>
+where
+    E: Dtype + Float,
+    StandardNormal: Distribution<E>,
+{
+    let std = E::from_f64(std).unwrap();
+    t.try_fill_with_distr(Normal::new(E::zero(), std).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tests::*};
+    use num_traits::ToPrimitive;
+
+    #[test]
+    fn test_nonlinearity_gain() {
+        assert_eq!(Nonlinearity::Linear.gain(), 1.0);
+        assert_eq!(Nonlinearity::ReLU.gain(), 2.0f64.sqrt());
+        assert_eq!(Nonlinearity::LeakyReLU(0.0).gain(), 2.0f64.sqrt());
+    }
+
+    #[test]
+    fn test_xavier_bounds() {
+        let dev: TestDevice = Default::default();
+        let mut t: Tensor<Rank2<20, 30>, TestDtype, _> = dev.zeros();
+        InitScheme::Xavier.fill(&mut t, 30, 20);
+        let b = (6.0f64 / 50.0).sqrt();
+        for v in t.as_vec() {
+            let v = v.to_f64().unwrap();
+            assert!(-b <= v && v <= b);
+        }
+    }
+}
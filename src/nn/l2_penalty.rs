@@ -0,0 +1,105 @@
+use super::tensor_collection::*;
+
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use std::string::String;
+
+struct L2Penalty<E: Dtype, D: Device<E>> {
+    total: Option<Tensor<Rank0, E, D, OwnedTape<E, D>>>,
+}
+
+impl<E: Dtype, D: Device<E>> TensorVisitor<E, D> for L2Penalty<E, D> {
+    type Viewer = (ViewTensorRef, ViewTensorName);
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        (t, _): (&Tensor<S, E, D>, String),
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if opts.do_gradient_update {
+            let sum_sq = t.retaped::<OwnedTape<E, D>>().try_sum_of_squares()?;
+            self.total = Some(match self.total.take() {
+                Some(total) => total.try_add(sum_sq)?,
+                None => sum_sq,
+            });
+        }
+        Ok(None)
+    }
+}
+
+/// Returns the sum of the squares of all of `model`'s trainable parameters, with a tape,
+/// so it can be added to a loss to apply an L2 penalty (weight decay) to every parameter.
+///
+/// Non-trainable tensors like the running statistics of [crate::nn::modules::BatchNorm1D]
+/// are skipped. A model without parameters has a penalty of `0.0` on `device`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<(Linear<2, 3>, Linear<3, 1>), f32>();
+/// let x: Tensor<Rank1<2>, f32, _> = dev.sample_normal();
+/// let loss = model.forward(x.trace(model.alloc_grads())).sum();
+/// let loss = loss + l2_penalty(&model, &dev) * 1e-4;
+/// let grads = loss.backward();
+/// ```
+pub fn l2_penalty<E: Dtype, D: Device<E>, M: TensorCollection<E, D>>(
+    model: &M,
+    device: &D,
+) -> Tensor<Rank0, E, D, OwnedTape<E, D>> {
+    try_l2_penalty(model, device).unwrap()
+}
+
+/// Fallible version of [l2_penalty]
+#[allow(clippy::type_complexity)]
+pub fn try_l2_penalty<E: Dtype, D: Device<E>, M: TensorCollection<E, D>>(
+    model: &M,
+    device: &D,
+) -> Result<Tensor<Rank0, E, D, OwnedTape<E, D>>, D::Err> {
+    let mut op = L2Penalty { total: None };
+    M::iter_tensors(&mut RecursiveWalker {
+        m: (model, String::new()),
+        f: &mut op,
+    })?;
+    match op.total {
+        Some(total) => Ok(total),
+        None => Ok(device.try_zeros()?.retaped()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, tests::*};
+
+    #[test]
+    fn test_l2_penalty_linear() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<(Linear<2, 3>, Linear<3, 1>), TestDtype>();
+
+        let penalty = l2_penalty(&model, &dev);
+        let expected = model.0.weight.clone().square().sum()
+            + model.0.bias.clone().square().sum()
+            + model.1.weight.clone().square().sum()
+            + model.1.bias.clone().square().sum();
+        assert_close_to_tensor!(penalty, expected);
+
+        let g = penalty.backward();
+        assert_close_to_tensor!(g.get(&model.0.weight), model.0.weight.clone() * 2.0);
+        assert_close_to_tensor!(g.get(&model.0.bias), model.0.bias.clone() * 2.0);
+        assert_close_to_tensor!(g.get(&model.1.weight), model.1.weight.clone() * 2.0);
+        assert_close_to_tensor!(g.get(&model.1.bias), model.1.bias.clone() * 2.0);
+    }
+
+    #[test]
+    fn test_l2_penalty_skips_non_trainable() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<BatchNorm1D<3>, TestDtype>();
+        // gamma is all ones and beta all zeros, running_var (also ones) is skipped
+        assert_close_to_literal!(l2_penalty(&model, &dev), 3.0);
+        let relu = dev.build_module::<ReLU, TestDtype>();
+        assert_close_to_literal!(l2_penalty::<TestDtype, _, _>(&relu, &dev), 0.0);
+    }
+}
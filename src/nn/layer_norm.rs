@@ -44,6 +44,34 @@ pub struct LayerNorm1D<const M: usize, E: Dtype, D: Storage<E>> {
 
 impl<const M: usize, E: Dtype, D: Storage<E>> NonMutableModule for LayerNorm1D<M, E, D> {}
 
+impl<const M: usize, E: Dtype, D: Device<E>> LayerNorm1D<M, E, D> {
+    /// Creates a [LayerNorm1D] with every element of [Self::gamma] set to `gamma` and
+    /// every element of [Self::beta] set to `beta`. Some architectures initialize `gamma`
+    /// to a small value instead of 1.
+    ///
+    /// Note that [crate::nn::ResetParams::reset_params()] always resets `gamma` to ones
+    /// and `beta` to zeros.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let m = modules::LayerNorm1D::<3, f32, _>::new(&dev, 0.1, 0.0);
+    /// assert_eq!(m.gamma.array(), [0.1; 3]);
+    /// ```
+    pub fn new(device: &D, gamma: E, beta: E) -> Self {
+        Self::try_new(device, gamma, beta).unwrap()
+    }
+
+    /// Fallible version of [LayerNorm1D::new].
+    pub fn try_new(device: &D, gamma: E, beta: E) -> Result<Self, D::Err> {
+        Ok(Self {
+            gamma: device.try_tensor_from_vec(std::vec![gamma; M], (Const,))?,
+            beta: device.try_tensor_from_vec(std::vec![beta; M], (Const,))?,
+            epsilon: 1e-5,
+        })
+    }
+}
+
 impl<const M: usize, E: Dtype, D: Device<E>> TensorCollection<E, D> for LayerNorm1D<M, E, D> {
     type To<E2: Dtype, D2: Device<E2>> = LayerNorm1D<M, E2, D2>;
 
@@ -94,10 +122,8 @@ impl<B: Dim, const M: usize, E: Dtype, D: Device<E>, T: Tape<E, D>>
     type Error = D::Err;
 
     fn try_forward(&self, x: Tensor<(B, Const<M>), E, D, T>) -> Result<Self::Output, D::Err> {
-        let shape = *x.shape();
         x.try_normalize::<Axis<1>>(self.epsilon)?
-            .try_mul(self.gamma.retaped::<T>().try_broadcast_like(&shape)?)?
-            .try_add(self.beta.retaped::<T>().try_broadcast_like(&shape)?)
+            .try_affine(&self.gamma, &self.beta)
     }
 }
 
@@ -108,17 +134,15 @@ impl<B: Dim, S: Dim, const M: usize, E: Dtype, D: Device<E>, T: Tape<E, D>>
     type Error = D::Err;
 
     fn try_forward(&self, x: Tensor<(B, S, Const<M>), E, D, T>) -> Result<Self::Output, D::Err> {
-        let shape = *x.shape();
         x.try_normalize::<Axis<2>>(self.epsilon)?
-            .try_mul(self.gamma.retaped::<T>().try_broadcast_like(&shape)?)?
-            .try_add(self.beta.retaped::<T>().try_broadcast_like(&shape)?)
+            .try_affine(&self.gamma, &self.beta)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::*;
+    use crate::{optim::*, tests::*};
 
     #[test]
     fn test_layer_norm_reset() {
@@ -140,6 +164,21 @@ mod tests {
         assert_close_to_literal!(m.beta, [0.0; 5]);
     }
 
+    #[test]
+    fn test_layer_norm_new() {
+        let dev: TestDevice = Default::default();
+        let gamma = TestDtype::from_f64(0.5).unwrap();
+        let beta = TestDtype::from_f64(0.25).unwrap();
+        let mut m = LayerNorm1D::<5, TestDtype, _>::new(&dev, gamma, beta);
+        assert_close_to_literal!(m.gamma, [0.5; 5]);
+        assert_close_to_literal!(m.beta, [0.25; 5]);
+        assert_eq!(m.epsilon, 1e-5);
+
+        m.reset_params();
+        assert_close_to_literal!(m.gamma, [1.0; 5]);
+        assert_close_to_literal!(m.beta, [0.0; 5]);
+    }
+
     #[test]
     fn test_layer_norm_1d_forward() {
         let dev: TestDevice = Default::default();
@@ -176,4 +215,21 @@ mod tests {
         );
         assert_close_to_literal!(g.get(&m.beta), [0.2; 5]);
     }
+
+    #[test]
+    fn test_update_changes_all_params() {
+        let dev: TestDevice = Default::default();
+        let mut m = dev.build_module::<builder::LayerNorm1D<5>, TestDtype>();
+        let before = m.clone();
+
+        let x: Tensor<Rank2<3, 5>, TestDtype, _> = dev.sample_normal();
+        let w: Tensor<Rank2<3, 5>, TestDtype, _> = dev.sample_normal();
+        let g = (m.forward(x.trace(m.alloc_grads())) * w)
+            .square()
+            .mean()
+            .backward();
+        let mut opt = Sgd::new(&m, Default::default());
+        opt.update(&mut m, &g).expect("");
+        assert_params_changed(&before, &m, &[]);
+    }
 }
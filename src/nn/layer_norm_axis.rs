@@ -0,0 +1,155 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::*;
+
+pub mod builder {
+    #[derive(Debug)]
+    pub struct LayerNormAxis<const M: usize, const AXIS: usize>;
+}
+
+impl<const M: usize, const AXIS: usize, E: Dtype, D: Device<E>> BuildOnDevice<D, E>
+    for builder::LayerNormAxis<M, AXIS>
+where
+    LayerNormAxis<M, AXIS, E, D>: BuildModule<D, E>,
+{
+    type Built = LayerNormAxis<M, AXIS, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, D::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// Layer normalization over axis `AXIS` instead of the last axis, e.g. over the channels
+/// of channel first images. Otherwise the same as [LayerNorm1D].
+///
+/// [Self::gamma] and [Self::beta] have shape `(M, )`, and are broadcast over every
+/// axis except `AXIS`. Inputs with 2 to 4 dimensions are supported.
+///
+/// # Generics
+/// - `M` The size of axis `AXIS`, and of the affine transform tensors.
+/// - `AXIS` The axis to normalize over.
+///
+/// # Examples
+/// Normalizing over the channels of a batch of images, separately for each pixel:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = LayerNormAxis<3, 1>;
+/// let model = dev.build_module::<Model, f32>();
+/// let _: Tensor<Rank4<10, 3, 8, 8>, f32, _> = model.forward(dev.zeros::<Rank4<10, 3, 8, 8>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LayerNormAxis<const M: usize, const AXIS: usize, E: Dtype, D: Storage<E>> {
+    pub gamma: Tensor<Rank1<M>, E, D>,
+    pub beta: Tensor<Rank1<M>, E, D>,
+    pub epsilon: f64,
+}
+
+impl<const M: usize, const AXIS: usize, E: Dtype, D: Storage<E>> NonMutableModule
+    for LayerNormAxis<M, AXIS, E, D>
+{
+}
+
+impl<const M: usize, const AXIS: usize, E: Dtype, D: Device<E>> TensorCollection<E, D>
+    for LayerNormAxis<M, AXIS, E, D>
+{
+    type To<E2: Dtype, D2: Device<E2>> = LayerNormAxis<M, AXIS, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            (
+                Self::tensor(
+                    "gamma",
+                    |s| &s.gamma,
+                    |s| &mut s.gamma,
+                    TensorOptions::reset_to_ones(),
+                ),
+                Self::tensor(
+                    "beta",
+                    |s| &s.beta,
+                    |s| &mut s.beta,
+                    TensorOptions::reset_to_zeros(),
+                ),
+            ),
+            |(gamma, beta)| LayerNormAxis {
+                gamma,
+                beta,
+                epsilon: 1e-5,
+            },
+        )
+    }
+}
+
+macro_rules! layer_norm_axis {
+    ([$($Dims:ident),*], $Shape:ty, $Ax:literal, $BroadcastAx:ty) => {
+        impl<$($Dims: Dim, )* const M: usize, E: Dtype, D: Device<E>, T: Tape<E, D>>
+            Module<Tensor<$Shape, E, D, T>> for LayerNormAxis<M, $Ax, E, D>
+        {
+            type Output = Tensor<$Shape, E, D, T>;
+            type Error = D::Err;
+
+            fn try_forward(&self, x: Tensor<$Shape, E, D, T>) -> Result<Self::Output, D::Err> {
+                let shape = x.shape;
+                let gamma = self.gamma.clone().try_broadcast_like::<_, $BroadcastAx>(&shape)?;
+                let beta = self.beta.clone().try_broadcast_like::<_, $BroadcastAx>(&shape)?;
+                x.try_normalize::<Axis<$Ax>>(self.epsilon)?
+                    .try_mul(gamma)?
+                    .try_add(beta)
+            }
+        }
+    };
+}
+
+layer_norm_axis!([A], (Const<M>, A), 0, Axis<1>);
+layer_norm_axis!([A], (A, Const<M>), 1, Axis<0>);
+layer_norm_axis!([A, B], (Const<M>, A, B), 0, Axes2<1, 2>);
+layer_norm_axis!([A, B], (A, Const<M>, B), 1, Axes2<0, 2>);
+layer_norm_axis!([A, B], (A, B, Const<M>), 2, Axes2<0, 1>);
+layer_norm_axis!([A, B, C], (Const<M>, A, B, C), 0, Axes3<1, 2, 3>);
+layer_norm_axis!([A, B, C], (A, Const<M>, B, C), 1, Axes3<0, 2, 3>);
+layer_norm_axis!([A, B, C], (A, B, Const<M>, C), 2, Axes3<0, 1, 3>);
+layer_norm_axis!([A, B, C], (A, B, C, Const<M>), 3, Axes3<0, 1, 2>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_layer_norm_channel_axis() {
+        let dev: TestDevice = Default::default();
+        let m = dev.build_module::<builder::LayerNormAxis<4, 1>, TestDtype>();
+        let x: Tensor<Rank4<2, 4, 3, 3>, TestDtype, _> = dev.sample_normal();
+        let y = m.forward(x);
+
+        // every (b, h, w) slice over the channels has 0 mean and unit variance
+        let mean: Tensor<Rank3<2, 3, 3>, _, _> = y.clone().mean();
+        assert_close_to_literal!(mean, [[[0.0; 3]; 3]; 2]);
+        let var: Tensor<Rank3<2, 3, 3>, _, _> = y.var();
+        assert_close_to_literal!(var, [[[1.0; 3]; 3]; 2], 1e-3);
+    }
+
+    #[test]
+    fn test_layer_norm_axis_matches_last_axis() {
+        let dev: TestDevice = Default::default();
+        let mut m = dev.build_module::<builder::LayerNormAxis<4, 1>, TestDtype>();
+        m.gamma = dev.sample_normal();
+        m.beta = dev.sample_normal();
+        let mut m1 = dev.build_module::<crate::nn::builders::LayerNorm1D<4>, TestDtype>();
+        m1.gamma = m.gamma.clone();
+        m1.beta = m.beta.clone();
+
+        let x: Tensor<Rank3<2, 4, 5>, TestDtype, _> = dev.sample_normal();
+        let g = m.forward(x.leaky_trace()).exp().sum().backward();
+
+        // the same as moving the channels last and using LayerNorm1D
+        let x_t = x.leaky_trace().permute::<Rank3<2, 5, 4>, _>();
+        let y_t = m1.forward(x_t).permute::<Rank3<2, 4, 5>, _>();
+        assert_close_to_tensor!(m.forward(x.clone()), y_t.retaped::<NoneTape>());
+        let g1 = y_t.exp().sum().backward();
+        assert_close_to_tensor!(g.get(&x), g1.get(&x));
+        assert_close_to_tensor!(g.get(&m.gamma), g1.get(&m1.gamma));
+        assert_close_to_tensor!(g.get(&m.beta), g1.get(&m1.beta));
+    }
+}
@@ -0,0 +1,158 @@
+use std::{string::String, vec::Vec};
+
+use crate::{
+    shapes::Dtype,
+    tensor::{PutTape, SplitTape},
+    tensor_ops::Device,
+};
+
+use super::*;
+
+/// Like [Repeated], runs `N` layers of type `T` in sequence, but each layer can be
+/// configured separately. Per-layer settings stored in the layers themselves
+/// (e.g. [Dropout::p]) can be changed through [LayeredStack::modules], and
+/// [LayeredStack::frozen] stops a layer from being trained.
+///
+/// A frozen layer runs without recording its operations on the tape, so its
+/// parameters get no gradients. Gradients also don't flow through it to the layers
+/// before it, so freeze layers starting from the first one (progressive freezing).
+/// Frozen layers always use [Module::forward()], so e.g. their dropout is disabled
+/// during training.
+///
+/// `frozen` isn't a tensor, so it is reset to all `false` by anything that rebuilds the
+/// module, like [ToDevice] or [ToDtype].
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = LayeredStack<(Linear<10, 10>, Dropout), 3>;
+/// let mut model = dev.build_module::<Model, f32>();
+/// model.modules[0].1.p = 0.1;
+/// model.modules[2].1.p = 0.5;
+/// model.frozen[0] = true;
+/// let x: Tensor<Rank1<10>, f32, _> = dev.zeros();
+/// let out = model.forward_mut(x.leaky_trace());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LayeredStack<T, const N: usize> {
+    pub modules: Vec<T>,
+    pub frozen: [bool; N],
+}
+
+impl<D: Device<E>, E: Dtype, T: BuildOnDevice<D, E>, const N: usize> BuildOnDevice<D, E>
+    for LayeredStack<T, N>
+{
+    type Built = LayeredStack<T::Built, N>;
+}
+
+impl<E: Dtype, D: Device<E>, T: TensorCollection<E, D>, const N: usize> TensorCollection<E, D>
+    for LayeredStack<T, N>
+{
+    type To<E2: Dtype, D2: Device<E2>> = LayeredStack<T::To<E2, D2>, N>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        let names: Vec<String> = (0..N).map(|i| format!("{i}")).collect();
+
+        visitor.visit_fields(
+            (0..N)
+                .zip(names.iter())
+                .map(|(i, name)| {
+                    Self::module(name, move |s| &s.modules[i], move |s| &mut s.modules[i])
+                })
+                .collect::<Vec<_>>(),
+            |modules| LayeredStack {
+                modules,
+                frozen: [false; N],
+            },
+        )
+    }
+}
+
+impl<T, const N: usize> std::ops::Index<usize> for LayeredStack<T, N> {
+    type Output = T;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.modules[index]
+    }
+}
+
+impl<Input: SplitTape, T, const N: usize> Module<Input> for LayeredStack<T, N>
+where
+    T: Module<Input, Output = Input>
+        + Module<Input::NoTape, Output = Input::NoTape, Error = <T as Module<Input>>::Error>,
+{
+    type Output = Input;
+    type Error = <T as Module<Input>>::Error;
+
+    fn try_forward(&self, mut x: Input) -> Result<Self::Output, Self::Error> {
+        for i in 0..N {
+            x = if self.frozen[i] {
+                let (x, tape) = x.split_tape();
+                crate::hooks::try_forward(&self.modules[i], x)?.put_tape(tape)
+            } else {
+                crate::hooks::try_forward(&self.modules[i], x)?
+            };
+        }
+        Ok(x)
+    }
+}
+
+impl<Input: SplitTape, T, const N: usize> ModuleMut<Input> for LayeredStack<T, N>
+where
+    T: ModuleMut<Input, Output = Input>
+        + Module<Input::NoTape, Output = Input::NoTape, Error = <T as ModuleMut<Input>>::Error>,
+{
+    type Output = Input;
+    type Error = <T as ModuleMut<Input>>::Error;
+
+    fn try_forward_mut(&mut self, mut x: Input) -> Result<Self::Output, Self::Error> {
+        for i in 0..N {
+            x = if self.frozen[i] {
+                let (x, tape) = x.split_tape();
+                crate::hooks::try_forward(&self.modules[i], x)?.put_tape(tape)
+            } else {
+                crate::hooks::try_forward_mut(&mut self.modules[i], x)?
+            };
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, tests::*};
+
+    #[test]
+    fn test_layers_configured_differently() {
+        let dev: TestDevice = Default::default();
+
+        type Model = LayeredStack<(Linear<3, 3>, Dropout), 2>;
+        let mut m = dev.build_module::<Model, TestDtype>();
+        assert_eq!(m.frozen, [false; 2]);
+        m.modules[0].1.p = 0.0;
+        m.modules[1].1.p = 0.99999;
+        m.frozen[0] = true;
+
+        // layers run in order, and the second layer drops everything
+        let x: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let y = m.forward_mut(x.leaky_trace());
+        assert_close_to_literal!(y, [0.0; 3]);
+
+        let y = m.forward(x.clone());
+        let expected = m.modules[1].forward(m.modules[0].forward(x.clone()));
+        assert_eq!(y.array(), expected.array());
+
+        // only the layer that isn't frozen gets gradients
+        m.modules[1].1.p = 0.0;
+        let g = m
+            .forward_mut(x.trace(m.alloc_grads()))
+            .square()
+            .sum()
+            .backward();
+        assert_eq!(g.get(&m.modules[0].0.weight).array(), [[0.0; 3]; 3]);
+        assert_ne!(g.get(&m.modules[1].0.weight).array(), [[0.0; 3]; 3]);
+    }
+}
@@ -94,6 +94,109 @@ impl<
     }
 }
 
+impl<const I: usize, const O: usize, E, D: Device<E>> Linear<I, O, E, D>
+where
+    E: Dtype + num_traits::Float + rand_distr::uniform::SampleUniform,
+    rand_distr::StandardNormal: rand_distr::Distribution<E>,
+{
+    /// Re-initializes only [Self::weight] with the default initialization, keeping
+    /// [Self::bias]. Useful after loading weights to partially re-initialize a model.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mut model = dev.build_module::<Linear<5, 2>, f32>();
+    /// let bias = model.bias.array();
+    /// model.reset_weight();
+    /// assert_eq!(model.bias.array(), bias);
+    /// ```
+    pub fn reset_weight(&mut self) {
+        self.try_reset_weight().unwrap()
+    }
+
+    /// Fallible version of [Linear::reset_weight].
+    pub fn try_reset_weight(&mut self) -> Result<(), D::Err> {
+        InitScheme::Uniform.try_fill(&mut self.weight, I, O)
+    }
+
+    /// Sets only [Self::bias] to zeros, keeping [Self::weight].
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mut model = dev.build_module::<Linear<5, 2>, f32>();
+    /// model.reset_bias();
+    /// assert_eq!(model.bias.array(), [0.0; 2]);
+    /// ```
+    pub fn reset_bias(&mut self) {
+        self.try_reset_bias().unwrap()
+    }
+
+    /// Fallible version of [Linear::reset_bias].
+    pub fn try_reset_bias(&mut self) -> Result<(), D::Err> {
+        self.bias.try_fill_with_zeros()
+    }
+}
+
+impl<const I: usize, const O: usize, E, D: Device<E>> Linear<I, O, E, D>
+where
+    E: Dtype + num_traits::Float + rand_distr::uniform::SampleUniform,
+    rand_distr::StandardNormal: rand_distr::Distribution<E>,
+{
+    /// Creates a [Linear] whose weight is initialized with `scheme`, using `I` as fan-in
+    /// and `O` as fan-out.
+    ///
+    /// [InitScheme::Uniform] matches the default initialization. For all other schemes the
+    /// bias is initialized to zeros.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let model = modules::Linear::<5, 2, f32, _>::new_with_init(
+    ///     &dev,
+    ///     InitScheme::Normal { std: 0.02 },
+    /// );
+    /// ```
+    pub fn new_with_init(device: &D, scheme: InitScheme) -> Self {
+        Self::try_new_with_init(device, scheme).unwrap()
+    }
+
+    /// Fallible version of [Linear::new_with_init].
+    pub fn try_new_with_init(device: &D, scheme: InitScheme) -> Result<Self, D::Err> {
+        let mut model = Self {
+            weight: device.try_zeros()?,
+            bias: device.try_zeros()?,
+        };
+        model.try_reset_params_with(scheme)?;
+        Ok(model)
+    }
+
+    /// Re-initializes an existing [Linear] with `scheme`, in the same way as
+    /// [Linear::new_with_init]. [crate::nn::ResetParams::reset_params] keeps using
+    /// the default initialization.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mut model = dev.build_module::<Linear<5, 2>, f32>();
+    /// model.reset_params_with(InitScheme::Xavier);
+    /// assert_eq!(model.bias.array(), [0.0; 2]);
+    /// ```
+    pub fn reset_params_with(&mut self, scheme: InitScheme) {
+        self.try_reset_params_with(scheme).unwrap()
+    }
+
+    /// Fallible version of [Linear::reset_params_with].
+    pub fn try_reset_params_with(&mut self, scheme: InitScheme) -> Result<(), D::Err> {
+        scheme.try_fill(&mut self.weight, I, O)?;
+        if scheme == InitScheme::Uniform {
+            scheme.try_fill(&mut self.bias, I, O)
+        } else {
+            self.bias.try_fill_with_zeros()
+        }
+    }
+}
+
 impl<const I: usize, const O: usize, E: Dtype, D: Device<E>, T> Module<T> for Linear<I, O, E, D>
 where
     T: SplitTape + TryMatMul<Tensor<Rank2<I, O>, E, D, T::Tape>> + HasErr<Err = D::Err>,
@@ -111,8 +214,8 @@ where
 }
 
 #[derive(Clone, Debug)]
-struct Bias1D<'a, const M: usize, E: Dtype, D: Storage<E>> {
-    beta: &'a Tensor<Rank1<M>, E, D>,
+pub(super) struct Bias1D<'a, const M: usize, E: Dtype, D: Storage<E>> {
+    pub(super) beta: &'a Tensor<Rank1<M>, E, D>,
 }
 
 impl<'a, const M: usize, E: Dtype, D: Device<E>, T: Tape<E, D>> Module<Tensor<Rank1<M>, E, D, T>>
@@ -156,7 +259,8 @@ impl<'a, B: Dim, S: Dim, const M: usize, E: Dtype, D: Device<E>, T: Tape<E, D>>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::*;
+    use crate::{optim::*, tests::*};
+    use num_traits::ToPrimitive;
 
     const W: [[f64; 5]; 2] = [
         [-0.3458893, -0.30371523, -0.3712057, 0.14303583, -0.0268966],
@@ -186,6 +290,72 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_linear_new_with_normal_init() {
+        let dev = TestDevice::seed_from_u64(0);
+        let m =
+            Linear::<256, 256, TestDtype, _>::new_with_init(&dev, InitScheme::Normal { std: 0.02 });
+        let stats = m.weight.summary();
+        assert!(stats.mean.to_f64().unwrap().abs() < 1e-3);
+        assert!((stats.std.to_f64().unwrap() - 0.02).abs() < 1e-3);
+        assert_eq!(m.bias.array(), [TestDtype::zero(); 256]);
+    }
+
+    #[test]
+    fn test_linear_reset_params_with_std() {
+        let dev = TestDevice::seed_from_u64(0);
+        let mut m = dev.build_module::<builder::Linear<512, 512>, TestDtype>();
+
+        let relu = Nonlinearity::ReLU;
+        let schemes = [
+            (InitScheme::Xavier, (2.0 / 1024.0f64).sqrt()),
+            (
+                InitScheme::Kaiming {
+                    mode: FanMode::FanIn,
+                    nonlinearity: relu,
+                },
+                relu.gain() / 512.0f64.sqrt(),
+            ),
+        ];
+        for (scheme, expected_std) in schemes {
+            m.reset_params_with(scheme);
+            let stats = m.weight.summary();
+            assert!(stats.mean.to_f64().unwrap().abs() < 1e-3);
+            let std = stats.std.to_f64().unwrap();
+            assert!(
+                (std - expected_std).abs() < 0.05 * expected_std,
+                "{scheme:?}: std {std} != {expected_std}"
+            );
+            assert_eq!(m.bias.array(), [TestDtype::zero(); 512]);
+        }
+    }
+
+    #[test]
+    fn test_linear_reset_bias_and_weight() {
+        let dev: TestDevice = Default::default();
+        let mut m = dev.build_module::<builder::Linear<5, 2>, TestDtype>();
+        m.weight = dev.tensor(W).to_dtype::<TestDtype>();
+        m.bias = dev.tensor(B).to_dtype::<TestDtype>();
+
+        m.reset_bias();
+        assert_eq!(m.bias.array(), [TestDtype::zero(); 2]);
+        assert_eq!(
+            m.weight.array(),
+            dev.tensor(W).to_dtype::<TestDtype>().array()
+        );
+
+        m.reset_weight();
+        assert_ne!(
+            m.weight.array(),
+            dev.tensor(W).to_dtype::<TestDtype>().array()
+        );
+        let bound: TestDtype = NumCast::from((1.0 / 5.0f64).sqrt()).unwrap();
+        for v in m.weight.as_vec() {
+            assert!(-bound <= v && v <= bound);
+        }
+        assert_eq!(m.bias.array(), [TestDtype::zero(); 2]);
+    }
+
     #[test]
     fn test_forward_1d() {
         let dev: TestDevice = Default::default();
@@ -213,6 +383,24 @@ mod tests {
         assert_close_to_literal!(g.get(&model.bias), [-0.93430865, 0.08624211]);
     }
 
+    #[test]
+    fn test_forward_1d_matches_batch_of_1() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<builder::Linear<5, 3>, TestDtype>();
+        let x: Tensor<Rank1<5>, TestDtype, _> = dev.sample_normal();
+        let x2: Tensor<Rank2<1, 5>, TestDtype, _> = x.clone().broadcast();
+
+        let y = model.forward(x.leaky_trace());
+        let y2 = model.forward(x2.leaky_trace());
+        assert_close_to_tensor!(y.retaped::<NoneTape>().broadcast::<Rank2<1, 3>, _>(), y2);
+
+        let g = y.exp().sum().backward();
+        let g2 = y2.exp().sum().backward();
+        assert_close_to_tensor!(g.get(&model.weight), g2.get(&model.weight));
+        assert_close_to_tensor!(g.get(&model.bias), g2.get(&model.bias));
+        assert_close_to_tensor!(g.get(&x).broadcast::<Rank2<1, 5>, _>(), g2.get(&x2));
+    }
+
     #[test]
     fn test_forward_2d() {
         let dev: TestDevice = Default::default();
@@ -291,4 +479,21 @@ mod tests {
         );
         assert_close_to_literal!(g.get(&model.bias), [0.40265593, -0.2874091]);
     }
+
+    #[test]
+    fn test_update_changes_all_params() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<builder::Linear<5, 3>, TestDtype>();
+        let before = model.clone();
+
+        let x: Tensor<Rank2<4, 5>, TestDtype, _> = dev.sample_normal();
+        let g = model
+            .forward(x.trace(model.alloc_grads()))
+            .square()
+            .mean()
+            .backward();
+        let mut opt = Sgd::new(&model, Default::default());
+        opt.update(&mut model, &g).expect("");
+        assert_params_changed(&before, &model, &[]);
+    }
 }
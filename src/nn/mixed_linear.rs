@@ -0,0 +1,118 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{modules::Linear, *};
+
+/// An inference only [Linear] that stores its weight and bias in the storage dtype `S`,
+/// and computes in `E`. Create one with [MixedPrecisionLinear::from_linear()].
+///
+/// On every forward the parameters are converted to `E` before running the same
+/// computation as [Linear], so e.g. f16 storage halves the memory of an f32 model while
+/// keeping the matmul in f32.
+///
+/// This has no [TensorCollection] impl, so it can't be trained, saved, or reset.
+///
+/// This is a separate type rather than a compute dtype parameter on [Linear], because
+/// [TensorCollection], the optimizers, and the builders all tie a module's parameters to
+/// the single dtype they are computed in. A second dtype on [Linear] would change the
+/// type of every model containing one, only to support inference.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::{prelude::*, nn::modules::MixedPrecisionLinear};
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<Linear<5, 2>, f64>();
+/// let mixed: MixedPrecisionLinear<5, 2, f32, f64, _> = MixedPrecisionLinear::from_linear(&model);
+/// let _: Tensor<Rank2<10, 2>, f64, _> = mixed.forward(dev.zeros::<Rank2<10, 5>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MixedPrecisionLinear<const I: usize, const O: usize, S: Dtype, E: Dtype, D: Storage<S>> {
+    /// Transposed weight matrix, shape (O, I), stored as `S`.
+    pub weight: Tensor<Rank2<O, I>, S, D>,
+
+    /// Bias vector, shape (O, ), stored as `S`.
+    pub bias: Tensor<Rank1<O>, S, D>,
+
+    marker: std::marker::PhantomData<E>,
+}
+
+impl<const I: usize, const O: usize, S: Dtype, E: Dtype, D> MixedPrecisionLinear<I, O, S, E, D>
+where
+    D: Device<E> + ToDtypeKernel<E, S> + ToDtypeKernel<S, E>,
+{
+    /// Converts the parameters of `linear` to the storage dtype `S`.
+    pub fn from_linear(linear: &Linear<I, O, E, D>) -> Self {
+        Self::try_from_linear(linear).unwrap()
+    }
+
+    /// Fallible version of [MixedPrecisionLinear::from_linear].
+    pub fn try_from_linear(linear: &Linear<I, O, E, D>) -> Result<Self, D::Err> {
+        Ok(Self {
+            weight: linear.weight.clone().try_to_dtype()?,
+            bias: linear.bias.clone().try_to_dtype()?,
+            marker: Default::default(),
+        })
+    }
+
+    /// Converts the parameters back to the compute dtype `E`, returning an equivalent [Linear].
+    pub fn to_linear(&self) -> Linear<I, O, E, D> {
+        self.try_to_linear().unwrap()
+    }
+
+    /// Fallible version of [MixedPrecisionLinear::to_linear].
+    pub fn try_to_linear(&self) -> Result<Linear<I, O, E, D>, D::Err> {
+        Ok(Linear {
+            weight: self.weight.clone().try_to_dtype()?,
+            bias: self.bias.clone().try_to_dtype()?,
+        })
+    }
+}
+
+impl<const I: usize, const O: usize, S: Dtype, E: Dtype, D, T> Module<T>
+    for MixedPrecisionLinear<I, O, S, E, D>
+where
+    D: Device<E> + ToDtypeKernel<E, S> + ToDtypeKernel<S, E>,
+    Linear<I, O, E, D>: Module<T, Error = D::Err>,
+{
+    type Output = <Linear<I, O, E, D> as Module<T>>::Output;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: T) -> Result<Self::Output, D::Err> {
+        self.try_to_linear()?.try_forward(x)
+    }
+}
+
+impl<const I: usize, const O: usize, S: Dtype, E: Dtype, D: Storage<S>> NonMutableModule
+    for MixedPrecisionLinear<I, O, S, E, D>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders, tests::*};
+
+    #[test]
+    fn test_mixed_linear_matches_linear() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<builders::Linear<16, 8>, f64>();
+        let mixed: MixedPrecisionLinear<16, 8, f32, f64, _> =
+            MixedPrecisionLinear::from_linear(&model);
+
+        let x: Tensor<Rank2<4, 16>, f64, _> = dev.sample_normal();
+        let y = mixed.forward(x.clone());
+        assert_close_to_tensor!(y, model.forward(x), 1e-6);
+    }
+
+    #[cfg(feature = "f16")]
+    #[test]
+    fn test_f16_stored_linear_matches_f32() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<builders::Linear<16, 8>, f32>();
+        let mixed: MixedPrecisionLinear<16, 8, half::f16, f32, _> =
+            MixedPrecisionLinear::from_linear(&model);
+
+        let x: Tensor<Rank2<4, 16>, f32, _> = dev.sample_normal();
+        let y = mixed.forward(x.clone());
+        assert_close_to_tensor!(y, model.forward(x), 1e-2);
+    }
+}
@@ -188,35 +188,52 @@ mod add_into;
 mod batchnorm1d;
 mod batchnorm2d;
 mod bias2d;
+mod checkpointed;
 #[cfg(feature = "nightly")]
 mod conv;
 mod convtrans;
+mod depthwise_conv1d;
 mod dropout;
 mod ema;
 mod embedding;
 mod flatten;
+mod fn_module;
 mod generalized_residual;
+mod grad_norms;
 mod impl_module_for_tuples;
+mod init;
+mod l2_penalty;
 mod layer_norm;
+mod layer_norm_axis;
+mod layered_stack;
 mod linear;
+mod mixed_linear;
+mod moe;
 #[cfg(feature = "numpy")]
 mod npz;
+mod optional;
+mod per_sample_grads;
 mod pool2d;
 mod pool_global;
+mod positional_encoding;
 pub mod prelu;
+mod quantized_linear;
 mod repeated;
 mod reshape;
 mod residual;
 #[cfg(feature = "safetensors")]
 mod safetensors;
+mod shared;
+mod spectral_norm;
 mod split_into;
 mod transformer;
 mod unbiased_linear;
 mod upscale;
+mod weight_norm;
 
 pub use module::{
-    BuildModule, BuildOnDevice, DeviceBuildExt, Module, ModuleMut, NonMutableModule,
-    ZeroSizedModule,
+    BuildModule, BuildOnDevice, DeviceBuildExt, ForwardRealized, Module, ModuleMut,
+    NonMutableModule, ZeroSizedModule,
 };
 
 pub use tensor_collection::*;
@@ -224,12 +241,21 @@ pub use tensor_collection::*;
 #[cfg(feature = "safetensors")]
 pub use self::safetensors::{LoadFromSafetensors, SaveToSafetensors};
 pub use ema::ModelEMA;
+pub use grad_norms::{
+    centralize_grads, clip_grad_norm, grad_norms_by_name, try_centralize_grads, try_clip_grad_norm,
+    try_grad_norms_by_name,
+};
+pub use init::{FanMode, InitScheme, Nonlinearity};
+pub use l2_penalty::{l2_penalty, try_l2_penalty};
 #[cfg(feature = "numpy")]
 pub use npz::{LoadFromNpz, SaveToNpz};
 pub use num_params::NumParams;
+pub use per_sample_grads::PerSampleGrads;
+pub use quantized_linear::quantize_linear;
 pub use reset_params::ResetParams;
 pub use to_device::ToDevice;
 pub use to_dtype::ToDtype;
+pub use transformer::{attention_entropy, try_attention_entropy, AttentionMode, CausalMask};
 pub use zero_grads::ZeroGrads;
 
 pub mod modules {
@@ -241,31 +267,43 @@ pub mod modules {
     pub use super::batchnorm1d::BatchNorm1D;
     pub use super::batchnorm2d::BatchNorm2D;
     pub use super::bias2d::Bias2D;
+    pub use super::checkpointed::Checkpointed;
     #[cfg(feature = "nightly")]
     pub use super::conv::Conv2D;
     #[cfg(feature = "nightly")]
     pub use super::convtrans::ConvTrans2D;
+    pub use super::depthwise_conv1d::DepthwiseConv1D;
     pub use super::dropout::{Dropout, DropoutOneIn};
-    pub use super::embedding::Embedding;
+    pub use super::embedding::{BatchedTokenIds, Embedding, TokenIds};
     #[cfg(feature = "nightly")]
     pub use super::flatten::Flatten2D;
+    pub use super::fn_module::FnModule;
     pub use super::generalized_residual::GeneralizedResidual;
     pub use super::layer_norm::LayerNorm1D;
+    pub use super::layer_norm_axis::LayerNormAxis;
+    pub use super::layered_stack::LayeredStack;
     pub use super::linear::Linear;
+    pub use super::mixed_linear::MixedPrecisionLinear;
+    pub use super::moe::MoEFeedForward;
     #[cfg(feature = "nightly")]
     pub use super::pool2d::{AvgPool2D, MaxPool2D, MinPool2D};
     pub use super::pool_global::{AvgPoolGlobal, MaxPoolGlobal, MinPoolGlobal};
+    pub use super::positional_encoding::PositionalEncoding;
+    pub use super::quantized_linear::QuantizedLinear;
     pub use super::repeated::Repeated;
     pub use super::residual::Residual;
+    pub use super::shared::Shared;
+    pub use super::spectral_norm::SpectralNorm;
     pub use super::split_into::SplitInto;
     pub use super::transformer::{
-        MultiHeadAttention, Transformer, TransformerDecoder, TransformerDecoderBlock,
-        TransformerEncoder, TransformerEncoderBlock,
+        CrossAttention, MaskedMultiHeadAttention, MultiHeadAttention, Transformer,
+        TransformerDecoder, TransformerDecoderBlock, TransformerEncoder, TransformerEncoderBlock,
     };
     pub use super::unbiased_linear::UnbiasedLinear;
     pub use super::upscale::Upscale2D;
     #[cfg(feature = "nightly")]
     pub use super::upscale::Upscale2DBy;
+    pub use super::weight_norm::WeightNorm;
     pub use super::*;
 }
 
@@ -277,32 +315,45 @@ pub mod builders {
     pub use super::batchnorm1d::builder::BatchNorm1D;
     pub use super::batchnorm2d::builder::BatchNorm2D;
     pub use super::bias2d::builder::Bias2D;
+    pub use super::checkpointed::Checkpointed;
     #[cfg(feature = "nightly")]
     pub use super::conv::builder::Conv2D;
     #[cfg(feature = "nightly")]
     pub use super::convtrans::builder::ConvTrans2D;
+    pub use super::depthwise_conv1d::builder::DepthwiseConv1D;
     pub use super::dropout::{Dropout, DropoutOneIn};
     pub use super::embedding::builder::Embedding;
+    pub use super::embedding::{BatchedTokenIds, TokenIds};
     #[cfg(feature = "nightly")]
     pub use super::flatten::Flatten2D;
+    pub use super::fn_module::FnModule;
     pub use super::generalized_residual::GeneralizedResidual;
     pub use super::layer_norm::builder::LayerNorm1D;
+    pub use super::layer_norm_axis::builder::LayerNormAxis;
+    pub use super::layered_stack::LayeredStack;
     pub use super::linear::builder::Linear;
+    pub use super::moe::builder::MoEFeedForward;
     #[cfg(feature = "nightly")]
     pub use super::pool2d::{AvgPool2D, MaxPool2D, MinPool2D};
     pub use super::pool_global::{AvgPoolGlobal, MaxPoolGlobal, MinPoolGlobal};
+    pub use super::positional_encoding::PositionalEncoding;
     pub use super::prelu::builder::{PReLU, PReLU1D};
     pub use super::repeated::Repeated;
     pub use super::reshape::Reshape;
     pub use super::residual::Residual;
+    pub use super::shared::Shared;
+    pub use super::spectral_norm::builder::SpectralNorm;
     pub use super::split_into::SplitInto;
     pub use super::transformer::builder::{
-        MultiHeadAttention, Transformer, TransformerDecoder, TransformerDecoderBlock,
-        TransformerEncoder, TransformerEncoderBlock,
+        CrossAttention, MaskedMultiHeadAttention, MultiHeadAttention, Transformer,
+        TransformerDecoder, TransformerDecoderBlock, TransformerEncoder, TransformerEncoderBlock,
     };
+    #[cfg(feature = "nightly")]
+    pub use super::transformer::builder::{TransformerEncoder4x, TransformerEncoderBlock4x};
     pub use super::unbiased_linear::builder::UnbiasedLinear;
     pub use super::upscale::Upscale2D;
     #[cfg(feature = "nightly")]
     pub use super::upscale::Upscale2DBy;
+    pub use super::weight_norm::builder::WeightNorm;
     pub use super::*;
 }
@@ -1,7 +1,11 @@
 pub use super::build_module::BuildModule;
 pub use super::to_device::*;
 
-use crate::{shapes::Dtype, tensor_ops::Device};
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{Storage, Tape, Tensor},
+    tensor_ops::{Device, RealizeTo},
+};
 
 use super::tensor_collection::*;
 
@@ -18,7 +22,7 @@ pub trait Module<Input> {
     ///
     /// **See [ModuleMut::forward_mut()] for version that can mutate `self`.**
     fn forward(&self, input: Input) -> Self::Output {
-        self.try_forward(input).unwrap()
+        crate::hooks::try_forward(self, input).unwrap()
     }
 }
 
@@ -35,7 +39,7 @@ pub trait ModuleMut<Input> {
     ///
     /// **See [Module::forward()] for immutable version**
     fn forward_mut(&mut self, input: Input) -> Self::Output {
-        self.try_forward_mut(input).unwrap()
+        crate::hooks::try_forward_mut(self, input).unwrap()
     }
 }
 
@@ -106,3 +110,60 @@ where
         self.try_forward(input)
     }
 }
+
+/// Forward a tensor whose shape is only known at runtime (e.g. `(usize, usize)` from a
+/// data loader) through a module that expects the shape `Dst`.
+///
+/// The input is converted with [RealizeTo::try_realize()]. If its dimensions don't match
+/// `Dst`, the original tensor is returned as the error. Implemented for all modules.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<Linear<3, 2>, f32>();
+/// let x: Tensor<(usize, usize), f32, _> = dev.zeros_like(&(4, 3));
+/// let y: Tensor<(usize, Const<2>), f32, _> = model
+///     .forward_realized::<(usize, Const<3>)>(x)
+///     .unwrap();
+///
+/// let x: Tensor<(usize, usize), f32, _> = dev.zeros_like(&(4, 5));
+/// assert!(model.forward_realized::<(usize, Const<3>)>(x).is_err());
+/// ```
+pub trait ForwardRealized<Src: Shape, E, D: Storage<E>, T: Tape<E, D>> {
+    #[allow(clippy::type_complexity)]
+    fn forward_realized<Dst: Shape<Concrete = Src::Concrete>>(
+        &self,
+        x: Tensor<Src, E, D, T>,
+    ) -> Result<<Self as Module<Tensor<Dst, E, D, T>>>::Output, Tensor<Src, E, D, T>>
+    where
+        Self: Module<Tensor<Dst, E, D, T>>,
+    {
+        let x = x.try_realize::<Dst>()?;
+        Ok(self.forward(x))
+    }
+}
+
+impl<M, Src: Shape, E, D: Storage<E>, T: Tape<E, D>> ForwardRealized<Src, E, D, T> for M {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, shapes::*, tensor::*, tests::*};
+
+    #[test]
+    fn test_forward_realized() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<Linear<3, 2>, TestDtype>();
+
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        let x_dyn = x.clone().realize::<(usize, usize)>();
+        let y = model.forward_realized::<Rank2<4, 3>>(x_dyn).unwrap();
+        assert_eq!(y.array(), model.forward(x).array());
+
+        let x_dyn: Tensor<(usize, usize), TestDtype, _> = dev.zeros_like(&(4, 5));
+        let err = model
+            .forward_realized::<(usize, Const<3>)>(x_dyn)
+            .unwrap_err();
+        assert_eq!(err.shape, (4, 5));
+    }
+}
@@ -0,0 +1,282 @@
+use std::{format, string::String, vec::Vec};
+
+use num_traits::Float;
+use rand_distr::uniform::SampleUniform;
+
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{
+    modules::{Linear, ReLU},
+    *,
+};
+
+pub mod builder {
+    #[derive(Debug, Clone)]
+    pub struct MoEFeedForward<const M: usize, const I: usize, const NUM_EXPERTS: usize>;
+}
+
+impl<const M: usize, const I: usize, const X: usize, E: Dtype, D: Device<E>> BuildOnDevice<D, E>
+    for builder::MoEFeedForward<M, I, X>
+where
+    MoEFeedForward<M, I, X, E, D>: BuildModule<D, E>,
+{
+    type Built = MoEFeedForward<M, I, X, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+type Expert<const M: usize, const I: usize, E, D> = (Linear<M, I, E, D>, ReLU, Linear<I, M, E, D>);
+
+/// A mixture of experts feedforward layer with top-1 (switch) routing.
+///
+/// Each token is passed through a learned gate `Linear<M, NUM_EXPERTS>` followed by softmax,
+/// and is then routed to the single expert with the highest probability. The output for that
+/// token is the output of the selected expert scaled by its gate probability, so gradients
+/// only flow into the gate and the experts that were selected for at least one token.
+///
+/// Routing is decided on the host, so the gate probabilities are copied off the device
+/// on every forward.
+///
+/// Generics:
+/// - `M`: The size of the input & output of each token.
+/// - `I`: The hidden size of each expert.
+/// - `NUM_EXPERTS`: The number of `(Linear<M, I>, ReLU, Linear<I, M>)` experts.
+///
+/// Both unbatched `(S, M)` and batched `(B, S, M)` inputs are supported.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let moe = dev.build_module::<MoEFeedForward<8, 16, 4>, f32>();
+/// let x: Tensor<Rank3<2, 5, 8>, f32, _> = dev.sample_normal();
+/// let _: Tensor<Rank3<2, 5, 8>, f32, _> = moe.forward(x);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MoEFeedForward<
+    const M: usize,
+    const I: usize,
+    const NUM_EXPERTS: usize,
+    E: Dtype,
+    D: Storage<E>,
+> {
+    pub gate: Linear<M, NUM_EXPERTS, E, D>,
+    pub experts: Vec<Expert<M, I, E, D>>,
+}
+
+impl<const M: usize, const I: usize, const X: usize, E, D: Device<E>> TensorCollection<E, D>
+    for MoEFeedForward<M, I, X, E, D>
+where
+    E: Dtype + Float + SampleUniform,
+{
+    type To<E2: Dtype, D2: Device<E2>> = MoEFeedForward<M, I, X, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        let names: Vec<String> = (0..X).map(|i| format!("experts.{i}")).collect();
+
+        visitor.visit_fields(
+            (
+                Self::module("gate", |s| &s.gate, |s| &mut s.gate),
+                (0..X)
+                    .zip(names.iter())
+                    .map(|(i, name)| {
+                        Self::module(name, move |s| &s.experts[i], move |s| &mut s.experts[i])
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            |(gate, experts)| MoEFeedForward { gate, experts },
+        )
+    }
+}
+
+impl<const M: usize, const I: usize, const X: usize, E, D> MoEFeedForward<M, I, X, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+{
+    /// Returns the index of the expert each token is routed to. `x` has shape `(S, M)`.
+    pub fn route<S: Dim, T: Tape<E, D>>(&self, x: &Tensor<(S, Const<M>), E, D, T>) -> Vec<usize> {
+        let logits = self.gate.forward(x.retaped::<NoneTape>());
+        top1::<X, E>(&logits.as_vec())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn try_route_tokens<T: Tape<E, D>>(
+        &self,
+        x: Tensor<(usize, Const<M>), E, D, T>,
+    ) -> Result<Tensor<(usize, Const<M>), E, D, T>, D::Err> {
+        let n = x.shape.0;
+        assert!(n > 0, "MoEFeedForward requires at least one token");
+        let dev = x.device.clone();
+        let (x, tape) = x.split_tape();
+
+        let probs = self
+            .gate
+            .try_forward(x.clone().put_tape(tape))?
+            .try_softmax::<Axis<1>>()?;
+        let route = top1::<X, E>(&probs.as_vec());
+        let probs = probs.try_select(dev.try_tensor_from_vec(route.clone(), (n,))?)?;
+
+        // run each expert on only the tokens routed to it, then undo the permutation
+        let mut order = Vec::with_capacity(n);
+        let mut out: Option<Tensor<(usize, Const<M>), E, D, T>> = None;
+        for (j, expert) in self.experts.iter().enumerate() {
+            let idx: Vec<usize> = (0..n).filter(|&i| route[i] == j).collect();
+            if idx.is_empty() {
+                continue;
+            }
+            let len = idx.len();
+            order.extend_from_slice(&idx);
+            let x_j = x
+                .clone()
+                .retaped::<T>()
+                .try_gather(dev.try_tensor_from_vec(idx, (len,))?)?;
+            let y_j = expert.try_forward(x_j)?;
+            out = Some(match out {
+                None => y_j,
+                Some(out) => (out, y_j).try_concat_along(Axis::<0>)?,
+            });
+        }
+
+        let mut inverse = std::vec![0; n];
+        for (k, &i) in order.iter().enumerate() {
+            inverse[i] = k;
+        }
+        let out = out
+            .unwrap()
+            .try_gather(dev.try_tensor_from_vec(inverse, (n,))?)?;
+        let probs = probs.try_broadcast_like(out.shape())?;
+        out.try_mul(probs)
+    }
+}
+
+/// Index of the max value in each row of length `X`.
+fn top1<const X: usize, E: Dtype + Float>(data: &[E]) -> Vec<usize> {
+    data.chunks(X)
+        .map(|row| {
+            let mut best = 0;
+            for (i, v) in row.iter().enumerate() {
+                if *v > row[best] {
+                    best = i;
+                }
+            }
+            best
+        })
+        .collect()
+}
+
+impl<const M: usize, const I: usize, const X: usize, E, D, S, T>
+    Module<Tensor<(S, Const<M>), E, D, T>> for MoEFeedForward<M, I, X, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    S: Dim,
+    T: Tape<E, D>,
+{
+    type Output = Tensor<(S, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<(S, Const<M>), E, D, T>) -> Result<Self::Output, D::Err> {
+        let shape = *x.shape();
+        let x = x.try_reshape_like(&(shape.0.size(), Const::<M>))?;
+        self.try_route_tokens(x)?.try_reshape_like(&shape)
+    }
+}
+
+impl<const M: usize, const I: usize, const X: usize, E, D, B, S, T>
+    Module<Tensor<(B, S, Const<M>), E, D, T>> for MoEFeedForward<M, I, X, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    B: Dim,
+    S: Dim,
+    T: Tape<E, D>,
+{
+    type Output = Tensor<(B, S, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<(B, S, Const<M>), E, D, T>) -> Result<Self::Output, D::Err> {
+        let shape = *x.shape();
+        let x = x.try_reshape_like(&(shape.0.size() * shape.1.size(), Const::<M>))?;
+        self.try_route_tokens(x)?.try_reshape_like(&shape)
+    }
+}
+
+impl<const M: usize, const I: usize, const X: usize, E: Dtype, D: Device<E>> NonMutableModule
+    for MoEFeedForward<M, I, X, E, D>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_moe_routing_and_gradients() {
+        let dev: TestDevice = Default::default();
+
+        let mut moe = dev.build_module::<builder::MoEFeedForward<4, 8, 3>, TestDtype>();
+        // token i is routed to expert i
+        moe.gate.weight = dev
+            .tensor([
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+            ])
+            .to_dtype::<TestDtype>();
+        moe.gate.bias = dev.zeros();
+
+        let x = dev
+            .tensor([[5.0, 0.0, 0.0, 1.0], [0.0, 5.0, 0.0, -1.0]])
+            .to_dtype::<TestDtype>();
+        assert_eq!(moe.route(&x), [0, 1]);
+
+        let y = moe.forward(x.leaky_trace());
+
+        // each row is the selected expert's output scaled by the gate probability
+        let p = moe.gate.forward(x.clone()).softmax::<Axis<1>>().array();
+        let y0 = moe.experts[0].forward(x.clone()) * p[0][0];
+        let y1 = moe.experts[1].forward(x.clone()) * p[1][1];
+        let y = y.retaped::<NoneTape>();
+        let y_arr = y.array();
+        assert_close!(y_arr[0], y0.array()[0]);
+        assert_close!(y_arr[1], y1.array()[1]);
+
+        let g = moe.forward(x.leaky_trace()).square().mean().backward();
+        let zeros: [[TestDtype; 4]; 8] = [[TestDtype::default(); 4]; 8];
+        assert_ne!(
+            g.get(&moe.gate.weight).array(),
+            [[TestDtype::default(); 4]; 3]
+        );
+        assert_ne!(g.get(&moe.experts[0].0.weight).array(), zeros);
+        assert_ne!(g.get(&moe.experts[1].0.weight).array(), zeros);
+        assert!(g.get_ref_checked(&moe.experts[2].0.weight).is_none());
+        assert!(g.get_ref_checked(&moe.experts[2].2.weight).is_none());
+    }
+
+    #[test]
+    fn test_moe_batched() {
+        let dev: TestDevice = Default::default();
+        let moe = dev.build_module::<builder::MoEFeedForward<4, 8, 3>, TestDtype>();
+        let x: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let y = moe.forward(x.clone());
+        for i in 0..2 {
+            let x_i = x.clone().select(dev.tensor(i));
+            assert_close_to_tensor!(y.clone().select(dev.tensor(i)), moe.forward(x_i));
+        }
+    }
+
+    #[test]
+    fn test_moe_num_params() {
+        let dev: TestDevice = Default::default();
+        let moe = dev.build_module::<builder::MoEFeedForward<4, 8, 3>, TestDtype>();
+        assert_eq!(
+            moe.num_trainable_params(),
+            4 * 3 + 3 + 3 * (4 * 8 + 8 + 8 * 4 + 4)
+        );
+    }
+}
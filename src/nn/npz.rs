@@ -332,4 +332,39 @@ mod tests {
         let y2 = loaded.forward_mut((src.clone(), tgt.clone()));
         assert_eq!(y1.array(), y2.array());
     }
+
+    #[test]
+    fn test_save_load_encoder() {
+        let dev: TestDevice = Default::default();
+        type Model = TransformerEncoder<8, 2, 16, 2>;
+
+        let mut saved = Model::build_on_device(&dev);
+        saved.reset_params();
+
+        let file = NamedTempFile::new().expect("failed to create tempfile");
+        saved.save(file.path()).expect("");
+
+        // every parameter is stored under its dotted field path
+        let f = std::fs::File::open(file.path()).unwrap();
+        let zip = zip::ZipArchive::new(f).unwrap();
+        let names: std::vec::Vec<&str> = zip.file_names().collect();
+        for name in [
+            "0.self_attn.w_q.weight.npy",
+            "0.self_attn.w_o.bias.npy",
+            "0.norm1.gamma.npy",
+            "0.ff.0.0.weight.npy",
+            "1.ff.0.2.bias.npy",
+            "1.norm2.beta.npy",
+        ] {
+            assert!(names.contains(&name), "{name} not in {names:?}");
+        }
+
+        let mut loaded = Model::build_on_device(&dev);
+        let x: Tensor<Rank3<2, 5, 8>, TestDtype, _> = dev.sample_normal();
+        let y1 = saved.forward(x.clone());
+        assert_ne!(y1.array(), loaded.forward(x.clone()).array());
+
+        loaded.load(file.path()).expect("");
+        assert_eq!(y1.array(), loaded.forward(x).array());
+    }
 }
@@ -0,0 +1,62 @@
+use super::module::{Module, ModuleMut};
+
+/// An optional layer: `Some(m)` calls `m`, and `None` returns the input unchanged. This
+/// requires that the module's output is the same type as its input.
+///
+/// `Option` doesn't implement [super::TensorCollection], since which fields a module has
+/// has to be known from its type. Build the layer separately and put it in a tuple to
+/// use it as part of a model:
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let use_norm = false;
+/// let norm = use_norm.then(|| dev.build_module::<LayerNorm1D<3>, f32>());
+/// let model = (dev.build_module::<Linear<2, 3>, f32>(), norm);
+/// let y = model.forward(dev.tensor([1.0, 2.0]));
+/// assert_eq!(y.array(), model.0.forward(dev.tensor([1.0, 2.0])).array());
+/// ```
+impl<T, M: Module<T, Output = T>> Module<T> for Option<M> {
+    type Output = T;
+    type Error = M::Error;
+
+    fn try_forward(&self, input: T) -> Result<Self::Output, Self::Error> {
+        match self {
+            Some(m) => crate::hooks::try_forward(m, input),
+            None => Ok(input),
+        }
+    }
+}
+
+impl<T, M: ModuleMut<T, Output = T>> ModuleMut<T> for Option<M> {
+    type Output = T;
+    type Error = M::Error;
+
+    fn try_forward_mut(&mut self, input: T) -> Result<Self::Output, Self::Error> {
+        match self {
+            Some(m) => crate::hooks::try_forward_mut(m, input),
+            None => Ok(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, shapes::*, tensor::*, tests::*};
+
+    #[test]
+    fn test_optional_dropout() {
+        let dev: TestDevice = Default::default();
+        let linear = dev.build_module::<Linear<5, 5>, TestDtype>();
+        let x: Tensor<Rank1<5>, TestDtype, _> = dev.sample_normal();
+
+        let mut model = (linear.clone(), None::<Dropout>);
+        let y = model.forward_mut(x.leaky_trace());
+        assert_close_to_tensor!(y, linear.forward(x.clone()));
+
+        let model = (linear.clone(), Some(ReLU));
+        let y = model.forward(x.clone());
+        assert_close_to_tensor!(y, linear.forward(x).relu());
+    }
+}
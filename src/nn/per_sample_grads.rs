@@ -0,0 +1,112 @@
+use super::{tensor_collection::TensorCollection, ZeroGrads};
+
+use crate::{
+    shapes::{Dtype, Rank0, RemoveDimTo, Shape},
+    tensor::{Gradients, OwnedTape, Tensor, Trace},
+    tensor_ops::{Backward, Device, SelectTo},
+};
+
+use std::vec::Vec;
+
+/// Computes a separate set of gradients for every sample in a batch, instead of the
+/// gradient summed over the batch. Useful for differentially private training, or for
+/// measuring the influence of individual samples.
+///
+/// This runs one forward and backward pass per sample, so it is `B` times slower than
+/// a batched backward pass.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<Linear<3, 2>, f32>();
+/// let batch: Tensor<Rank3<4, 5, 3>, f32, _> = dev.sample_normal();
+/// let grads = model.per_sample_grads(&batch, |m, x: Tensor<Rank2<5, 3>, f32, _, _>| {
+///     m.forward(x).square().mean()
+/// });
+/// assert_eq!(grads.len(), 4);
+/// ```
+pub trait PerSampleGrads<E: Dtype, D: Device<E>>: ZeroGrads<E, D> {
+    /// Calls `loss_fn` with each sample of `batch` (the batch dimension is the first one),
+    /// and returns the gradients of each loss with respect to the tensors in `self`.
+    fn per_sample_grads<S, Dst: Shape, F>(
+        &self,
+        batch: &Tensor<S, E, D>,
+        mut loss_fn: F,
+    ) -> Vec<Gradients<E, D>>
+    where
+        S: RemoveDimTo<Dst, ()>,
+        F: FnMut(&Self, Tensor<Dst, E, D, OwnedTape<E, D>>) -> Tensor<Rank0, E, D, OwnedTape<E, D>>,
+    {
+        self.try_per_sample_grads(batch, |m, x| Ok(loss_fn(m, x)))
+            .unwrap()
+    }
+
+    /// Fallible version of [PerSampleGrads::per_sample_grads]
+    fn try_per_sample_grads<S, Dst: Shape, F>(
+        &self,
+        batch: &Tensor<S, E, D>,
+        mut loss_fn: F,
+    ) -> Result<Vec<Gradients<E, D>>, D::Err>
+    where
+        S: RemoveDimTo<Dst, ()>,
+        F: FnMut(
+            &Self,
+            Tensor<Dst, E, D, OwnedTape<E, D>>,
+        ) -> Result<Tensor<Rank0, E, D, OwnedTape<E, D>>, D::Err>,
+    {
+        let batch_size = batch.shape.concrete()[0];
+        let mut all_grads = Vec::with_capacity(batch_size);
+        for i in 0..batch_size {
+            let idx = batch.device.try_tensor_from_vec(std::vec![i], ())?;
+            let sample = batch.clone().try_select(idx)?;
+            let grads = self.try_alloc_grads()?;
+            let loss = loss_fn(self, sample.traced(grads))?;
+            all_grads.push(loss.try_backward()?);
+        }
+        Ok(all_grads)
+    }
+}
+impl<E: Dtype, D: Device<E>, M: TensorCollection<E, D>> PerSampleGrads<E, D> for M {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    // the sums use `+` on purpose, `+=` is covered by the add/sub tests
+    #[allow(clippy::assign_op_pattern)]
+    #[test]
+    fn test_per_sample_grads_sum_to_batch_grads() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<Linear<3, 2>, TestDtype>();
+        let batch: Tensor<Rank3<4, 5, 3>, TestDtype, _> = dev.sample_normal();
+
+        let grads = model.per_sample_grads(&batch, |m, x: Tensor<Rank2<5, 3>, _, _, _>| {
+            m.forward(x).square().sum()
+        });
+        assert_eq!(grads.len(), 4);
+
+        let batch_grads = model
+            .forward(batch.trace(model.alloc_grads()))
+            .square()
+            .sum()
+            .backward();
+
+        let mut weight: Tensor<Rank2<2, 3>, TestDtype, _> = dev.zeros();
+        let mut bias: Tensor<Rank1<2>, TestDtype, _> = dev.zeros();
+        for g in grads.iter() {
+            // each sample's gradients only contain the model's parameters
+            assert!(g.get_ref_checked(&batch).is_none());
+            weight = weight + g.get(&model.weight);
+            bias = bias + g.get(&model.bias);
+        }
+        assert_close_to_tensor!(weight, batch_grads.get(&model.weight));
+        assert_close_to_tensor!(bias, batch_grads.get(&model.bias));
+
+        // samples have different gradients
+        assert_ne!(
+            grads[0].get(&model.weight).array(),
+            grads[1].get(&model.weight).array()
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::module::{Module, NonMutableModule, ZeroSizedModule};
+
+/// Adds the fixed sinusoidal positional encoding from
+/// [Attention is all you need](https://arxiv.org/abs/1706.03762) to a sequence of
+/// `SEQ` embeddings of size `MODEL_DIM`:
+///
+/// - `PE(pos, 2i) = sin(pos / 10000^(2i / MODEL_DIM))`
+/// - `PE(pos, 2i + 1) = cos(pos / 10000^(2i / MODEL_DIM))`
+///
+/// The encoding is a constant, so this has no parameters, and the gradient of the
+/// input is the gradient of the output.
+///
+/// Both unbatched `(SEQ, MODEL_DIM)` and batched `(B, SEQ, MODEL_DIM)` inputs are supported.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = (Embedding<10, 8>, PositionalEncoding<8, 5>);
+/// let model = dev.build_module::<Model, f32>();
+/// let ids: Tensor<Rank2<2, 5>, usize, _> = dev.zeros();
+/// let _: Tensor<Rank3<2, 5, 8>, f32, _> = model.forward(ids);
+/// ```
+#[derive(Default, Debug, Clone, Copy)]
+pub struct PositionalEncoding<const MODEL_DIM: usize, const SEQ: usize>;
+
+impl<const M: usize, const S: usize> ZeroSizedModule for PositionalEncoding<M, S> {}
+impl<const M: usize, const S: usize> NonMutableModule for PositionalEncoding<M, S> {}
+
+impl<const M: usize, const S: usize> PositionalEncoding<M, S> {
+    /// The `(SEQ, MODEL_DIM)` encoding that is added to the inputs.
+    pub fn table<E: Dtype, D: Device<E>>(&self, device: &D) -> Tensor<Rank2<S, M>, E, D> {
+        self.try_table(device).unwrap()
+    }
+
+    /// Fallible version of [PositionalEncoding::table]
+    pub fn try_table<E: Dtype, D: Device<E>>(
+        &self,
+        device: &D,
+    ) -> Result<Tensor<Rank2<S, M>, E, D>, D::Err> {
+        let mut data = std::vec::Vec::with_capacity(S * M);
+        for pos in 0..S {
+            for i in 0..M {
+                let freq = 10000f64.powf((i - i % 2) as f64 / M as f64);
+                let angle = pos as f64 / freq;
+                let v = if i % 2 == 0 { angle.sin() } else { angle.cos() };
+                data.push(E::from_f64(v).unwrap());
+            }
+        }
+        device.try_tensor_from_vec(data, (Const, Const))
+    }
+}
+
+impl<const M: usize, const S: usize, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Module<Tensor<Rank2<S, M>, E, D, T>> for PositionalEncoding<M, S>
+{
+    type Output = Tensor<Rank2<S, M>, E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<Rank2<S, M>, E, D, T>) -> Result<Self::Output, D::Err> {
+        let table = self.try_table(&x.device)?;
+        x.try_add(table)
+    }
+}
+
+impl<const M: usize, const S: usize, B: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Module<Tensor<(B, Const<S>, Const<M>), E, D, T>> for PositionalEncoding<M, S>
+{
+    type Output = Tensor<(B, Const<S>, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(
+        &self,
+        x: Tensor<(B, Const<S>, Const<M>), E, D, T>,
+    ) -> Result<Self::Output, D::Err> {
+        let table = self.try_table(&x.device)?;
+        let table = table.try_broadcast_like(x.shape())?;
+        x.try_add(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_positional_encoding_values() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let y = PositionalEncoding::<4, 3>.forward(x.leaky_trace());
+
+        let (s1, c1) = (1.0f64.sin(), 1.0f64.cos());
+        let (s2, c2) = (2.0f64.sin(), 2.0f64.cos());
+        let (s3, c3) = (0.01f64.sin(), 0.01f64.cos());
+        let (s4, c4) = (0.02f64.sin(), 0.02f64.cos());
+        assert_close_to_literal!(
+            y.retaped::<NoneTape>() - x.clone(),
+            [[0.0, 1.0, 0.0, 1.0], [s1, c1, s3, c3], [s2, c2, s4, c4]]
+        );
+
+        // the encoding is a constant, so the gradient passes straight through
+        let g = y.exp().sum().backward();
+        assert_close_to_tensor!(
+            g.get(&x),
+            (x.clone() + PositionalEncoding::<4, 3>.table(&dev)).exp()
+        );
+
+        // batched inputs get the same encoding at every batch index
+        let xb: Tensor<Rank3<2, 3, 4>, TestDtype, _> = x.clone().broadcast();
+        let yb = PositionalEncoding::<4, 3>.forward(xb);
+        assert_close_to_tensor!(
+            yb,
+            PositionalEncoding::<4, 3>
+                .forward(x)
+                .broadcast::<Rank3<2, 3, 4>, _>()
+        );
+    }
+}
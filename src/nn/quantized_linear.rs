@@ -0,0 +1,174 @@
+use std::vec::Vec;
+
+use num_traits::Float;
+
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{modules::Linear, *};
+
+/// An inference only [Linear] whose weight is quantized to `i8` with a single scale and
+/// zero point for the whole tensor. Create one with [quantize_linear()].
+///
+/// The weight is stored as `round(w / scale) + zero_point`, and is dequantized back to `E` with
+/// `(q - zero_point) * scale` on every forward before running the same computation as [Linear].
+/// The bias is kept in `E`. Dequantization happens on the host, so the weight is copied off
+/// the device on every forward.
+///
+/// This has no [TensorCollection] impl, so it can't be trained, saved, or reset.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<Linear<5, 2>, f32>();
+/// let q = quantize_linear(&model);
+/// let _: Tensor<Rank2<10, 2>, f32, _> = q.forward(dev.zeros::<Rank2<10, 5>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuantizedLinear<const I: usize, const O: usize, E: Dtype, D: Storage<E> + Storage<i8>> {
+    /// Quantized transposed weight matrix, shape (I, O)
+    pub weight: Tensor<Rank2<O, I>, i8, D>,
+
+    /// The size of one quantization step.
+    pub scale: E,
+
+    /// The quantized value that represents `0.0`.
+    pub zero_point: i8,
+
+    /// Bias vector, shape (O, )
+    pub bias: Tensor<Rank1<O>, E, D>,
+}
+
+/// Quantizes the weight of `linear` to `i8`. See [QuantizedLinear].
+///
+/// The quantization range is the range of the weights extended to include `0.0`, so
+/// `0.0` is exactly representable.
+pub fn quantize_linear<const I: usize, const O: usize, E, D>(
+    linear: &Linear<I, O, E, D>,
+) -> QuantizedLinear<I, O, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E> + TensorFromVec<i8>,
+{
+    QuantizedLinear::try_quantize(linear).unwrap()
+}
+
+impl<const I: usize, const O: usize, E, D> QuantizedLinear<I, O, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E> + TensorFromVec<i8>,
+{
+    /// Fallible version of [quantize_linear()].
+    pub fn try_quantize(linear: &Linear<I, O, E, D>) -> Result<Self, D::Err> {
+        let w: Vec<f64> = linear
+            .weight
+            .as_vec()
+            .into_iter()
+            .map(|w| w.to_f64().unwrap())
+            .collect();
+        let min = w.iter().fold(0.0f64, |a, &b| a.min(b));
+        let max = w.iter().fold(0.0f64, |a, &b| a.max(b));
+        let scale = if max > min { (max - min) / 255.0 } else { 1.0 };
+        let zero_point = (-128.0 - min / scale).round().clamp(-128.0, 127.0);
+        let q = w
+            .iter()
+            .map(|w| ((w / scale).round() + zero_point).clamp(-128.0, 127.0) as i8)
+            .collect();
+        Ok(Self {
+            weight: linear
+                .weight
+                .device
+                .try_tensor_from_vec(q, Default::default())?,
+            scale: E::from_f64(scale).unwrap(),
+            zero_point: zero_point as i8,
+            bias: linear.bias.clone(),
+        })
+    }
+
+    /// Converts the weight back to `E`, returning an equivalent [Linear].
+    pub fn dequantize(&self) -> Linear<I, O, E, D> {
+        self.try_dequantize().unwrap()
+    }
+
+    /// Fallible version of [QuantizedLinear::dequantize].
+    pub fn try_dequantize(&self) -> Result<Linear<I, O, E, D>, D::Err> {
+        let w = self
+            .weight
+            .as_vec()
+            .into_iter()
+            .map(|q| E::from_i16(q as i16 - self.zero_point as i16).unwrap() * self.scale)
+            .collect();
+        Ok(Linear {
+            weight: self
+                .bias
+                .device
+                .try_tensor_from_vec(w, Default::default())?,
+            bias: self.bias.clone(),
+        })
+    }
+}
+
+impl<const I: usize, const O: usize, E, D, T> Module<T> for QuantizedLinear<I, O, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E> + TensorFromVec<i8>,
+    Linear<I, O, E, D>: Module<T, Error = D::Err>,
+{
+    type Output = <Linear<I, O, E, D> as Module<T>>::Output;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: T) -> Result<Self::Output, D::Err> {
+        self.try_dequantize()?.try_forward(x)
+    }
+}
+
+impl<const I: usize, const O: usize, E: Dtype, D: Storage<E> + Storage<i8>> NonMutableModule
+    for QuantizedLinear<I, O, E, D>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders, tests::*};
+
+    #[test]
+    fn test_quantized_linear_close_to_linear() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<builders::Linear<16, 8>, f64>();
+        let q = quantize_linear(&model);
+        let scale = q.scale;
+
+        // each weight is within half a quantization step
+        let w = model.weight.array();
+        let w_q = q.dequantize().weight.array();
+        for (row, row_q) in w.iter().zip(w_q.iter()) {
+            for (a, b) in row.iter().zip(row_q.iter()) {
+                assert!((a - b).abs() <= scale * 0.5 + 1e-12);
+            }
+        }
+        assert_eq!(q.bias.array(), model.bias.array());
+
+        let x: Tensor<Rank2<4, 16>, f64, _> = dev.sample_normal();
+        let y = model.forward(x.clone()).array();
+        let y_q = q.forward(x.clone()).array();
+        for (i, x_i) in x.array().iter().enumerate() {
+            let tol = x_i.iter().map(|v| v.abs()).sum::<f64>() * scale * 0.5 + 1e-12;
+            for j in 0..8 {
+                assert!((y[i][j] - y_q[i][j]).abs() <= tol);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quantize_zero_is_exact() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<builders::Linear<2, 2>, f64>();
+        model.weight = dev.tensor([[0.0, 1.0], [-0.5, 0.25]]);
+        let q = quantize_linear(&model);
+        assert_eq!(q.dequantize().weight.array()[0][0], 0.0);
+        assert_eq!(q.weight.array()[0][0], q.zero_point);
+        assert_eq!(q.weight.array()[0][1], 127);
+        assert_eq!(q.weight.array()[1][0], -128);
+    }
+}
@@ -64,7 +64,7 @@ impl<Input, T: Module<Input, Output = Input>, const N: usize> Module<Input> for
 
     fn try_forward(&self, mut x: Input) -> Result<Self::Output, T::Error> {
         for i in 0..N {
-            x = self.modules[i].try_forward(x)?;
+            x = crate::hooks::try_forward(&self.modules[i], x)?;
         }
         Ok(x)
     }
@@ -78,7 +78,7 @@ impl<Input, T: ModuleMut<Input, Output = Input>, const N: usize> ModuleMut<Input
 
     fn try_forward_mut(&mut self, mut x: Input) -> Result<Self::Output, T::Error> {
         for i in 0..N {
-            x = self.modules[i].try_forward_mut(x)?;
+            x = crate::hooks::try_forward_mut(&mut self.modules[i], x)?;
         }
         Ok(x)
     }
@@ -105,6 +105,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reset_params_distinct() {
+        let dev: TestDevice = Default::default();
+
+        let mut m = dev.build_module::<Repeated<Linear<4, 4>, 2>, TestDtype>();
+        m.reset_params();
+        assert_ne!(m.modules[0].weight.array(), m.modules[1].weight.array());
+        assert_ne!(m.modules[0].bias.array(), m.modules[1].bias.array());
+    }
+
     #[test]
     fn test_forward() {
         let dev: TestDevice = Default::default();
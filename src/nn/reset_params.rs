@@ -19,6 +19,33 @@ impl<E: Dtype, D: Device<E>> TensorVisitor<E, D> for Resetter {
     }
 }
 
+/// Resets every tensor using a copy of its device with a seeded random number generator.
+struct SeededResetter<D> {
+    seed: u64,
+    device: Option<D>,
+}
+impl<E: Dtype, D: Device<E> + WithRngSeed> TensorVisitor<E, D> for SeededResetter<D> {
+    type Viewer = ViewTensorMut;
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        t: &mut Tensor<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        let seed = self.seed;
+        let seeded = self
+            .device
+            .get_or_insert_with(|| t.device.with_rng_seed(seed));
+        let device = std::mem::replace(&mut t.device, seeded.clone());
+        let result = (opts.reset)(t);
+        t.device = device;
+        result.map(|_| None)
+    }
+}
+
 /// Reset a module's parameters with their default reset function:
 ///
 /// ```rust
@@ -41,5 +68,85 @@ pub trait ResetParams<E: Dtype, D: Device<E>>: TensorCollection<E, D> {
         })?;
         Ok(())
     }
+
+    /// Reset all a model's parameters, sampling them from a random number generator
+    /// seeded with `seed`. Resetting the same model with the same seed always gives
+    /// the same parameters:
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mut a = dev.build_module::<Linear<2, 5>, f32>();
+    /// let mut b = dev.build_module::<Linear<2, 5>, f32>();
+    /// a.reset_params_seeded(0);
+    /// b.reset_params_seeded(0);
+    /// assert_eq!(a.weight.array(), b.weight.array());
+    /// ```
+    ///
+    /// The device's own random number generator is left untouched, so this doesn't change
+    /// what e.g. dropout samples afterwards.
+    fn reset_params_seeded(&mut self, seed: u64)
+    where
+        D: WithRngSeed,
+    {
+        self.try_reset_params_seeded(seed).unwrap();
+    }
+    /// Fallible version of [ResetParams::reset_params_seeded].
+    fn try_reset_params_seeded(&mut self, seed: u64) -> Result<(), D::Err>
+    where
+        D: WithRngSeed,
+    {
+        Self::iter_tensors(&mut RecursiveWalker {
+            m: self,
+            f: &mut SeededResetter { seed, device: None },
+        })?;
+        Ok(())
+    }
 }
 impl<E: Dtype, D: Device<E>, M: TensorCollection<E, D>> ResetParams<E, D> for M {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::{builders::*, DeviceBuildExt},
+        tests::*,
+    };
+
+    #[test]
+    fn test_reset_params_seeded() {
+        let dev: TestDevice = Default::default();
+        type Model = TransformerEncoderBlock<8, 2, 16>;
+        let mut a = dev.build_module::<Model, TestDtype>();
+        let mut b = dev.build_module::<Model, TestDtype>();
+        assert_ne!(a.ff.0 .0.weight.array(), b.ff.0 .0.weight.array());
+
+        a.reset_params_seeded(7);
+        b.reset_params_seeded(7);
+        assert_eq!(
+            a.self_attn.w_q.weight.array(),
+            b.self_attn.w_q.weight.array()
+        );
+        assert_eq!(a.self_attn.w_o.bias.array(), b.self_attn.w_o.bias.array());
+        assert_eq!(a.ff.0 .0.weight.array(), b.ff.0 .0.weight.array());
+        assert_eq!(a.ff.0 .2.weight.array(), b.ff.0 .2.weight.array());
+
+        b.reset_params_seeded(8);
+        assert_ne!(
+            a.self_attn.w_q.weight.array(),
+            b.self_attn.w_q.weight.array()
+        );
+        assert_ne!(a.ff.0 .0.weight.array(), b.ff.0 .0.weight.array());
+    }
+
+    #[test]
+    fn test_reset_params_seeded_keeps_device_rng() {
+        let dev: TestDevice = Default::default();
+        let other: TestDevice = Default::default();
+        let mut m = dev.build_module::<Linear<2, 3>, TestDtype>();
+        let _ = other.build_module::<Linear<2, 3>, TestDtype>();
+
+        m.reset_params_seeded(0);
+        assert_eq!(dev.random_u64(), other.random_u64());
+    }
+}
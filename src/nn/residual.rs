@@ -46,7 +46,7 @@ impl<T: WithEmptyTape + TryAdd<T>, F: Module<T, Output = T, Error = T::Err>> Mod
     type Error = F::Error;
 
     fn try_forward(&self, x: T) -> Result<Self::Output, F::Error> {
-        self.0.try_forward(x.with_empty_tape())?.try_add(x)
+        crate::hooks::try_forward(&self.0, x.with_empty_tape())?.try_add(x)
     }
 }
 
@@ -57,7 +57,7 @@ impl<T: WithEmptyTape + TryAdd<T>, F: ModuleMut<T, Output = T, Error = T::Err>>
     type Error = F::Error;
 
     fn try_forward_mut(&mut self, x: T) -> Result<Self::Output, F::Error> {
-        self.0.try_forward_mut(x.with_empty_tape())?.try_add(x)
+        crate::hooks::try_forward_mut(&mut self.0, x.with_empty_tape())?.try_add(x)
     }
 }
 
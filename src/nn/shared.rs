@@ -0,0 +1,107 @@
+use crate::{shapes::Dtype, tensor_ops::Device};
+
+use super::*;
+
+/// Applies the same instance of `T` `N` times. This requires that `T`'s input is the same as it's output.
+///
+/// Unlike [crate::nn::modules::Repeated], which holds `N` separately initialized copies of `T`, all `N`
+/// applications share the parameters of a single module (as in ALBERT or the Universal
+/// Transformer). Since every application uses the same tensors, the gradients of all `N`
+/// uses accumulate into the same parameters, and optimizers update them once.
+///
+/// # Generics
+/// - `T` the [Module] to apply
+/// - `N` the number of times to apply `T`.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = Shared<(Linear<10, 10>, ReLU), 5>;
+/// let model = dev.build_module::<Model, f32>();
+/// assert_eq!(model.num_trainable_params(), 10 * 10 + 10);
+/// let out: Tensor<Rank1<10>, f32, _> = model.forward(dev.zeros());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Shared<T, const N: usize> {
+    pub module: T,
+}
+
+impl<D: Device<E>, E: Dtype, T: BuildOnDevice<D, E>, const N: usize> BuildOnDevice<D, E>
+    for Shared<T, N>
+{
+    type Built = Shared<T::Built, N>;
+}
+
+impl<E: Dtype, D: Device<E>, T: TensorCollection<E, D>, const N: usize> TensorCollection<E, D>
+    for Shared<T, N>
+{
+    type To<E2: Dtype, D2: Device<E2>> = Shared<T::To<E2, D2>, N>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            Self::module("module", |s| &s.module, |s| &mut s.module),
+            |module| Shared { module },
+        )
+    }
+}
+
+impl<Input, T: Module<Input, Output = Input>, const N: usize> Module<Input> for Shared<T, N> {
+    type Output = T::Output;
+    type Error = T::Error;
+
+    fn try_forward(&self, mut x: Input) -> Result<Self::Output, T::Error> {
+        for _ in 0..N {
+            x = self.module.try_forward(x)?;
+        }
+        Ok(x)
+    }
+}
+
+impl<Input, T: ModuleMut<Input, Output = Input>, const N: usize> ModuleMut<Input> for Shared<T, N> {
+    type Output = T::Output;
+    type Error = T::Error;
+
+    fn try_forward_mut(&mut self, mut x: Input) -> Result<Self::Output, T::Error> {
+        for _ in 0..N {
+            x = self.module.try_forward_mut(x)?;
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, tests::*};
+
+    #[test]
+    fn test_shared_num_params() {
+        let dev: TestDevice = Default::default();
+        type Block = TransformerEncoderBlock<8, 2, 16>;
+        let block = dev.build_module::<Block, TestDtype>();
+        let shared = dev.build_module::<Shared<Block, 3>, TestDtype>();
+        assert_eq!(shared.num_trainable_params(), block.num_trainable_params());
+    }
+
+    #[test]
+    fn test_shared_gradients_accumulate() {
+        let dev: TestDevice = Default::default();
+        let mut m = dev.build_module::<Shared<Linear<1, 1>, 3>, TestDtype>();
+        m.module.weight = dev.tensor([[2.0]]).to_dtype::<TestDtype>();
+        m.module.bias = dev.tensor([1.0]).to_dtype::<TestDtype>();
+
+        // y = w(w(wx + b) + b) + b = w^3 x + (w^2 + w + 1) b
+        let x = dev.tensor([3.0]).to_dtype::<TestDtype>();
+        let y = m.forward(x.leaky_trace());
+        assert_close_to_literal!(y, [31.0]);
+
+        let g = y.sum().backward();
+        // dy/dw = 3 w^2 x + (2w + 1) b
+        assert_close_to_literal!(g.get(&m.module.weight), [[41.0]]);
+        // dy/db = w^2 + w + 1
+        assert_close_to_literal!(g.get(&m.module.bias), [7.0]);
+    }
+}
@@ -0,0 +1,218 @@
+use num_traits::Float;
+use rand_distr::{uniform::SampleUniform, Uniform};
+
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{linear::Bias1D, modules::Linear, *};
+
+pub mod builder {
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct SpectralNorm<const I: usize, const O: usize>;
+}
+
+impl<const I: usize, const O: usize, E: Dtype, D: Device<E>> BuildOnDevice<D, E>
+    for builder::SpectralNorm<I, O>
+where
+    SpectralNorm<I, O, E, D>: BuildModule<D, E>,
+{
+    type Built = SpectralNorm<I, O, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// A [Linear] whose weight is divided by its largest singular value (spectral norm) on every
+/// forward, as described in [Spectral Normalization for Generative Adversarial Networks](https://arxiv.org/abs/1802.05957).
+///
+/// The largest singular value is estimated as `u^T W v` with `v = normalize(W^T u)`, where
+/// [Self::u] is an estimate of the top left singular vector. It is not trainable, and each
+/// [ModuleMut::forward_mut()] first refines it with one step of power iteration.
+/// [Module::forward()] uses it as is. Gradients flow through the estimated singular value
+/// into the weight, but not into `u` or `v`.
+///
+/// Generics:
+/// - `I` The "input" size of vectors & matrices.
+/// - `O` The "output" size of vectors & matrices.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let mut model = dev.build_module::<SpectralNorm<5, 2>, f32>();
+/// let _: Tensor<Rank2<10, 2>, f32, _> = model.forward_mut(dev.zeros::<Rank2<10, 5>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpectralNorm<const I: usize, const O: usize, E: Dtype, D: Storage<E>> {
+    pub linear: Linear<I, O, E, D>,
+
+    /// Estimate of the top left singular vector of `linear.weight`, shape (O, )
+    pub u: Tensor<Rank1<O>, E, D>,
+}
+
+impl<const I: usize, const O: usize, E, D: Device<E>> TensorCollection<E, D>
+    for SpectralNorm<I, O, E, D>
+where
+    E: Dtype + Float + SampleUniform,
+{
+    type To<E2: Dtype, D2: Device<E2>> = SpectralNorm<I, O, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            (
+                Self::module("linear", |s| &s.linear, |s| &mut s.linear),
+                Self::tensor(
+                    "u",
+                    |s| &s.u,
+                    |s| &mut s.u,
+                    TensorOptions::detached(|t| {
+                        t.try_fill_with_distr(Uniform::new(-E::ONE, E::ONE))
+                    }),
+                ),
+            ),
+            |(linear, u)| SpectralNorm { linear, u },
+        )
+    }
+}
+
+/// Divides `t` by its l2 norm.
+fn l2_normalize<const N: usize, E: Dtype + Float, D: Device<E>>(
+    t: Tensor<Rank1<N>, E, D>,
+) -> Result<Tensor<Rank1<N>, E, D>, D::Err> {
+    let norm = t
+        .clone()
+        .try_square()?
+        .try_sum()?
+        .try_sqrt()?
+        .try_add(E::from_f32(1e-12).unwrap())?;
+    t.try_div(norm.try_broadcast()?)
+}
+
+impl<const I: usize, const O: usize, E: Dtype + Float, D: Device<E>> SpectralNorm<I, O, E, D> {
+    /// The weight divided by its estimated spectral norm, which is what forward uses.
+    pub fn weight(&self) -> Tensor<Rank2<O, I>, E, D> {
+        self.try_weight::<NoneTape>().unwrap()
+    }
+
+    fn try_weight<T: Tape<E, D>>(&self) -> Result<Tensor<Rank2<O, I>, E, D, T>, D::Err> {
+        let w = &self.linear.weight;
+        let u = l2_normalize(self.u.clone())?;
+        let v = l2_normalize(u.clone().try_matmul(w.clone())?)?;
+        let uv: Tensor<Rank2<O, I>, E, D> = u
+            .try_broadcast::<_, Axis<1>>()?
+            .try_mul(v.try_broadcast::<_, Axis<0>>()?)?;
+        let sigma = w.retaped::<T>().try_mul(uv)?.try_sum::<Rank0, _>()?;
+        w.retaped::<T>().try_div(sigma.try_broadcast()?)
+    }
+
+    /// Runs one step of power iteration on [Self::u].
+    pub fn try_power_iteration(&mut self) -> Result<(), D::Err> {
+        let w = &self.linear.weight;
+        let v = l2_normalize(self.u.clone().try_matmul(w.clone())?)?;
+        self.u = l2_normalize(v.try_matmul(w.clone().try_permute()?)?)?;
+        Ok(())
+    }
+}
+
+impl<const I: usize, const O: usize, E, D, T> Module<T> for SpectralNorm<I, O, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    T: SplitTape + TryMatMul<Tensor<Rank2<I, O>, E, D, T::Tape>> + HasErr<Err = D::Err>,
+    T::Tape: Tape<E, D>,
+    for<'a> Bias1D<'a, O, E, D>: Module<T::Output, Output = T::Output, Error = D::Err>,
+{
+    type Output = T::Output;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: T) -> Result<Self::Output, D::Err> {
+        let w = self.try_weight::<T::Tape>()?;
+        let o = x.try_matmul(w.try_permute()?)?;
+        Bias1D {
+            beta: &self.linear.bias,
+        }
+        .try_forward(o)
+    }
+}
+
+impl<const I: usize, const O: usize, E, D, T> ModuleMut<T> for SpectralNorm<I, O, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    Self: Module<T, Error = D::Err>,
+{
+    type Output = <Self as Module<T>>::Output;
+    type Error = D::Err;
+
+    fn try_forward_mut(&mut self, x: T) -> Result<Self::Output, D::Err> {
+        self.try_power_iteration()?;
+        self.try_forward(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    /// Largest singular value of `w`, using many steps of power iteration on the host.
+    fn spectral_norm<const O: usize, const I: usize>(w: [[f64; I]; O]) -> f64 {
+        let mut v = [1.0; I];
+        let mut sigma = 0.0;
+        for _ in 0..500 {
+            let wv: Vec<f64> = w
+                .iter()
+                .map(|row| row.iter().zip(v.iter()).map(|(a, b)| a * b).sum())
+                .collect();
+            sigma = wv.iter().map(|x| x * x).sum::<f64>().sqrt();
+            for (i, v_i) in v.iter_mut().enumerate() {
+                *v_i = (0..O).map(|o| w[o][i] * wv[o]).sum::<f64>();
+            }
+            let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+            v.iter_mut().for_each(|x| *x /= norm);
+        }
+        sigma
+    }
+
+    #[test]
+    fn test_spectral_norm_converges() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<builder::SpectralNorm<5, 3>, f64>();
+        model.linear.weight = model.linear.weight.clone() * 10.0;
+        assert!(spectral_norm(model.linear.weight.array()) > 1.5);
+
+        let x: Tensor<Rank2<4, 5>, f64, _> = dev.sample_normal();
+        for _ in 0..20 {
+            model.forward_mut(x.clone());
+        }
+        assert!((spectral_norm(model.weight().array()) - 1.0).abs() < 1e-3);
+
+        // forward uses the normalized weight, and doesn't change u
+        let u = model.u.array();
+        let linear = Linear {
+            weight: model.weight(),
+            bias: model.linear.bias.clone(),
+        };
+        assert_close_to_tensor!(model.forward(x.clone()), linear.forward(x));
+        assert_eq!(model.u.array(), u);
+    }
+
+    #[test]
+    fn test_spectral_norm_gradients() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<builder::SpectralNorm<5, 3>, TestDtype>();
+        let x: Tensor<Rank1<5>, TestDtype, _> = dev.sample_normal();
+        let g = model
+            .forward_mut(x.trace(model.alloc_grads()))
+            .square()
+            .mean()
+            .backward();
+        assert_ne!(
+            g.get(&model.linear.weight).array(),
+            [[TestDtype::default(); 5]; 3]
+        );
+        assert_ne!(g.get(&model.linear.bias).array(), [TestDtype::default(); 3]);
+        assert_eq!(model.num_trainable_params(), 5 * 3 + 3);
+    }
+}
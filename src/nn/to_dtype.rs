@@ -83,4 +83,31 @@ mod tests {
                 .collect::<Vec<_>>()
         );
     }
+
+    #[cfg(feature = "f16")]
+    #[test]
+    fn test_linear_f32_to_f16() {
+        use crate::{shapes::Rank1, tensor::SampleTensor};
+        use half::f16;
+
+        let dev: TestDevice = Default::default();
+        let model_f32 = dev.build_module::<Linear<4, 3>, f32>();
+        let model_f16: modules::Linear<4, 3, f16, TestDevice> = model_f32.to_dtype::<f16>();
+
+        // f16 has an 11 bit significand, so a relative error of at most 2^-11
+        let close = |a: f32, b: f16| (a - b.to_f32()).abs() <= a.abs() * 4.9e-4;
+        let w32 = model_f32.weight.as_vec();
+        let w16 = model_f16.weight.as_vec();
+        assert!(w32.iter().zip(w16.iter()).all(|(a, b)| close(*a, *b)));
+        let b32 = model_f32.bias.as_vec();
+        let b16 = model_f16.bias.as_vec();
+        assert!(b32.iter().zip(b16.iter()).all(|(a, b)| close(*a, *b)));
+
+        let x: Tensor<Rank1<4>, f32, _> = dev.sample_normal();
+        let y32 = model_f32.forward(x.clone()).as_vec();
+        let y16 = model_f16.forward(x.to_dtype::<f16>()).as_vec();
+        for (a, b) in y32.iter().zip(y16.iter()) {
+            assert!((a - b.to_f32()).abs() < 1e-2, "{a} != {b}");
+        }
+    }
 }
@@ -0,0 +1,157 @@
+use num_traits::Float;
+use rand_distr::uniform::SampleUniform;
+
+use crate::{nn::modules::*, shapes::*, tensor::*, tensor_ops::Device};
+
+use super::mha::MultiHeadAttention;
+
+pub mod builder {
+    #[derive(Debug, Clone)]
+    pub struct CrossAttention<
+        const EMBED_DIM: usize,
+        const NUM_HEADS: usize,
+        const K_DIM: usize = EMBED_DIM,
+        const V_DIM: usize = EMBED_DIM,
+    >;
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E: Dtype, D: Device<E>>
+    BuildOnDevice<D, E> for builder::CrossAttention<M, H, K, V>
+where
+    CrossAttention<M, H, K, V, E, D>: BuildModule<D, E>,
+{
+    type Built = CrossAttention<M, H, K, V, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        #[allow(clippy::let_unit_value)]
+        let _ = super::mha::builder::MultiHeadAttention::<M, H, K, V>::TYPE_CHECK;
+        Self::Built::try_build(device)
+    }
+}
+
+/// Encoder-decoder attention, where queries attend to a `memory` sequence that is
+/// used for both keys and values. This is a thin wrapper around [MultiHeadAttention]
+/// that takes `(query, memory)` instead of `(query, key, value)`.
+///
+/// Both unbatched `(S, EMBED_DIM)` and batched `(B, S, EMBED_DIM)` inputs are supported.
+/// `memory` must not have a tape.
+///
+/// Generics are the same as [MultiHeadAttention].
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let attn = dev.build_module::<CrossAttention<8, 2>, f32>();
+/// let tgt: Tensor<Rank2<3, 8>, f32, _> = dev.sample_normal();
+/// let mem: Tensor<Rank2<5, 8>, f32, _> = dev.sample_normal();
+/// let _: Tensor<Rank2<3, 8>, f32, _> = attn.forward((tgt, mem));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CrossAttention<
+    const EMBED_DIM: usize,
+    const NUM_HEADS: usize,
+    const K_DIM: usize,
+    const V_DIM: usize,
+    E: Dtype,
+    D: Storage<E>,
+> {
+    pub mha: MultiHeadAttention<EMBED_DIM, NUM_HEADS, K_DIM, V_DIM, E, D>,
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D: Device<E>>
+    TensorCollection<E, D> for CrossAttention<M, H, K, V, E, D>
+where
+    E: Dtype + Float + SampleUniform,
+{
+    type To<E2: Dtype, D2: Device<E2>> = CrossAttention<M, H, K, V, E2, D2>;
+
+    fn iter_tensors<Vi: ModuleVisitor<Self, E, D>>(
+        visitor: &mut Vi,
+    ) -> Result<Option<Self::To<Vi::E2, Vi::D2>>, Vi::Err> {
+        visitor.visit_fields(Self::module("mha", |s| &s.mha, |s| &mut s.mha), |mha| {
+            CrossAttention { mha }
+        })
+    }
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, S1, S2, T>
+    Module<(
+        Tensor<(S1, Const<M>), E, D, T>,
+        Tensor<(S2, Const<M>), E, D>,
+    )> for CrossAttention<M, H, K, V, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    S1: Dim,
+    S2: Dim,
+    T: Tape<E, D>,
+{
+    type Output = Tensor<(S1, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(
+        &self,
+        (q, mem): (
+            Tensor<(S1, Const<M>), E, D, T>,
+            Tensor<(S2, Const<M>), E, D>,
+        ),
+    ) -> Result<Self::Output, D::Err> {
+        crate::hooks::try_forward(&self.mha, (q, mem.clone(), mem))
+    }
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, B, S1, S2, T>
+    Module<(
+        Tensor<(B, S1, Const<M>), E, D, T>,
+        Tensor<(B, S2, Const<M>), E, D>,
+    )> for CrossAttention<M, H, K, V, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    B: Dim,
+    S1: Dim,
+    S2: Dim,
+    T: Tape<E, D>,
+{
+    type Output = Tensor<(B, S1, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(
+        &self,
+        (q, mem): (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+    ) -> Result<Self::Output, D::Err> {
+        crate::hooks::try_forward(&self.mha, (q, mem.clone(), mem))
+    }
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E: Dtype, D: Device<E>>
+    NonMutableModule for CrossAttention<M, H, K, V, E, D>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_cross_attention_matches_mha() {
+        let dev = TestDevice::seed_from_u64(0);
+        let attn = dev.build_module::<builder::CrossAttention<8, 2>, TestDtype>();
+
+        // unbatched
+        let q: Tensor<Rank2<3, 8>, TestDtype, _> = dev.sample_normal();
+        let mem: Tensor<Rank2<4, 8>, TestDtype, _> = dev.sample_normal();
+        let y = attn.forward((q.clone(), mem.clone()));
+        assert_close_to_tensor!(y, attn.mha.forward((q, mem.clone(), mem)));
+
+        // batched
+        let q: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let mem: Tensor<Rank3<2, 4, 8>, TestDtype, _> = dev.sample_normal();
+        let y = attn.forward((q.clone(), mem.clone()));
+        assert_close_to_tensor!(y, attn.mha.forward((q, mem.clone(), mem)));
+    }
+}
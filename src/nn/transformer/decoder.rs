@@ -3,12 +3,12 @@ use rand_distr::uniform::SampleUniform;
 
 use crate::{
     nn::modules::*,
-    shapes::Dtype,
-    tensor::{HasErr, PutTape, SplitTape, Storage},
+    shapes::{Const, Dim, Dtype},
+    tensor::{HasErr, PutTape, SplitTape, Storage, Tape, Tensor},
     tensor_ops::{Device, TryAdd},
 };
 
-use super::mha::MultiHeadAttention;
+use super::{mha::MultiHeadAttention, CausalMask};
 
 pub mod builder {
     #[derive(Clone, Debug)]
@@ -107,6 +107,43 @@ impl<const M: usize, const H: usize, const F: usize, const L: usize, E: Dtype, D
 {
 }
 
+impl<const M: usize, const H: usize, const F: usize, const L: usize, E, D>
+    TransformerDecoder<M, H, F, L, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+{
+    /// Runs every block with [TransformerDecoderBlock::forward_causal()], so target
+    /// position `i` only attends to target positions `j <= i`.
+    #[allow(clippy::type_complexity)]
+    pub fn forward_causal<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        tgt_mem: (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        mask: &mut CausalMask<E, D>,
+    ) -> Tensor<(B, S1, Const<M>), E, D, T> {
+        self.try_forward_causal(tgt_mem, mask).unwrap()
+    }
+
+    /// Fallible version of [TransformerDecoder::forward_causal]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_causal<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        (mut tgt, mem): (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        mask: &mut CausalMask<E, D>,
+    ) -> Result<Tensor<(B, S1, Const<M>), E, D, T>, D::Err> {
+        for block in self.0.modules.iter() {
+            tgt = block.try_forward_causal((tgt, mem.clone()), mask)?;
+        }
+        Ok(tgt)
+    }
+}
+
 /// A transformer decoder block. Different than the normal transformer block
 /// as this self attention accepts an additional sequence from the encoder.
 ///
@@ -186,19 +223,17 @@ where
 
     fn try_forward(&self, (tgt, mem): (Tgt, Mem)) -> Result<Self::Output, D::Err> {
         let (tgt, tape) = tgt.split_tape();
-        let x = self.self_attn.try_forward(tgt.clone().put_tape(tape))?;
+        let x = crate::hooks::try_forward(&self.self_attn, tgt.clone().put_tape(tape))?;
         let x = x.try_add(tgt)?;
-        let x = self.norm1.try_forward(x)?;
+        let x = crate::hooks::try_forward(&self.norm1, x)?;
 
         let (x, tape) = x.split_tape();
         let x_residual = x.clone();
-        let x = self
-            .mh_attn
-            .try_forward((x.put_tape(tape), mem.clone(), mem))?;
+        let x = crate::hooks::try_forward(&self.mh_attn, (x.put_tape(tape), mem.clone(), mem))?;
         let x = x.try_add(x_residual)?;
-        let x = self.norm2.try_forward(x)?;
-        let x = self.ff.try_forward(x)?;
-        self.norm3.try_forward(x)
+        let x = crate::hooks::try_forward(&self.norm2, x)?;
+        let x = crate::hooks::try_forward(&self.ff, x)?;
+        crate::hooks::try_forward(&self.norm3, x)
     }
 }
 
@@ -207,11 +242,123 @@ impl<const M: usize, const H: usize, const F: usize, E: Dtype, D: Device<E>> Non
 {
 }
 
+impl<const M: usize, const H: usize, const F: usize, E, D> TransformerDecoderBlock<M, H, F, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+{
+    /// Like [Module::forward()], but the self attention is masked with
+    /// [MultiHeadAttention::forward_causal()], so target position `i` only attends to
+    /// target positions `j <= i`. The cross attention over `mem` isn't masked.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let block = dev.build_module::<TransformerDecoderBlock<8, 2, 16>, f32>();
+    /// let mut mask = CausalMask::default();
+    /// let tgt: Tensor<Rank3<1, 3, 8>, f32, _> = dev.sample_normal();
+    /// let mem: Tensor<Rank3<1, 5, 8>, f32, _> = dev.sample_normal();
+    /// let y = block.forward_causal((tgt, mem), &mut mask);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_causal<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        tgt_mem: (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        mask: &mut CausalMask<E, D>,
+    ) -> Tensor<(B, S1, Const<M>), E, D, T> {
+        self.try_forward_causal(tgt_mem, mask).unwrap()
+    }
+
+    /// Fallible version of [TransformerDecoderBlock::forward_causal]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_causal<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        (tgt, mem): (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        mask: &mut CausalMask<E, D>,
+    ) -> Result<Tensor<(B, S1, Const<M>), E, D, T>, D::Err> {
+        let (tgt, tape) = tgt.split_tape();
+        let x = self
+            .self_attn
+            .try_forward_causal((tgt.clone().put_tape(tape), tgt.clone(), tgt.clone()), mask)?;
+        let x = x.try_add(tgt)?;
+        let x = crate::hooks::try_forward(&self.norm1, x)?;
+
+        let (x, tape) = x.split_tape();
+        let x_residual = x.clone();
+        let x = crate::hooks::try_forward(&self.mh_attn, (x.put_tape(tape), mem.clone(), mem))?;
+        let x = x.try_add(x_residual)?;
+        let x = crate::hooks::try_forward(&self.norm2, x)?;
+        let x = crate::hooks::try_forward(&self.ff, x)?;
+        crate::hooks::try_forward(&self.norm3, x)
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::excessive_precision)]
 mod tests {
     use super::*;
-    use crate::{shapes::Rank3, tensor::*, tests::*};
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_decoder_causal_ignores_later_targets() {
+        let dev: TestDevice = Default::default();
+        let decoder = dev.build_module::<builder::TransformerDecoder<8, 2, 16, 2>, f64>();
+        let mut mask = CausalMask::default();
+
+        let tgt: Tensor<Rank3<1, 4, 8>, f64, _> = dev.sample_normal();
+        let mem: Tensor<Rank3<1, 3, 8>, f64, _> = dev.sample_normal();
+        let y = decoder
+            .forward_causal((tgt.clone(), mem.clone()), &mut mask)
+            .as_vec();
+
+        // changing the last target doesn't change the outputs before it
+        let mut tgt2 = tgt.as_vec();
+        tgt2[24..].fill(5.0);
+        let tgt2 = dev.tensor_from_vec(tgt2, tgt.shape);
+        let y2 = decoder
+            .forward_causal((tgt2, mem.clone()), &mut mask)
+            .as_vec();
+        for (a, b) in y[..24].iter().zip(y2[..24].iter()) {
+            assert!((a - b).abs() < 1e-10, "{a} != {b}");
+        }
+        assert_ne!(y[24..], y2[24..]);
+
+        // the first position only attends to itself, so it ignores all other targets
+        let first = decoder
+            .forward((tgt.clone().slice((.., ..1, ..)), mem.clone()))
+            .as_vec();
+        for (a, b) in first.iter().zip(y[..8].iter()) {
+            assert!((a - b).abs() < 1e-10, "{a} != {b}");
+        }
+
+        // gradients flow back through the masked path
+        let g = decoder
+            .forward_causal((tgt.leaky_trace(), mem), &mut mask)
+            .exp()
+            .mean()
+            .backward();
+        assert_ne!(g.get(&tgt).array(), [[[0.0; 8]; 4]]);
+    }
+
+    #[test]
+    fn test_decoder_block_unbatched() {
+        let dev: TestDevice = Default::default();
+        let decoder = dev.build_module::<builder::TransformerDecoderBlock<8, 2, 16>, TestDtype>();
+        let tgt: Tensor<Rank2<4, 8>, TestDtype, _> = dev.sample_normal();
+        let mem: Tensor<Rank2<3, 8>, TestDtype, _> = dev.sample_normal();
+        let y = decoder.forward((tgt.clone(), mem.clone()));
+        let y_batched = decoder.forward((
+            tgt.broadcast::<Rank3<1, 4, 8>, _>(),
+            mem.broadcast::<Rank3<1, 3, 8>, _>(),
+        ));
+        assert_close_to_tensor!(y_batched.reshape::<Rank2<4, 8>>(), y);
+    }
 
     #[test]
     fn test_decoder_block_forward() {
@@ -3,9 +3,9 @@ use rand_distr::uniform::SampleUniform;
 
 use crate::{
     nn::modules::*,
-    shapes::Dtype,
-    tensor::{PutTape, SplitTape, Storage},
-    tensor_ops::Device,
+    shapes::{Const, Dim, Dtype, Shape},
+    tensor::{Merge, PutTape, SplitTape, Storage, Tape, Tensor},
+    tensor_ops::{Device, TryAdd},
 };
 
 use super::mha::MultiHeadAttention;
@@ -27,6 +27,63 @@ pub type TransformerEncoder<
     D,
 > = Repeated<TransformerEncoderBlock<MODEL_DIM, NUM_HEADS, FF_DIM, E, D>, NUM_LAYERS>;
 
+impl<const M: usize, const H: usize, const F: usize, const L: usize, E, D>
+    TransformerEncoder<M, H, F, L, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+{
+    /// Runs every block with [TransformerEncoderBlock::forward_with_bias()], adding the
+    /// same precomputed `bias` to the attention logits of every layer, like the shared
+    /// relative position bias of T5.
+    ///
+    /// `bias` has shape `(NUM_HEADS, S, S)`. If `bias` has an [crate::tensor::OwnedTape],
+    /// the gradients of all layers are accumulated into it.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let encoder = dev.build_module::<TransformerEncoder<8, 2, 16, 3>, f32>();
+    /// let src: Tensor<Rank3<1, 5, 8>, f32, _> = dev.sample_normal();
+    /// let bias: Tensor<Rank3<2, 5, 5>, f32, _> = dev.sample_normal();
+    /// let y = encoder.forward_with_bias(src, bias);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_with_bias<B: Dim, S: Dim, T, R>(
+        &self,
+        src: Tensor<(B, S, Const<M>), E, D, T>,
+        bias: Tensor<(Const<H>, S, S), E, D, R>,
+    ) -> Tensor<(B, S, Const<M>), E, D, T>
+    where
+        T: Tape<E, D> + Merge<R> + Merge<T>,
+        R: Tape<E, D>,
+    {
+        self.try_forward_with_bias(src, bias).unwrap()
+    }
+
+    /// Fallible version of [TransformerEncoder::forward_with_bias]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_with_bias<B: Dim, S: Dim, T, R>(
+        &self,
+        src: Tensor<(B, S, Const<M>), E, D, T>,
+        bias: Tensor<(Const<H>, S, S), E, D, R>,
+    ) -> Result<Tensor<(B, S, Const<M>), E, D, T>, D::Err>
+    where
+        T: Tape<E, D> + Merge<R> + Merge<T>,
+        R: Tape<E, D>,
+    {
+        // the tape of `bias` is only merged in once, and every layer records its use of
+        // `bias` on a fresh tape of the same type, so its gradients are all accumulated.
+        let (bias, bias_tape) = bias.split_tape();
+        let (src, tape) = src.split_tape();
+        let mut x = src.put_tape(tape.merge(bias_tape));
+        for block in self.modules.iter() {
+            x = block.try_forward_with_bias(x, bias.retaped::<T>())?;
+        }
+        Ok(x)
+    }
+}
+
 pub mod builder {
     #[derive(Debug)]
     pub struct TransformerEncoder<
@@ -42,6 +99,19 @@ pub mod builder {
         const NUM_HEADS: usize,
         const FF_DIM: usize,
     >;
+
+    /// A [TransformerEncoderBlock] with the usual feedforward size of `4 * MODEL_DIM`.
+    #[cfg(feature = "nightly")]
+    pub type TransformerEncoderBlock4x<const MODEL_DIM: usize, const NUM_HEADS: usize> =
+        TransformerEncoderBlock<MODEL_DIM, NUM_HEADS, { 4 * MODEL_DIM }>;
+
+    /// A [TransformerEncoder] with the usual feedforward size of `4 * MODEL_DIM`.
+    #[cfg(feature = "nightly")]
+    pub type TransformerEncoder4x<
+        const MODEL_DIM: usize,
+        const NUM_HEADS: usize,
+        const NUM_LAYERS: usize,
+    > = TransformerEncoder<MODEL_DIM, NUM_HEADS, { 4 * MODEL_DIM }, NUM_LAYERS>;
 }
 
 impl<const M: usize, const H: usize, const F: usize, const L: usize, E: Dtype, D: Device<E>>
@@ -73,6 +143,15 @@ where
 /// - `NUM_HEADS`: The number of heads in [MultiHeadAttention].
 /// - `FF_DIM`: The size of the hidden layer in the feedforward network.
 ///
+/// **Dropout**: [ModuleMut::forward_mut()] applies [Self::dropout] to the outputs of the
+/// self attention and of the feedforward network, before they are added to the residual,
+/// when the input has an [crate::tensor::OwnedTape]. [Module::forward()] never applies
+/// dropout, so use it for evaluation. The mask is sampled from the device's rng, and
+/// the backward pass drops the same elements (see [crate::tensor_ops::dropout()]).
+///
+/// [Self::dropout] is disabled (`p = 0.0`) when the block is built. It isn't a tensor, so
+/// it is also reset to `0.0` by anything that rebuilds the module, like [ToDevice] or [ToDtype].
+///
 /// **Pytorch equivalent**:
 /// ```python
 /// encoder = torch.nn.TransformerEncoderLayer(
@@ -91,11 +170,38 @@ pub struct TransformerEncoderBlock<
     pub norm1: LayerNorm1D<MODEL_DIM, E, D>,
     pub ff: FF<MODEL_DIM, FF_DIM, E, D>,
     pub norm2: LayerNorm1D<MODEL_DIM, E, D>,
+    /// Only used by [ModuleMut::forward_mut()]. Not carried over when the block is
+    /// rebuilt by a visitor (e.g. [ToDevice], [ToDtype]), so set it again afterwards.
+    pub dropout: Dropout,
 }
 
 type FF<const M: usize, const F: usize, E, D> =
     Residual<(Linear<M, F, E, D>, ReLU, Linear<F, M, E, D>)>;
 
+impl<const M: usize, const H: usize, const F: usize, E: Dtype, D: Storage<E>>
+    TransformerEncoderBlock<M, H, F, E, D>
+{
+    /// The self attention sub-module.
+    pub fn attn(&self) -> &MultiHeadAttention<M, H, M, M, E, D> {
+        &self.self_attn
+    }
+
+    /// Mutable access to the self attention sub-module.
+    pub fn attn_mut(&mut self) -> &mut MultiHeadAttention<M, H, M, M, E, D> {
+        &mut self.self_attn
+    }
+
+    /// The residual feedforward sub-module.
+    pub fn ff(&self) -> &FF<M, F, E, D> {
+        &self.ff
+    }
+
+    /// Mutable access to the residual feedforward sub-module.
+    pub fn ff_mut(&mut self) -> &mut FF<M, F, E, D> {
+        &mut self.ff
+    }
+}
+
 impl<const M: usize, const H: usize, const F: usize, E, D: Device<E>> TensorCollection<E, D>
     for TransformerEncoderBlock<M, H, F, E, D>
 where
@@ -118,6 +224,8 @@ where
                 norm1,
                 ff,
                 norm2,
+                // the visitor only sees tensors, so `p` can't be carried over
+                dropout: Dropout { p: 0.0 },
             },
         )
     }
@@ -136,24 +244,110 @@ where
 
     fn try_forward(&self, src: Src) -> Result<Self::Output, D::Err> {
         let (src, tape) = src.split_tape();
-        let x = self.self_attn.try_forward(src.clone().put_tape(tape))?;
+        let x = crate::hooks::try_forward(&self.self_attn, src.clone().put_tape(tape))?;
         let x = x + src;
-        let x = self.norm1.try_forward(x)?;
-        let x = self.ff.try_forward(x)?;
-        self.norm2.try_forward(x)
+        let x = crate::hooks::try_forward(&self.norm1, x)?;
+        let x = crate::hooks::try_forward(&self.ff, x)?;
+        crate::hooks::try_forward(&self.norm2, x)
     }
 }
 
-impl<const M: usize, const H: usize, const F: usize, E: Dtype, D: Device<E>> NonMutableModule
-    for TransformerEncoderBlock<M, H, F, E, D>
+impl<const M: usize, const H: usize, const F: usize, E: Dtype, D: Device<E>, S: Shape, T>
+    ModuleMut<Tensor<S, E, D, T>> for TransformerEncoderBlock<M, H, F, E, D>
+where
+    T: Tape<E, D>,
+    Self: Module<Tensor<S, E, D, T>, Output = Tensor<S, E, D, T>, Error = D::Err>,
+    MultiHeadAttention<M, H, M, M, E, D>:
+        Module<Tensor<S, E, D, T>, Output = Tensor<S, E, D, T>, Error = D::Err>,
+    LayerNorm1D<M, E, D>: Module<Tensor<S, E, D, T>, Output = Tensor<S, E, D, T>, Error = D::Err>,
+    (Linear<M, F, E, D>, ReLU, Linear<F, M, E, D>):
+        Module<Tensor<S, E, D, T>, Output = Tensor<S, E, D, T>, Error = D::Err>,
+{
+    type Output = Tensor<S, E, D, T>;
+    type Error = D::Err;
+
+    /// Like [Module::forward()], but with dropout when `T` is an [crate::tensor::OwnedTape].
+    fn try_forward_mut(&mut self, src: Tensor<S, E, D, T>) -> Result<Self::Output, D::Err> {
+        if !T::OWNS_TAPE || self.dropout.p == 0.0 {
+            return self.try_forward(src);
+        }
+        let (src, tape) = src.split_tape();
+        let x = crate::hooks::try_forward(&self.self_attn, src.clone().put_tape(tape))?;
+        let x = self.try_dropout(x)?.try_add(src)?;
+        let x = crate::hooks::try_forward(&self.norm1, x)?;
+
+        let (x, tape) = x.split_tape();
+        let y = crate::hooks::try_forward(&self.ff.0, x.clone().put_tape(tape))?;
+        let y = self.try_dropout(y)?.try_add(x)?;
+        crate::hooks::try_forward(&self.norm2, y)
+    }
+}
+
+impl<const M: usize, const H: usize, const F: usize, E: Dtype, D: Device<E>>
+    TransformerEncoderBlock<M, H, F, E, D>
+{
+    fn try_dropout<S: Shape, T: Tape<E, D>>(
+        &self,
+        x: Tensor<S, E, D, T>,
+    ) -> Result<Tensor<S, E, D, T>, D::Err> {
+        x.try_dropout(self.dropout.p)
+    }
+}
+
+impl<const M: usize, const H: usize, const F: usize, E, D> TransformerEncoderBlock<M, H, F, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
 {
+    /// Like [Module::forward()], but `bias` is added to the self attention logits with
+    /// [MultiHeadAttention::forward_with_bias()]. See [TransformerEncoder::forward_with_bias()]
+    /// to share one bias between all the blocks of an encoder.
+    #[allow(clippy::type_complexity)]
+    pub fn forward_with_bias<B: Dim, S: Dim, T, R>(
+        &self,
+        src: Tensor<(B, S, Const<M>), E, D, T>,
+        bias: Tensor<(Const<H>, S, S), E, D, R>,
+    ) -> Tensor<(B, S, Const<M>), E, D, T>
+    where
+        T: Tape<E, D> + Merge<R>,
+        R: Tape<E, D>,
+    {
+        self.try_forward_with_bias(src, bias).unwrap()
+    }
+
+    /// Fallible version of [TransformerEncoderBlock::forward_with_bias]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_with_bias<B: Dim, S: Dim, T, R>(
+        &self,
+        src: Tensor<(B, S, Const<M>), E, D, T>,
+        bias: Tensor<(Const<H>, S, S), E, D, R>,
+    ) -> Result<Tensor<(B, S, Const<M>), E, D, T>, D::Err>
+    where
+        T: Tape<E, D> + Merge<R>,
+        R: Tape<E, D>,
+    {
+        let (src, tape) = src.split_tape();
+        let x = self
+            .self_attn
+            .try_forward_with_bias((src.clone().put_tape(tape), src.clone(), src.clone()), bias)?;
+        let x = x.try_add(src)?;
+        let x = crate::hooks::try_forward(&self.norm1, x)?;
+        let x = crate::hooks::try_forward(&self.ff, x)?;
+        crate::hooks::try_forward(&self.norm2, x)
+    }
 }
 
 #[cfg(test)]
 #[allow(clippy::excessive_precision)]
 mod tests {
     use super::*;
-    use crate::{shapes::Rank3, tensor::*, tests::*};
+    use crate::{
+        optim::*,
+        shapes::{Rank1, Rank3},
+        tensor::*,
+        tensor_ops::*,
+        tests::*,
+    };
 
     #[test]
     fn test_encoder_block_forward() {
@@ -207,4 +401,151 @@ mod tests {
             ]
         );
     }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_encoder_4x_ff_dim() {
+        let dev: TestDevice = Default::default();
+        let _: TransformerEncoderBlock<8, 2, 32, TestDtype, TestDevice> =
+            dev.build_module::<builder::TransformerEncoderBlock4x<8, 2>, TestDtype>();
+        let _: TransformerEncoder<8, 2, 32, 3, TestDtype, TestDevice> =
+            dev.build_module::<builder::TransformerEncoder4x<8, 2, 3>, TestDtype>();
+    }
+
+    #[test]
+    fn test_encoder_batched_matches_unbatched() {
+        let dev: TestDevice = Default::default();
+        let encoder = dev.build_module::<builder::TransformerEncoder<8, 2, 16, 2>, TestDtype>();
+        let x: Tensor<Rank3<2, 4, 8>, TestDtype, _> = dev.sample_normal();
+        let y = encoder.forward(x.clone());
+        let x_array = x.array();
+        let y_array = y.array();
+        for (x_i, y_i) in x_array.iter().zip(y_array.iter()) {
+            let y_i_unbatched = encoder.forward(dev.tensor(*x_i));
+            assert_close!(y_i_unbatched.array(), *y_i);
+        }
+    }
+
+    #[test]
+    fn test_encoder_shared_bias() {
+        let dev: TestDevice = Default::default();
+        let encoder = dev.build_module::<builder::TransformerEncoder<8, 2, 16, 2>, TestDtype>();
+        let x: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let bias: Tensor<Rank3<2, 3, 3>, TestDtype, _> = dev.sample_normal();
+
+        let y = encoder.forward_with_bias(x.leaky_trace(), bias.leaky_trace());
+        let y_value = y.retaped::<NoneTape>();
+        let g = y.exp().sum().backward().get(&bias);
+
+        // the same as passing the bias to each block separately
+        let y0 = encoder.modules[0].forward_with_bias(x.clone(), bias.clone());
+        let y1 = encoder.modules[1].forward_with_bias(y0, bias.clone());
+        assert_close_to_tensor!(y_value, y1);
+
+        // both blocks use the same bias, so it gets the gradients of both. `other` has
+        // the same values as `bias`, but a different id.
+        let other = dev.tensor_from_vec(bias.as_vec(), bias.shape);
+        let y0 = encoder.modules[0].forward_with_bias(x.leaky_trace(), bias.leaky_trace());
+        let g0 = encoder.modules[1]
+            .forward_with_bias(y0, other.leaky_trace())
+            .exp()
+            .sum()
+            .backward();
+        let y0 = encoder.modules[0].forward_with_bias(x.leaky_trace(), other.leaky_trace());
+        let g1 = encoder.modules[1]
+            .forward_with_bias(y0, bias.leaky_trace())
+            .exp()
+            .sum()
+            .backward();
+        assert_ne!(g.array(), [[[0.0; 3]; 3]; 2]);
+        assert_close_to_tensor!(g, g0.get(&bias) + g1.get(&bias));
+    }
+
+    #[test]
+    fn test_encoder_block_dropout() {
+        let dev = TestDevice::seed_from_u64(0);
+        let mut block = dev.build_module::<builder::TransformerEncoderBlock<8, 2, 16>, f64>();
+        assert_eq!(block.dropout.p, 0.0);
+        let x: Tensor<Rank3<2, 3, 8>, f64, _> = dev.sample_normal();
+        let y = block.forward(x.clone());
+
+        // disabled dropout in training is the same as evaluation
+        let y_train = block.forward_mut(x.leaky_trace());
+        assert_close_to_tensor!(y_train.retaped::<NoneTape>(), y, 1e-10);
+
+        // evaluation never drops
+        block.dropout.p = 0.5;
+        assert_close_to_tensor!(block.forward(x.clone()), y, 1e-10);
+        assert_close_to_tensor!(block.forward_mut(x.clone()), y, 1e-10);
+
+        // training drops, and the gradients still reach all of the parameters
+        let y_train = block.forward_mut(x.trace(block.alloc_grads()));
+        assert_ne!(y_train.as_vec(), y.as_vec());
+        let g = y_train.square().mean().backward();
+        assert_ne!(g.get(&block.self_attn.w_v.weight).array(), [[0.0; 8]; 8]);
+        assert_ne!(g.get(&block.ff.0 .2.weight).array(), [[0.0; 16]; 8]);
+
+        // each element is dropped with probability p, and the survivors are scaled up
+        block.dropout.p = 0.3;
+        let ones: Tensor<Rank1<1000>, f64, _> = dev.ones();
+        let dropped = block.try_dropout(ones.leaky_trace()).unwrap().as_vec();
+        let zeros = dropped.iter().filter(|&&v| v == 0.0).count();
+        // Binomial(1000, 0.3) has mean 300 and std ~14.5
+        assert!((zeros as f64 - 300.0).abs() < 60.0, "{zeros} zeros");
+        for v in dropped {
+            // `p` is an f32, so the scale is computed from the rounded value
+            let scale = 1.0 / (1.0 - block.dropout.p as f64);
+            assert!(v == 0.0 || (v - scale).abs() < 1e-10, "{v}");
+        }
+    }
+
+    #[test]
+    fn test_encoder_block_ff_mut() {
+        let dev: TestDevice = Default::default();
+
+        let mut encoder =
+            dev.build_module::<builder::TransformerEncoderBlock<8, 2, 16>, TestDtype>();
+        let x: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let y = encoder.forward(x.clone());
+
+        let ff = encoder.ff_mut();
+        ff.0 .2.weight = ff.0 .2.weight.clone() * 2.0;
+        assert_ne!(y.array(), encoder.forward(x).array());
+    }
+
+    #[test]
+    fn test_encoder_block_update_changes_all_params() {
+        let dev: TestDevice = Default::default();
+        let mut block = dev.build_module::<builder::TransformerEncoderBlock<8, 2, 16>, TestDtype>();
+        let before = block.clone();
+
+        let x: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        // the output is layer normalized, so weight it to get a loss that isn't ~constant
+        let w: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let g = (block.forward(x.trace(block.alloc_grads())) * w)
+            .sum()
+            .backward();
+        let mut opt = Sgd::new(&block, Default::default());
+        opt.update(&mut block, &g).expect("");
+        // see the MultiHeadAttention test for why the key bias isn't updated
+        assert_params_changed(&before, &block, &["w_k.bias"]);
+    }
+
+    #[test]
+    fn test_encoder_update_changes_all_params() {
+        let dev: TestDevice = Default::default();
+        let mut encoder = dev.build_module::<builder::TransformerEncoder<8, 2, 16, 2>, TestDtype>();
+        let before = encoder.clone();
+
+        let x: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        // the output is layer normalized, so weight it to get a loss that isn't ~constant
+        let w: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let g = (encoder.forward(x.trace(encoder.alloc_grads())) * w)
+            .sum()
+            .backward();
+        let mut opt = Sgd::new(&encoder, Default::default());
+        opt.update(&mut encoder, &g).expect("");
+        // see the MultiHeadAttention test for why the key bias isn't updated
+        assert_params_changed(&before, &encoder, &["w_k.bias"]);
+    }
 }
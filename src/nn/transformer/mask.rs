@@ -0,0 +1,106 @@
+use num_traits::Float;
+use std::collections::BTreeMap;
+
+use crate::{shapes::Dtype, tensor::*, tensor_ops::Device};
+
+/// A cache of causal attention masks, keyed by sequence length.
+///
+/// The mask for a sequence of length `S` has shape `(S, S)`, and is added to the
+/// attention logits before the softmax. It is `0` where key `j <= i` for query `i`,
+/// and `-inf` above the diagonal, so positions can't attend to later positions.
+///
+/// Masks are built the first time a length is requested, and returned from the
+/// cache afterwards. Keep one cache around for a whole generation loop to avoid
+/// rebuilding the mask every step. See [super::MultiHeadAttention::forward_causal()].
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let mut cache: CausalMask<f32, Cpu> = Default::default();
+/// let mask = cache.get(&dev, 3);
+/// let inf = f32::NEG_INFINITY;
+/// assert_eq!(mask.as_vec(), [0.0, inf, inf, 0.0, 0.0, inf, 0.0, 0.0, 0.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CausalMask<E, D: Storage<E>> {
+    masks: BTreeMap<usize, Tensor<(usize, usize), E, D>>,
+}
+
+impl<E, D: Storage<E>> Default for CausalMask<E, D> {
+    fn default() -> Self {
+        Self {
+            masks: Default::default(),
+        }
+    }
+}
+
+impl<E: Dtype + Float, D: Device<E>> CausalMask<E, D> {
+    /// Returns the `(size, size)` mask, building it if it isn't cached yet.
+    pub fn get(&mut self, device: &D, size: usize) -> Tensor<(usize, usize), E, D> {
+        self.try_get(device, size).unwrap()
+    }
+
+    /// Fallible version of [CausalMask::get]
+    pub fn try_get(
+        &mut self,
+        device: &D,
+        size: usize,
+    ) -> Result<Tensor<(usize, usize), E, D>, D::Err> {
+        if let Some(mask) = self.masks.get(&size) {
+            return Ok(mask.clone());
+        }
+        let mask = device.try_upper_tri_like(&(size, size), E::neg_infinity(), 1)?;
+        self.masks.insert(size, mask.clone());
+        Ok(mask)
+    }
+
+    /// Removes all cached masks.
+    pub fn clear(&mut self) {
+        self.masks.clear();
+    }
+}
+
+/// Whether attention can look at every position, or only at earlier ones. Lets the same
+/// [super::MultiHeadAttention] weights be used both as an encoder and as a decoder, see
+/// [super::MultiHeadAttention::forward_with_mode()].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AttentionMode {
+    /// Every query attends to every key, as in an encoder.
+    #[default]
+    Bidirectional,
+    /// Query `i` only attends to keys `j <= i`, as in a decoder. See [CausalMask].
+    Causal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_causal_mask_cache() {
+        let dev: TestDevice = Default::default();
+        let mut cache: CausalMask<TestDtype, _> = Default::default();
+
+        let a = cache.get(&dev, 4);
+        let inf = TestDtype::neg_infinity();
+        let fresh: Tensor<(usize, usize), TestDtype, _> = dev.upper_tri_like(&(4, 4), inf, 1);
+        assert_eq!(a.as_vec(), fresh.as_vec());
+        let a_vec = a.as_vec();
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(a_vec[i * 4 + j] == inf, j > i);
+            }
+        }
+
+        // the second request for the same size returns the cached tensor
+        let b = cache.get(&dev, 4);
+        assert_eq!(a.id, b.id);
+        assert_eq!(cache.masks.len(), 1);
+
+        let c = cache.get(&dev, 2);
+        assert_eq!(c.shape, (2, 2));
+        assert_ne!(a.id, c.id);
+        assert_eq!(cache.masks.len(), 2);
+    }
+}
@@ -0,0 +1,204 @@
+use num_traits::Float;
+use rand_distr::uniform::SampleUniform;
+
+use crate::{nn::modules::*, shapes::*, tensor::*, tensor_ops::*};
+
+use super::{mha::MultiHeadAttention, CausalMask};
+
+pub mod builder {
+    #[derive(Debug, Clone)]
+    pub struct MaskedMultiHeadAttention<
+        const EMBED_DIM: usize,
+        const NUM_HEADS: usize,
+        const K_DIM: usize = EMBED_DIM,
+        const V_DIM: usize = EMBED_DIM,
+    >;
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E: Dtype, D: Device<E>>
+    BuildOnDevice<D, E> for builder::MaskedMultiHeadAttention<M, H, K, V>
+where
+    MaskedMultiHeadAttention<M, H, K, V, E, D>: BuildModule<D, E>,
+{
+    type Built = MaskedMultiHeadAttention<M, H, K, V, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        #[allow(clippy::let_unit_value)]
+        let _ = super::mha::builder::MultiHeadAttention::<M, H, K, V>::TYPE_CHECK;
+        Self::Built::try_build(device)
+    }
+}
+
+/// Causal self attention, where position `i` only attends to positions `j <= i`. This
+/// is a thin wrapper around [MultiHeadAttention] that always uses
+/// [MultiHeadAttention::forward_causal()], so it can be used in place of
+/// [MultiHeadAttention] in a decoder only model.
+///
+/// Both unbatched `(S, EMBED_DIM)` and batched `(B, S, EMBED_DIM)` inputs are supported,
+/// either as a single tensor, or as `(query, key, value)` with the same sequence length.
+/// The mask is rebuilt on every forward, use [MultiHeadAttention::forward_causal()]
+/// with a [CausalMask] to cache it.
+///
+/// Generics are the same as [MultiHeadAttention].
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let attn = dev.build_module::<MaskedMultiHeadAttention<8, 2>, f32>();
+/// let x: Tensor<Rank2<3, 8>, f32, _> = dev.sample_normal();
+/// let _: Tensor<Rank2<3, 8>, f32, _> = attn.forward(x);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaskedMultiHeadAttention<
+    const EMBED_DIM: usize,
+    const NUM_HEADS: usize,
+    const K_DIM: usize,
+    const V_DIM: usize,
+    E: Dtype,
+    D: Storage<E>,
+> {
+    pub mha: MultiHeadAttention<EMBED_DIM, NUM_HEADS, K_DIM, V_DIM, E, D>,
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D: Device<E>>
+    TensorCollection<E, D> for MaskedMultiHeadAttention<M, H, K, V, E, D>
+where
+    E: Dtype + Float + SampleUniform,
+{
+    type To<E2: Dtype, D2: Device<E2>> = MaskedMultiHeadAttention<M, H, K, V, E2, D2>;
+
+    fn iter_tensors<Vi: ModuleVisitor<Self, E, D>>(
+        visitor: &mut Vi,
+    ) -> Result<Option<Self::To<Vi::E2, Vi::D2>>, Vi::Err> {
+        visitor.visit_fields(Self::module("mha", |s| &s.mha, |s| &mut s.mha), |mha| {
+            MaskedMultiHeadAttention { mha }
+        })
+    }
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, S, T>
+    Module<(
+        Tensor<(S, Const<M>), E, D, T>,
+        Tensor<(S, Const<M>), E, D>,
+        Tensor<(S, Const<M>), E, D>,
+    )> for MaskedMultiHeadAttention<M, H, K, V, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    S: Dim,
+    T: Tape<E, D>,
+{
+    type Output = Tensor<(S, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(
+        &self,
+        (q, k, v): (
+            Tensor<(S, Const<M>), E, D, T>,
+            Tensor<(S, Const<M>), E, D>,
+            Tensor<(S, Const<M>), E, D>,
+        ),
+    ) -> Result<Self::Output, D::Err> {
+        let s = q.shape.0;
+        let q = q.try_broadcast_like(&(Const::<1>, s, Const::<M>))?;
+        let k = k.try_broadcast_like(&(Const::<1>, s, Const::<M>))?;
+        let v = v.try_broadcast_like(&(Const::<1>, s, Const::<M>))?;
+        let out = self.try_forward((q, k, v))?;
+        out.try_reshape_like(&(s, Const::<M>))
+    }
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, B, S, T>
+    Module<(
+        Tensor<(B, S, Const<M>), E, D, T>,
+        Tensor<(B, S, Const<M>), E, D>,
+        Tensor<(B, S, Const<M>), E, D>,
+    )> for MaskedMultiHeadAttention<M, H, K, V, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    B: Dim,
+    S: Dim,
+    T: Tape<E, D>,
+{
+    type Output = Tensor<(B, S, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(
+        &self,
+        qkv: (
+            Tensor<(B, S, Const<M>), E, D, T>,
+            Tensor<(B, S, Const<M>), E, D>,
+            Tensor<(B, S, Const<M>), E, D>,
+        ),
+    ) -> Result<Self::Output, D::Err> {
+        self.mha.try_forward_causal(qkv, &mut CausalMask::default())
+    }
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, Src> Module<Src>
+    for MaskedMultiHeadAttention<M, H, K, V, E, D>
+where
+    E: Dtype,
+    D: Device<E>,
+    Src: SplitTape,
+    Self: Module<(Src, Src::NoTape, Src::NoTape), Output = Src, Error = D::Err>,
+{
+    type Output = Src;
+    type Error = D::Err;
+
+    fn try_forward(&self, src: Src) -> Result<Self::Output, D::Err> {
+        let (src, tape) = src.split_tape();
+        self.try_forward((src.clone().put_tape(tape), src.clone(), src))
+    }
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E: Dtype, D: Device<E>>
+    NonMutableModule for MaskedMultiHeadAttention<M, H, K, V, E, D>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use num_traits::ToPrimitive;
+
+    #[test]
+    fn test_masked_attention_weights_lower_triangular() {
+        let dev: TestDevice = Default::default();
+        let mut attn = dev.build_module::<builder::MaskedMultiHeadAttention<3, 1>, TestDtype>();
+
+        // with identity value & output projections, attending over the identity
+        // matrix outputs the attention weights themselves.
+        let eye = dev
+            .tensor([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+            .to_dtype::<TestDtype>();
+        attn.mha.w_v.weight.copy_from(&eye.as_vec());
+        attn.mha.w_v.bias.fill_with_zeros();
+        attn.mha.w_o.weight.copy_from(&eye.as_vec());
+        attn.mha.w_o.bias.fill_with_zeros();
+
+        let weights = attn.forward(eye.leaky_trace());
+        let w = weights.retaped::<NoneTape>();
+        let w_arr = w.array();
+        for (i, row) in w_arr.iter().enumerate() {
+            for v in row[i + 1..].iter() {
+                assert_eq!(v.to_f64().unwrap(), 0.0);
+            }
+        }
+        assert_close_to_literal!(w.clone().sum::<Rank1<3>, Axis<1>>(), [1.0; 3]);
+        assert_close_to_literal!(w.clone().select(dev.tensor(0)), [1.0, 0.0, 0.0]);
+
+        // the batched path masks the same way
+        let batched = attn.forward(eye.clone().broadcast::<Rank3<2, 3, 3>, _>());
+        assert_close_to_tensor!(batched, w.broadcast::<Rank3<2, 3, 3>, _>());
+
+        // the first position can't see the others, so it gets no gradient from them
+        let g = weights.select(dev.tensor(0)).sum().backward();
+        let g = g.get(&eye).array();
+        assert_ne!(g[0], [0.0; 3]);
+        assert_eq!(g[1], [0.0; 3]);
+        assert_eq!(g[2], [0.0; 3]);
+    }
+}
@@ -1,4 +1,5 @@
 use num_traits::Float;
+use rand::Rng;
 use rand_distr::uniform::SampleUniform;
 
 use crate::{nn::modules::*, shapes::*, tensor::*, tensor_ops::*};
@@ -85,6 +86,34 @@ where
     }
 }
 
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E: Dtype, D: Storage<E>>
+    MultiHeadAttention<M, H, K, V, E, D>
+{
+    /// Creates an attention layer from existing query, key, value and output projections,
+    /// e.g. when importing weights from another framework.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let w_q = dev.build_module::<Linear<8, 8>, f32>();
+    /// let w_k = dev.build_module::<Linear<8, 8>, f32>();
+    /// let w_v = dev.build_module::<Linear<8, 8>, f32>();
+    /// let w_o = dev.build_module::<Linear<8, 8>, f32>();
+    /// let mha: modules::MultiHeadAttention<8, 2, 8, 8, f32, Cpu> =
+    ///     modules::MultiHeadAttention::from_projections(w_q, w_k, w_v, w_o);
+    /// ```
+    pub fn from_projections(
+        w_q: Linear<M, K, E, D>,
+        w_k: Linear<M, K, E, D>,
+        w_v: Linear<M, V, E, D>,
+        w_o: Linear<V, M, E, D>,
+    ) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = builder::MultiHeadAttention::<M, H, K, V>::TYPE_CHECK;
+        Self { w_q, w_k, w_v, w_o }
+    }
+}
+
 impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, S1, S2, T>
     Module<(
         Tensor<(S1, Const<M>), E, D, T>,
@@ -151,36 +180,850 @@ where
         assert_eq!(q.shape.0, v.shape.0);
         assert_eq!(k.shape.1, v.shape.1);
 
+        self.try_attend((q, k, v), Ok)
+    }
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D>
+    MultiHeadAttention<M, H, K, V, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+{
+    /// Batched attention where `bias` is added to the scaled attention logits
+    /// before the softmax. This can be used for relative position biases, ALiBi,
+    /// or any custom additive mask.
+    ///
+    /// `bias` has shape `(NUM_HEADS, S1, S2)` and is broadcast over the batch dimension.
+    /// If `bias` has an [OwnedTape], it is merged into the output's tape, so gradients
+    /// flow back into `bias`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    /// let q: Tensor<Rank3<1, 3, 8>, f32, _> = dev.sample_normal();
+    /// let kv: Tensor<Rank3<1, 4, 8>, f32, _> = dev.sample_normal();
+    /// let bias: Tensor<Rank3<2, 3, 4>, f32, _> = dev.sample_normal();
+    /// let y = mha.forward_with_bias((q, kv.clone(), kv), bias);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_with_bias<B: Dim, S1: Dim, S2: Dim, T, R>(
+        &self,
+        qkv: (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        bias: Tensor<(Const<H>, S1, S2), E, D, R>,
+    ) -> Tensor<(B, S1, Const<M>), E, D, T>
+    where
+        T: Tape<E, D> + Merge<R>,
+        R: Tape<E, D>,
+    {
+        self.try_forward_with_bias(qkv, bias).unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_with_bias]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_with_bias<B: Dim, S1: Dim, S2: Dim, T, R>(
+        &self,
+        (q, k, v): (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        bias: Tensor<(Const<H>, S1, S2), E, D, R>,
+    ) -> Result<Tensor<(B, S1, Const<M>), E, D, T>, D::Err>
+    where
+        T: Tape<E, D> + Merge<R>,
+        R: Tape<E, D>,
+    {
+        assert_eq!(q.shape.0, k.shape.0);
+        assert_eq!(q.shape.0, v.shape.0);
+        assert_eq!(k.shape.1, v.shape.1);
+        assert_eq!(q.shape.1, bias.shape.1);
+        assert_eq!(k.shape.1, bias.shape.2);
+
+        self.try_attend((q, k, v), |logits| {
+            let shape = *logits.shape();
+            let bias = bias.try_reshape_like(&(H, shape.2, shape.3))?;
+            logits.try_add(bias.try_broadcast_like(&shape)?)
+        })
+    }
+
+    /// Batched attention where sample `i` only attends to the first `valid_lengths[i]`
+    /// keys/values, so padding at the end of each sequence is ignored. This builds a
+    /// key padding mask from `valid_lengths` and adds it to the attention logits.
+    ///
+    /// Panics if any of the lengths are 0 or larger than `S2`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    /// let q: Tensor<Rank3<2, 3, 8>, f32, _> = dev.sample_normal();
+    /// let kv: Tensor<Rank3<2, 4, 8>, f32, _> = dev.sample_normal();
+    /// let y = mha.forward_with_valid_lengths((q, kv.clone(), kv), [4, 2]);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_with_valid_lengths<const B: usize, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        qkv: (
+            Tensor<(Const<B>, S1, Const<M>), E, D, T>,
+            Tensor<(Const<B>, S2, Const<M>), E, D>,
+            Tensor<(Const<B>, S2, Const<M>), E, D>,
+        ),
+        valid_lengths: [usize; B],
+    ) -> Tensor<(Const<B>, S1, Const<M>), E, D, T> {
+        self.try_forward_with_valid_lengths(qkv, valid_lengths)
+            .unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_with_valid_lengths]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_with_valid_lengths<const B: usize, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        (q, k, v): (
+            Tensor<(Const<B>, S1, Const<M>), E, D, T>,
+            Tensor<(Const<B>, S2, Const<M>), E, D>,
+            Tensor<(Const<B>, S2, Const<M>), E, D>,
+        ),
+        valid_lengths: [usize; B],
+    ) -> Result<Tensor<(Const<B>, S1, Const<M>), E, D, T>, D::Err> {
+        assert_eq!(k.shape.1, v.shape.1);
+
+        let s2 = k.shape.1;
+        let mut mask = std::vec::Vec::with_capacity(B * s2.size());
+        for len in valid_lengths {
+            assert!(
+                0 < len && len <= s2.size(),
+                "valid length {len} must be in 1..={}",
+                s2.size()
+            );
+            for j in 0..s2.size() {
+                mask.push(if j < len {
+                    E::zero()
+                } else {
+                    E::neg_infinity()
+                });
+            }
+        }
+        let mask = q.device.try_tensor_from_vec(mask, (Const::<B>, s2))?;
+
+        self.try_attend((q, k, v), |logits| {
+            let shape = *logits.shape();
+            logits.try_add(mask.try_broadcast_like::<_, Axes2<1, 2>>(&shape)?)
+        })
+    }
+
+    /// Batched attention where keys/values at positions that are `true` in
+    /// `key_padding_mask` are ignored, so padding anywhere in a sequence contributes
+    /// nothing to the output. Unlike [MultiHeadAttention::forward_causal], this masks
+    /// whole columns of the attention weights.
+    ///
+    /// Masked logits are replaced with the smallest finite value of `E` before the softmax,
+    /// and masked weights are set to zero after it. If all the keys of a sample are masked,
+    /// its attention weights are all zero (instead of NaN), and the output for that sample
+    /// is just the bias of `w_o`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    /// let q: Tensor<Rank3<2, 3, 8>, f32, _> = dev.sample_normal();
+    /// let kv: Tensor<Rank3<2, 4, 8>, f32, _> = dev.sample_normal();
+    /// let mask = dev.tensor([[false, false, false, true], [true, false, false, true]]);
+    /// let y = mha.forward_with_key_padding_mask((q, kv.clone(), kv), mask);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_with_key_padding_mask<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        qkv: (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        key_padding_mask: Tensor<(B, S2), bool, D>,
+    ) -> Tensor<(B, S1, Const<M>), E, D, T> {
+        self.try_forward_with_key_padding_mask(qkv, key_padding_mask)
+            .unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_with_key_padding_mask]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_with_key_padding_mask<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        (q, k, v): (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        key_padding_mask: Tensor<(B, S2), bool, D>,
+    ) -> Result<Tensor<(B, S1, Const<M>), E, D, T>, D::Err> {
+        assert_eq!(q.shape.0, k.shape.0);
+        assert_eq!(q.shape.0, v.shape.0);
+        assert_eq!(k.shape.1, v.shape.1);
+        assert_eq!(k.shape.0, key_padding_mask.shape.0);
+        assert_eq!(k.shape.1, key_padding_mask.shape.1);
+
+        let shape = (q.shape.0, H, q.shape.1, k.shape.1);
+        let keep = (!key_padding_mask).try_broadcast_like::<_, Axes2<1, 2>>(&shape)?;
+        let keep_w = keep.clone();
+        let device = q.device.clone();
+
+        let tokens = self.try_attend_heads(
+            (q, k, v),
+            |logits| {
+                let fill: Tensor<_, E, D> = device.try_ones_like(&shape)?;
+                let fill = fill.try_mul(E::min_value())?;
+                keep.try_choose(logits, fill)
+            },
+            |weights| {
+                let zeros: Tensor<_, E, D> = device.try_zeros_like(&shape)?;
+                keep_w.try_choose(weights, zeros)
+            },
+        )?;
+        crate::hooks::try_forward(&self.w_o, tokens)
+    }
+
+    /// Unbatched version of [MultiHeadAttention::forward_with_key_padding_mask], where
+    /// `key_padding_mask` has one entry per key.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    /// let q: Tensor<Rank2<3, 8>, f32, _> = dev.sample_normal();
+    /// let kv: Tensor<Rank2<4, 8>, f32, _> = dev.sample_normal();
+    /// let mask = dev.tensor([false, false, true, true]);
+    /// let y = mha.forward_unbatched_with_key_padding_mask((q, kv.clone(), kv), mask);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_unbatched_with_key_padding_mask<S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        qkv: (
+            Tensor<(S1, Const<M>), E, D, T>,
+            Tensor<(S2, Const<M>), E, D>,
+            Tensor<(S2, Const<M>), E, D>,
+        ),
+        key_padding_mask: Tensor<(S2,), bool, D>,
+    ) -> Tensor<(S1, Const<M>), E, D, T> {
+        self.try_forward_unbatched_with_key_padding_mask(qkv, key_padding_mask)
+            .unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_unbatched_with_key_padding_mask]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_unbatched_with_key_padding_mask<S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        (q, k, v): (
+            Tensor<(S1, Const<M>), E, D, T>,
+            Tensor<(S2, Const<M>), E, D>,
+            Tensor<(S2, Const<M>), E, D>,
+        ),
+        key_padding_mask: Tensor<(S2,), bool, D>,
+    ) -> Result<Tensor<(S1, Const<M>), E, D, T>, D::Err> {
+        assert_eq!(k.shape.0, v.shape.0);
+        let s1 = q.shape.0;
+        let s2 = k.shape.0;
+        let q = q.try_broadcast_like(&(Const::<1>, s1, Const::<M>))?;
+        let k = k.try_broadcast_like(&(Const::<1>, s2, Const::<M>))?;
+        let v = v.try_broadcast_like(&(Const::<1>, s2, Const::<M>))?;
+        let mask = key_padding_mask.try_broadcast_like(&(Const::<1>, s2))?;
+        let out = self.try_forward_with_key_padding_mask((q, k, v), mask)?;
+        out.try_reshape_like(&(s1, Const::<M>))
+    }
+
+    /// Batched self attention where position `i` only attends to positions `j <= i`.
+    /// The `(S, S)` mask comes from `mask`, so a cache that is kept across calls only
+    /// builds it once per sequence length.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    /// let mut mask = CausalMask::default();
+    /// let x: Tensor<Rank3<1, 3, 8>, f32, _> = dev.sample_normal();
+    /// let y = mha.forward_causal((x.clone(), x.clone(), x), &mut mask);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_causal<B: Dim, S: Dim, T: Tape<E, D>>(
+        &self,
+        qkv: (
+            Tensor<(B, S, Const<M>), E, D, T>,
+            Tensor<(B, S, Const<M>), E, D>,
+            Tensor<(B, S, Const<M>), E, D>,
+        ),
+        mask: &mut CausalMask<E, D>,
+    ) -> Tensor<(B, S, Const<M>), E, D, T> {
+        self.try_forward_causal(qkv, mask).unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_causal]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_causal<B: Dim, S: Dim, T: Tape<E, D>>(
+        &self,
+        (q, k, v): (
+            Tensor<(B, S, Const<M>), E, D, T>,
+            Tensor<(B, S, Const<M>), E, D>,
+            Tensor<(B, S, Const<M>), E, D>,
+        ),
+        mask: &mut CausalMask<E, D>,
+    ) -> Result<Tensor<(B, S, Const<M>), E, D, T>, D::Err> {
+        assert_eq!(q.shape.0, k.shape.0);
+        assert_eq!(q.shape.0, v.shape.0);
+        assert_eq!(q.shape.1, k.shape.1);
+        assert_eq!(k.shape.1, v.shape.1);
+
+        let s = q.shape.1;
+        let mask = mask.try_get(&q.device, s.size())?;
+        let mask = mask.try_reshape_like(&(s, s))?;
+
+        self.try_attend((q, k, v), |logits| {
+            let shape = *logits.shape();
+            logits.try_add(mask.try_broadcast_like::<_, Axes2<0, 1>>(&shape)?)
+        })
+    }
+
+    /// Batched self attention where `mode` picks at runtime whether positions can attend
+    /// to later positions. [AttentionMode::Bidirectional] is the same as [Module::forward()],
+    /// and [AttentionMode::Causal] is the same as [MultiHeadAttention::forward_causal()]
+    /// with a fresh [CausalMask].
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    /// let x: Tensor<Rank3<1, 3, 8>, f32, _> = dev.sample_normal();
+    /// let enc = mha.forward_with_mode((x.clone(), x.clone(), x.clone()), AttentionMode::Bidirectional);
+    /// let dec = mha.forward_with_mode((x.clone(), x.clone(), x), AttentionMode::Causal);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_with_mode<B: Dim, S: Dim, T: Tape<E, D>>(
+        &self,
+        qkv: (
+            Tensor<(B, S, Const<M>), E, D, T>,
+            Tensor<(B, S, Const<M>), E, D>,
+            Tensor<(B, S, Const<M>), E, D>,
+        ),
+        mode: AttentionMode,
+    ) -> Tensor<(B, S, Const<M>), E, D, T> {
+        self.try_forward_with_mode(qkv, mode).unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_with_mode]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_with_mode<B: Dim, S: Dim, T: Tape<E, D>>(
+        &self,
+        qkv: (
+            Tensor<(B, S, Const<M>), E, D, T>,
+            Tensor<(B, S, Const<M>), E, D>,
+            Tensor<(B, S, Const<M>), E, D>,
+        ),
+        mode: AttentionMode,
+    ) -> Result<Tensor<(B, S, Const<M>), E, D, T>, D::Err> {
+        match mode {
+            AttentionMode::Bidirectional => self.try_forward(qkv),
+            AttentionMode::Causal => self.try_forward_causal(qkv, &mut CausalMask::default()),
+        }
+    }
+
+    /// Batched attention without the final `w_o` projection. Returns the outputs of all
+    /// the heads concatenated, with shape `(B, S1, V_DIM)`, so a different projection can be
+    /// applied. `self.w_o.forward()` of the result is the same as [Module::forward()].
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2, 8, 6>, f32>();
+    /// let x: Tensor<Rank3<1, 3, 8>, f32, _> = dev.sample_normal();
+    /// let heads: Tensor<Rank3<1, 3, 6>, f32, _> = mha.forward_no_output_proj((x.clone(), x.clone(), x));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_no_output_proj<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        qkv: (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+    ) -> Tensor<(B, S1, Const<V>), E, D, T> {
+        self.try_forward_no_output_proj(qkv).unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_no_output_proj]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_no_output_proj<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        (q, k, v): (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+    ) -> Result<Tensor<(B, S1, Const<V>), E, D, T>, D::Err> {
+        assert_eq!(q.shape.0, k.shape.0);
+        assert_eq!(q.shape.0, v.shape.0);
+        assert_eq!(k.shape.1, v.shape.1);
+
+        self.try_attend_heads((q, k, v), Ok, Ok)
+    }
+
+    /// Unbatched version of [MultiHeadAttention::forward_no_output_proj], for inputs of
+    /// shape `(S, M)`. Returns the concatenated heads with shape `(S1, V_DIM)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2, 8, 6>, f32>();
+    /// let x: Tensor<Rank2<3, 8>, f32, _> = dev.sample_normal();
+    /// let heads: Tensor<Rank2<3, 6>, f32, _> =
+    ///     mha.forward_unbatched_no_output_proj((x.clone(), x.clone(), x));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_unbatched_no_output_proj<S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        qkv: (
+            Tensor<(S1, Const<M>), E, D, T>,
+            Tensor<(S2, Const<M>), E, D>,
+            Tensor<(S2, Const<M>), E, D>,
+        ),
+    ) -> Tensor<(S1, Const<V>), E, D, T> {
+        self.try_forward_unbatched_no_output_proj(qkv).unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_unbatched_no_output_proj]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_unbatched_no_output_proj<S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        (q, k, v): (
+            Tensor<(S1, Const<M>), E, D, T>,
+            Tensor<(S2, Const<M>), E, D>,
+            Tensor<(S2, Const<M>), E, D>,
+        ),
+    ) -> Result<Tensor<(S1, Const<V>), E, D, T>, D::Err> {
+        assert_eq!(k.shape.0, v.shape.0);
+        let s1 = q.shape.0;
+        let s2 = k.shape.0;
+        let q = q.try_broadcast_like(&(Const::<1>, s1, Const::<M>))?;
+        let k = k.try_broadcast_like(&(Const::<1>, s2, Const::<M>))?;
+        let v = v.try_broadcast_like(&(Const::<1>, s2, Const::<M>))?;
+        let heads = self.try_forward_no_output_proj((q, k, v))?;
+        heads.try_reshape_like(&(s1, Const::<V>))
+    }
+
+    /// Batched attention with dropout applied to the attention weights after the softmax.
+    /// The dropout mask is sampled from `rng`, so two forwards with identically seeded
+    /// rngs produce identical outputs.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # use rand::{rngs::StdRng, SeedableRng};
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    /// let x: Tensor<Rank3<1, 3, 8>, f32, _> = dev.sample_normal();
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let y = mha.forward_with_dropout((x.leaky_trace(), x.clone(), x), 0.1, &mut rng);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_with_dropout<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>, R: Rng>(
+        &self,
+        qkv: (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        prob: f64,
+        rng: &mut R,
+    ) -> Tensor<(B, S1, Const<M>), E, D, T> {
+        self.try_forward_with_dropout(qkv, prob, rng).unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_with_dropout]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_with_dropout<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>, R: Rng>(
+        &self,
+        (q, k, v): (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        prob: f64,
+        rng: &mut R,
+    ) -> Result<Tensor<(B, S1, Const<M>), E, D, T>, D::Err> {
+        assert_eq!(q.shape.0, k.shape.0);
+        assert_eq!(q.shape.0, v.shape.0);
+        assert_eq!(k.shape.1, v.shape.1);
+
+        let tokens = self.try_attend_heads((q, k, v), Ok, |weights| {
+            weights.try_dropout_with_rng(prob, rng)
+        })?;
+        crate::hooks::try_forward(&self.w_o, tokens)
+    }
+
+    /// Batched attention for inference, when none of the inputs are traced. This skips
+    /// the tape and forward hooks entirely, and applies the `1 / sqrt(head_dim)` scale to the
+    /// projected queries instead of to the `(B, NUM_HEADS, S1, S2)` attention logits. The
+    /// result is the same as [Module::forward()] up to floating point error.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    /// let x: Tensor<Rank3<1, 3, 8>, f32, _> = dev.sample_normal();
+    /// let y = mha.forward_inference((x.clone(), x.clone(), x));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_inference<B: Dim, S1: Dim, S2: Dim>(
+        &self,
+        qkv: (
+            Tensor<(B, S1, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+    ) -> Tensor<(B, S1, Const<M>), E, D> {
+        self.try_forward_inference(qkv).unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_inference]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_inference<B: Dim, S1: Dim, S2: Dim>(
+        &self,
+        (q, k, v): (
+            Tensor<(B, S1, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+    ) -> Result<Tensor<(B, S1, Const<M>), E, D>, D::Err> {
+        assert_eq!(q.shape.0, k.shape.0);
+        assert_eq!(q.shape.0, v.shape.0);
+        assert_eq!(k.shape.1, v.shape.1);
+
         let b = q.shape.0;
         let s1 = q.shape.1;
         let s2 = v.shape.1;
 
-        let v = self.w_v.try_forward(v.retaped::<T>())?;
+        let v = self.w_v.try_forward(v)?;
         let v = v.try_reshape_like(&(b, s2, H, V / H))?;
         let v = v.try_permute::<_, Axes4<0, 2, 1, 3>>()?;
 
-        let k = self.w_k.try_forward(k.retaped::<T>())?;
+        let k = self.w_k.try_forward(k)?;
         let k = k.try_reshape_like(&(b, s2, H, K / H))?;
         let k = k.try_permute::<_, Axes4<0, 2, 3, 1>>()?;
 
-        let q = self.w_q.try_forward(q)?;
+        let scalar: E = E::from_f64(1.0 / ((K / H) as f64).sqrt()).unwrap();
+        let q = self.w_q.try_forward(q)?.try_mul(scalar)?;
         let q = q.try_reshape_like(&(b, s1, H, K / H))?;
         let q = q.try_permute::<_, Axes4<0, 2, 1, 3>>()?;
 
-        // Get weights
+        let weights = try_attention_scores(q, k, 1.0, true)?.try_softmax::<Axis<3>>()?;
+        let tokens = weights.try_matmul(v)?;
+        let tokens = tokens.try_permute::<_, Axes4<0, 2, 1, 3>>()?;
+        let tokens = tokens.try_reshape_like(&(b, s1, Const::<V>))?;
+        self.w_o.try_forward(tokens)
+    }
+
+    /// Batched attention computed over chunks of `chunk_size` keys/values at a time, using
+    /// an online softmax. This never materializes the full `(B, NUM_HEADS, S1, S2)` attention
+    /// matrix, only `(B, NUM_HEADS, S1, chunk_size)` pieces of it, which reduces peak memory
+    /// for long sequences. The result is the same as [Module::try_forward] up to floating point
+    /// error.
+    ///
+    /// This also supports backprop, though the tape still holds on to each chunk.
+    ///
+    /// For f16 the logits are still accumulated in f32, but they can't be shifted by their
+    /// row max like in [Module::try_forward], so the scaled logits have to fit in `E`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    /// let q: Tensor<Rank3<1, 3, 8>, f32, _> = dev.sample_normal();
+    /// let kv: Tensor<Rank3<1, 10, 8>, f32, _> = dev.sample_normal();
+    /// let y = mha.forward_chunked((q, kv.clone(), kv), 4);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_chunked<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        qkv: (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        chunk_size: usize,
+    ) -> Tensor<(B, S1, Const<M>), E, D, T> {
+        self.try_forward_chunked(qkv, chunk_size).unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_chunked]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_chunked<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        (q, k, v): (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        chunk_size: usize,
+    ) -> Result<Tensor<(B, S1, Const<M>), E, D, T>, D::Err> {
+        assert_eq!(q.shape.0, k.shape.0);
+        assert_eq!(q.shape.0, v.shape.0);
+        assert_eq!(k.shape.1, v.shape.1);
+        assert!(chunk_size > 0, "chunk_size must be greater than 0");
+
+        let b = q.shape.0;
+        let s1 = q.shape.1;
+        let s2 = k.shape.1.size();
+
+        if s2 == 0 {
+            // with no keys to attend to, each query gets all zero weights, like a fully
+            // masked row, so only the bias of the output projection is left
+            let (q, tape) = q.split_tape();
+            let tokens = q.device.try_zeros_like(&(b, s1, Const::<V>))?;
+            return crate::hooks::try_forward(&self.w_o, tokens.put_tape(tape));
+        }
+
         let scalar: E = E::from_f64(1.0 / ((K / H) as f64).sqrt()).unwrap();
-        let weights = q.try_matmul(k)?.try_mul(scalar)?;
-        let weights = weights.try_softmax::<Axis<3>>()?;
+        let q = crate::hooks::try_forward(&self.w_q, q)?;
+        let q = q.try_reshape_like(&(b, s1, H, K / H))?;
+        let q = q.try_permute::<_, Axes4<0, 2, 1, 3>>()?;
+        let q = q.try_mul(scalar)?;
+        let (q, tape) = q.split_tape();
+        let mut tape = Some(tape);
 
-        // Get new tokens
-        let tokens = weights.try_matmul(v)?;
+        // The running max doesn't change the result of the softmax, so it is kept off the tape.
+        // Each step keeps (running max, softmax denominator, unnormalized output).
+        let mut state: Option<(
+            Tensor<(B, usize, S1), E, D>,
+            Tensor<(B, usize, S1), E, D, T>,
+            Tensor<(B, usize, S1, usize), E, D, T>,
+        )> = None;
+        for start in (0..s2).step_by(chunk_size) {
+            let end = (start + chunk_size).min(s2);
+            let c = end - start;
+
+            let k_c = k.clone().try_slice((.., start..end, ..))?;
+            let k_c = crate::hooks::try_forward(&self.w_k, k_c.retaped::<T>())?;
+            let k_c = k_c.try_reshape_like(&(b, c, H, K / H))?;
+            let k_c = k_c.try_permute::<_, Axes4<0, 2, 3, 1>>()?;
+
+            let v_c = v.clone().try_slice((.., start..end, ..))?;
+            let v_c = crate::hooks::try_forward(&self.w_v, v_c.retaped::<T>())?;
+            let v_c = v_c.try_reshape_like(&(b, c, H, V / H))?;
+            let v_c = v_c.try_permute::<_, Axes4<0, 2, 1, 3>>()?;
+
+            let q_c = match tape.take() {
+                Some(tape) => q.clone().put_tape(tape),
+                None => q.clone().retaped::<T>(),
+            };
+            // the online softmax needs the same logits in every chunk, so they aren't shifted
+            let logits = try_attention_scores(q_c, k_c, 1.0, false)?;
+            let shape = *logits.shape();
+            let m_c = logits
+                .retaped::<NoneTape>()
+                .try_max::<(B, usize, S1), Axis<3>>()?;
+
+            state = Some(match state.take() {
+                None => {
+                    let p = logits.try_sub(m_c.clone().try_broadcast_like(&shape)?)?;
+                    let (p, p_tape) = p.try_exp()?.split_tape();
+                    let l = p.clone().put_tape(p_tape).try_sum::<_, Axis<3>>()?;
+                    let acc = p.retaped::<T>().try_matmul(v_c)?;
+                    (m_c, l, acc)
+                }
+                Some((m, l, acc)) => {
+                    let m_new = m.clone().try_maximum(m_c)?;
+                    let correction = m.try_sub(m_new.clone())?.try_exp()?;
+                    let p = logits.try_sub(m_new.clone().try_broadcast_like(&shape)?)?;
+                    let (p, p_tape) = p.try_exp()?.split_tape();
+                    let l = l
+                        .try_mul(correction.clone())?
+                        .try_add(p.clone().put_tape(p_tape).try_sum::<_, Axis<3>>()?)?;
+                    let acc_shape = *acc.shape();
+                    let acc = acc
+                        .try_mul(correction.try_broadcast_like(&acc_shape)?)?
+                        .try_add(p.retaped::<T>().try_matmul(v_c)?)?;
+                    (m_new, l, acc)
+                }
+            });
+        }
+
+        let (_, l, acc) = state.unwrap();
+        let acc_shape = *acc.shape();
+        let tokens = acc.try_div(l.try_broadcast_like(&acc_shape)?)?;
         let tokens = tokens.try_permute::<_, Axes4<0, 2, 1, 3>>()?;
         let tokens = tokens.try_reshape_like(&(b, s1, Const::<V>))?;
 
-        self.w_o.try_forward(tokens)
+        crate::hooks::try_forward(&self.w_o, tokens)
+    }
+
+    /// Batched attention that also returns the attention weights after the softmax, with
+    /// shape `(B, NUM_HEADS, S1, S2)`. The weights are detached from the tape, and are meant
+    /// for inspecting what each head attends to, e.g. with [attention_entropy()].
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    /// let x: Tensor<Rank3<1, 3, 8>, f32, _> = dev.sample_normal();
+    /// let (y, weights) = mha.forward_with_weights((x.clone(), x.clone(), x));
+    /// let _: Tensor<Rank4<1, 2, 3, 3>, f32, _> = weights;
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn forward_with_weights<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        qkv: (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+    ) -> (
+        Tensor<(B, S1, Const<M>), E, D, T>,
+        Tensor<(B, Const<H>, S1, S2), E, D>,
+    ) {
+        self.try_forward_with_weights(qkv).unwrap()
+    }
+
+    /// Fallible version of [MultiHeadAttention::forward_with_weights]
+    #[allow(clippy::type_complexity)]
+    pub fn try_forward_with_weights<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
+        &self,
+        (q, k, v): (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+    ) -> Result<
+        (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, Const<H>, S1, S2), E, D>,
+        ),
+        D::Err,
+    > {
+        assert_eq!(q.shape.0, k.shape.0);
+        assert_eq!(q.shape.0, v.shape.0);
+        assert_eq!(k.shape.1, v.shape.1);
+
+        let shape = (q.shape.0, Const::<H>, q.shape.1, k.shape.1);
+        let mut weights = None;
+        let tokens = self.try_attend_heads((q, k, v), Ok, |w| {
+            weights = Some(w.retaped::<NoneTape>());
+            Ok(w)
+        })?;
+        let weights = weights.unwrap().try_reshape_like(&shape)?;
+        Ok((crate::hooks::try_forward(&self.w_o, tokens)?, weights))
+    }
+
+    /// Computes attention, calling `f` on the scaled attention logits of
+    /// shape `(B, NUM_HEADS, S1, S2)` before applying softmax.
+    #[allow(clippy::type_complexity)]
+    fn try_attend<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>, F>(
+        &self,
+        qkv: (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        f: F,
+    ) -> Result<Tensor<(B, S1, Const<M>), E, D, T>, D::Err>
+    where
+        F: FnOnce(
+            Tensor<(B, usize, S1, S2), E, D, T>,
+        ) -> Result<Tensor<(B, usize, S1, S2), E, D, T>, D::Err>,
+    {
+        let tokens = self.try_attend_heads(qkv, f, Ok)?;
+        crate::hooks::try_forward(&self.w_o, tokens)
+    }
+
+    /// [MultiHeadAttention::try_attend] without the output projection, returning the
+    /// outputs of all heads concatenated. `g` is called on the attention weights after
+    /// the softmax.
+    #[allow(clippy::type_complexity)]
+    fn try_attend_heads<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>, F, G>(
+        &self,
+        (q, k, v): (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+        f: F,
+        g: G,
+    ) -> Result<Tensor<(B, S1, Const<V>), E, D, T>, D::Err>
+    where
+        F: FnOnce(
+            Tensor<(B, usize, S1, S2), E, D, T>,
+        ) -> Result<Tensor<(B, usize, S1, S2), E, D, T>, D::Err>,
+        G: FnOnce(
+            Tensor<(B, usize, S1, S2), E, D, T>,
+        ) -> Result<Tensor<(B, usize, S1, S2), E, D, T>, D::Err>,
+    {
+        let b = q.shape.0;
+        let s1 = q.shape.1;
+        let s2 = v.shape.1;
+
+        let v = crate::hooks::try_forward(&self.w_v, v.retaped::<T>())?;
+        let v = v.try_reshape_like(&(b, s2, H, V / H))?;
+        let v = v.try_permute::<_, Axes4<0, 2, 1, 3>>()?;
+
+        let k = crate::hooks::try_forward(&self.w_k, k.retaped::<T>())?;
+        let k = k.try_reshape_like(&(b, s2, H, K / H))?;
+        let k = k.try_permute::<_, Axes4<0, 2, 3, 1>>()?;
+
+        let q = crate::hooks::try_forward(&self.w_q, q)?;
+        let q = q.try_reshape_like(&(b, s1, H, K / H))?;
+        let q = q.try_permute::<_, Axes4<0, 2, 1, 3>>()?;
+
+        // Get weights. For f16 the logits are computed in f32 and shifted by their row max,
+        // so they are all <= 0. The softmax then stays in `E`, since its exps can't overflow
+        // and each row sums to at most S2.
+        let scale = 1.0 / ((K / H) as f64).sqrt();
+        let weights = try_attention_scores(q, k, scale, true)?;
+        let weights = f(weights)?;
+        let weights = g(weights.try_softmax::<Axis<3>>()?)?;
+
+        // Get new tokens
+        let tokens = weights.try_matmul(v)?;
+        let tokens = tokens.try_permute::<_, Axes4<0, 2, 1, 3>>()?;
+        tokens.try_reshape_like(&(b, s1, Const::<V>))
     }
 }
 
+/// The entropy `-sum(p * ln(p))` of attention weights along the last axis, i.e. for each
+/// head and query position. Zero weights contribute nothing to the sum, and the
+/// gradient stays finite for them, since `ln(p)` is computed as `ln(max(p, tiny))` where
+/// `tiny` is the smallest positive normal value of `E`.
+///
+/// A head attending uniformly over `S` keys has entropy `ln(S)`, and a head attending to
+/// a single key has entropy `0`, so this is useful to spot collapsed or uniform heads
+/// in the weights returned by [MultiHeadAttention::forward_with_weights()].
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let weights: Tensor<Rank3<2, 3, 4>, f32, _> = dev.tensor([[[0.25; 4]; 3]; 2]);
+/// let entropy: Tensor<Rank2<2, 3>, f32, _> = attention_entropy(weights);
+/// ```
+pub fn attention_entropy<S: Shape, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    weights: Tensor<S, E, D, T>,
+) -> Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, D, T> {
+    try_attention_entropy(weights).unwrap()
+}
+
+/// Fallible version of [attention_entropy()]
+#[allow(clippy::type_complexity)]
+pub fn try_attention_entropy<S: Shape, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    weights: Tensor<S, E, D, T>,
+) -> Result<Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, D, T>, D::Err> {
+    // `0 * ln(0)` is nan in both the forward and the backward of the mul, but the limit
+    // of `p * ln(p)` as `p` goes to 0 is 0
+    let tiny = E::min_positive_value().to_f64().unwrap();
+    let ln_w = weights.with_empty_tape().try_clamp_min(tiny)?.try_ln()?;
+    weights
+        .try_mul(ln_w)?
+        .try_sum::<_, S::LastAxis>()?
+        .try_negate()
+}
+
 impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, Src> Module<Src>
     for MultiHeadAttention<M, H, K, V, E, D>
 where
@@ -302,11 +1145,489 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mha_forward_with_bias() {
+        let dev = TestDevice::seed_from_u64(0);
+
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2>, f64>();
+
+        let q: Tensor<Rank3<2, 3, 8>, f64, _> = dev.sample_normal();
+        let k: Tensor<Rank3<2, 4, 8>, f64, _> = dev.sample_normal();
+        let v: Tensor<Rank3<2, 4, 8>, f64, _> = dev.sample_normal();
+
+        // zero bias is the same as no bias
+        let y = mha.forward((q.clone(), k.clone(), v.clone()));
+        let y0 = mha.forward_with_bias((q.clone(), k.clone(), v.clone()), dev.zeros());
+        assert_close_to_tensor!(y, y0);
+
+        let bias: Tensor<Rank3<2, 3, 4>, f64, _> = dev.sample_normal();
+        let y1 = mha.forward_with_bias((q.clone(), k.clone(), v.clone()), bias.clone());
+        assert!(y.array() != y1.array());
+
+        // check the gradient of bias against finite differences
+        let loss = |b: Tensor<Rank3<2, 3, 4>, f64, _>| {
+            mha.forward_with_bias((q.clone(), k.clone(), v.clone()), b)
+                .square()
+                .mean::<Rank0, _>()
+                .array()
+        };
+        let g = mha
+            .forward_with_bias((q.leaky_trace(), k.clone(), v.clone()), bias.leaky_trace())
+            .square()
+            .mean()
+            .backward();
+        let grad = g.get(&bias).as_vec();
+        let data = bias.as_vec();
+        let eps = 1e-6;
+        for i in 0..data.len() {
+            let mut plus = data.clone();
+            plus[i] += eps;
+            let mut minus = data.clone();
+            minus[i] -= eps;
+            let fd: f64 = (loss(dev.tensor_from_vec(plus, bias.shape))
+                - loss(dev.tensor_from_vec(minus, bias.shape)))
+                / (2.0 * eps);
+            assert!((fd - grad[i]).abs() < 1e-6, "{fd} != {}", grad[i]);
+        }
+    }
+
+    #[test]
+    fn test_mha_forward_with_valid_lengths() {
+        let dev = TestDevice::seed_from_u64(1);
+
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2>, f64>();
+
+        let x: Tensor<Rank3<2, 3, 8>, f64, _> = dev.sample_normal();
+        let y = mha.forward_with_valid_lengths((x.clone(), x.clone(), x.clone()), [3, 2]);
+
+        // changing the padded position of sample 1 doesn't change any outputs
+        let mut data = x.as_vec();
+        for v in data[3 * 8 + 2 * 8..].iter_mut() {
+            *v += 10.0;
+        }
+        let kv = dev.tensor_from_vec(data, x.shape);
+        let y1 = mha.forward_with_valid_lengths((x.clone(), kv.clone(), kv), [3, 2]);
+        assert_close_to_tensor!(y, y1);
+
+        // sample 0 is the same as full attention
+        let full = mha.forward((x.clone(), x.clone(), x.clone()));
+        assert_eq!(y.array()[0], full.array()[0]);
+
+        // sample 1 is the same as attending to the first 2 positions only
+        let x1 = x.array()[1];
+        let q: Tensor<Rank2<3, 8>, f64, _> = dev.tensor(x1);
+        let kv: Tensor<Rank2<2, 8>, f64, _> = dev.tensor([x1[0], x1[1]]);
+        let expected = mha.forward((q, kv.clone(), kv));
+        assert_close_to_literal!(expected, y.array()[1]);
+    }
+
+    #[test]
+    #[should_panic = "valid length 0 must be in 1..=3"]
+    fn test_mha_forward_with_zero_valid_length() {
+        let dev: TestDevice = Default::default();
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2>, TestDtype>();
+        let x: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let _ = mha.forward_with_valid_lengths((x.clone(), x.clone(), x), [0, 2]);
+    }
+
+    #[test]
+    fn test_mha_forward_with_key_padding_mask() {
+        let dev = TestDevice::seed_from_u64(3);
+
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2>, f64>();
+
+        let q: Tensor<Rank3<2, 3, 8>, f64, _> = dev.sample_normal();
+        let kv: Tensor<Rank3<2, 4, 8>, f64, _> = dev.sample_normal();
+        let mask = dev.tensor([[false, false, false, true], [false, true, false, true]]);
+        let y =
+            mha.forward_with_key_padding_mask((q.clone(), kv.clone(), kv.clone()), mask.clone());
+
+        // changing the masked last token doesn't change any outputs
+        let mut data = kv.as_vec();
+        for b in 0..2 {
+            for v in data[b * 4 * 8 + 3 * 8..(b + 1) * 4 * 8].iter_mut() {
+                *v += 10.0;
+            }
+        }
+        let kv2 = dev.tensor_from_vec(data, kv.shape);
+        let y2 = mha.forward_with_key_padding_mask((q.clone(), kv2.clone(), kv2), mask.clone());
+        assert_close_to_tensor!(y, y2);
+
+        // sample 0 is the same as attending to the first 3 positions only
+        let kv0 = kv.array()[0];
+        let expected = mha.forward((
+            dev.tensor(q.array()[0]),
+            dev.tensor([kv0[0], kv0[1], kv0[2]]),
+            dev.tensor([kv0[0], kv0[1], kv0[2]]),
+        ));
+        assert_close_to_literal!(expected, y.array()[0]);
+
+        // the unbatched version matches the batched one
+        let y1 = mha.forward_unbatched_with_key_padding_mask(
+            (
+                dev.tensor(q.array()[1]),
+                dev.tensor(kv.array()[1]),
+                dev.tensor(kv.array()[1]),
+            ),
+            dev.tensor([false, true, false, true]),
+        );
+        assert_close_to_literal!(y1, y.array()[1]);
+    }
+
+    #[test]
+    fn test_mha_forward_with_fully_masked_keys() {
+        let dev: TestDevice = Default::default();
+
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2>, TestDtype>();
+
+        let q: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let kv: Tensor<Rank3<2, 4, 8>, TestDtype, _> = dev.sample_normal();
+        let mask = dev.tensor([[false, false, true, true], [true; 4]]);
+        let y = mha.forward_with_key_padding_mask((q.leaky_trace(), kv.clone(), kv), mask);
+
+        // sample 1 attends to nothing, so its output is just the output projection's bias
+        let y_arr = y.array();
+        let bias = mha.w_o.bias.array();
+        for token in y_arr[1] {
+            assert_eq!(token, bias);
+        }
+
+        let g = y.square().mean().backward();
+        let g_q = g.get(&q).array();
+        for row in g_q[0] {
+            assert!(row.iter().all(|v| v.is_finite()));
+        }
+        assert_eq!(g_q[1], [[TestDtype::zero(); 8]; 3]);
+        assert!(g
+            .get(&mha.w_q.weight)
+            .as_vec()
+            .iter()
+            .all(|v| v.is_finite()));
+    }
+
+    #[test]
+    fn test_mha_forward_causal() {
+        let dev = TestDevice::seed_from_u64(2);
+
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2>, f64>();
+        let mut mask = CausalMask::default();
+
+        let x: Tensor<Rank3<1, 4, 8>, f64, _> = dev.sample_normal();
+        let y = mha.forward_causal((x.clone(), x.clone(), x.clone()), &mut mask);
+        let y = y.array();
+
+        // position i is the same as attending to the first i + 1 positions only
+        for (i, y_i) in y[0].iter().enumerate() {
+            let q = x.clone().slice((.., i..i + 1, ..));
+            let kv = x.clone().slice((.., ..i + 1, ..));
+            let expected = mha.forward((q, kv.clone(), kv)).as_vec();
+            for (a, b) in expected.iter().zip(y_i) {
+                assert!((a - b).abs() < 1e-10, "{a} != {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_mha_forward_with_mode() {
+        let dev: TestDevice = Default::default();
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2>, TestDtype>();
+
+        let x: Tensor<Rank3<1, 4, 8>, TestDtype, _> = dev.sample_normal();
+        let bi = mha.forward_with_mode(
+            (x.clone(), x.clone(), x.clone()),
+            AttentionMode::Bidirectional,
+        );
+        assert_close_to_tensor!(bi, mha.forward(x.clone()));
+        let causal =
+            mha.forward_with_mode((x.clone(), x.clone(), x.clone()), AttentionMode::Causal);
+        assert_close_to_tensor!(
+            causal,
+            mha.forward_causal(
+                (x.clone(), x.clone(), x.clone()),
+                &mut CausalMask::default()
+            )
+        );
+
+        // changing the last position only changes the first output if the first position
+        // can attend to it, i.e. the upper triangle of the causal weights is zero
+        let mut x2 = x.as_vec();
+        for v in x2[3 * 8..].iter_mut() {
+            *v += TestDtype::ONE;
+        }
+        let x2 = dev.tensor_from_vec(x2, *x.shape());
+        let bi2 = mha.forward_with_mode(
+            (x2.clone(), x2.clone(), x2.clone()),
+            AttentionMode::Bidirectional,
+        );
+        let causal2 = mha.forward_with_mode((x2.clone(), x2.clone(), x2), AttentionMode::Causal);
+        assert_eq!(causal.array()[0][..3], causal2.array()[0][..3]);
+        assert_ne!(bi.array()[0][0], bi2.array()[0][0]);
+    }
+
+    #[test]
+    fn test_mha_forward_with_weights() {
+        let dev: TestDevice = Default::default();
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2>, TestDtype>();
+
+        let q: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let kv: Tensor<Rank3<2, 5, 8>, TestDtype, _> = dev.sample_normal();
+        let (y, weights) = mha.forward_with_weights((q.clone(), kv.clone(), kv.clone()));
+        assert_close_to_tensor!(y, mha.forward((q, kv.clone(), kv)));
+        assert_eq!(
+            weights.shape(),
+            &(Const::<2>, Const::<2>, Const::<3>, Const::<5>)
+        );
+        assert_close_to_literal!(weights.sum::<Rank3<2, 2, 3>, _>(), [[[1.0; 3]; 2]; 2]);
+    }
+
+    #[test]
+    fn test_attention_entropy() {
+        let dev: TestDevice = Default::default();
+
+        // uniform attention over S keys has entropy ln(S)
+        let uniform = dev.tensor([[[0.25; 4]; 3]; 2]).to_dtype::<TestDtype>();
+        let entropy: Tensor<Rank2<2, 3>, TestDtype, _> = attention_entropy(uniform);
+        let ln_4 = 4.0f64.ln();
+        assert_close_to_literal!(entropy, [[ln_4; 3]; 2]);
+
+        // one-hot attention has no entropy, even though it contains zeros
+        let one_hot = dev
+            .tensor([[[0.0, 1.0, 0.0], [1.0, 0.0, 0.0]]])
+            .to_dtype::<TestDtype>();
+        assert_close_to_literal!(attention_entropy(one_hot), [[0.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_attention_entropy_causal_grads() {
+        let dev: TestDevice = Default::default();
+        let logits: Tensor<Rank3<2, 4, 4>, TestDtype, _> = dev.sample_normal();
+        let mut mask = [[[false; 4]; 4]; 2];
+        for (i, row) in mask[0].iter_mut().enumerate() {
+            for m in row[i + 1..].iter_mut() {
+                *m = true;
+            }
+        }
+        mask[1] = mask[0];
+        let mask = dev.tensor(mask);
+
+        // masked positions of causal weights are exactly 0
+        let weights = logits
+            .leaky_trace()
+            .masked_fill(&mask, f64::NEG_INFINITY)
+            .softmax::<Axis<2>>();
+        assert_eq!(weights.array()[0][0][1], TestDtype::zero());
+
+        let entropy = attention_entropy(weights);
+        assert!(entropy.array().iter().flatten().all(|e| e.is_finite()));
+        let g = entropy.sum().backward();
+        assert!(g.get(&logits).as_vec().iter().all(|g| g.is_finite()));
+
+        // the first query attends to a single key, so its entropy doesn't depend on the logits
+        let g = g.get(&logits).array();
+        assert_eq!(g[0][0], [TestDtype::zero(); 4]);
+        assert_ne!(g[0][3], [TestDtype::zero(); 4]);
+    }
+
+    #[test]
+    fn test_mha_forward_no_output_proj() {
+        let dev: TestDevice = Default::default();
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2, 6, 4>, TestDtype>();
+
+        let q: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let kv: Tensor<Rank3<2, 5, 8>, TestDtype, _> = dev.sample_normal();
+        let heads = mha.forward_no_output_proj((q.leaky_trace(), kv.clone(), kv.clone()));
+        let y = mha.forward((q.clone(), kv.clone(), kv));
+        assert_close_to_tensor!(mha.w_o.forward(heads.retaped::<NoneTape>()), y);
+
+        // the tape is threaded through, so gradients reach the query
+        let g = heads.square().mean().backward();
+        assert_ne!(g.get(&q).array(), [[[TestDtype::zero(); 8]; 3]; 2]);
+    }
+
+    #[test]
+    fn test_mha_forward_unbatched_no_output_proj() {
+        let dev: TestDevice = Default::default();
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2, 6, 4>, TestDtype>();
+
+        let q: Tensor<Rank2<3, 8>, TestDtype, _> = dev.sample_normal();
+        let kv: Tensor<Rank2<5, 8>, TestDtype, _> = dev.sample_normal();
+        let heads = mha.forward_unbatched_no_output_proj((q.leaky_trace(), kv.clone(), kv.clone()));
+        let y = mha.forward((q.clone(), kv.clone(), kv));
+        assert_close_to_tensor!(mha.w_o.forward(heads.retaped::<NoneTape>()), y);
+
+        let g = heads.square().mean().backward();
+        assert_ne!(g.get(&q).array(), [[TestDtype::zero(); 8]; 3]);
+    }
+
+    #[test]
+    fn test_mha_forward_with_dropout_seeded() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let dev: TestDevice = Default::default();
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2>, TestDtype>();
+        let x: Tensor<Rank3<2, 4, 8>, TestDtype, _> = dev.sample_normal();
+
+        let fwd = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            mha.forward_with_dropout((x.clone(), x.clone(), x.clone()), 0.5, &mut rng)
+        };
+        assert_eq!(fwd(0).array(), fwd(0).array());
+        assert_ne!(fwd(0).array(), fwd(1).array());
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let y = mha.forward_with_dropout((x.clone(), x.clone(), x.clone()), 0.0, &mut rng);
+        assert_close_to_tensor!(y, mha.forward((x.clone(), x.clone(), x)));
+    }
+
+    #[test]
+    fn test_mha_forward_inference() {
+        let dev: TestDevice = Default::default();
+        let mha = dev.build_module::<builder::MultiHeadAttention<12, 4, 8, 16>, TestDtype>();
+        let q: Tensor<Rank3<2, 3, 12>, TestDtype, _> = dev.sample_normal();
+        let kv: Tensor<Rank3<2, 5, 12>, TestDtype, _> = dev.sample_normal();
+        let y = mha.forward_inference((q.clone(), kv.clone(), kv.clone()));
+        assert_close_to_tensor!(y, mha.forward((q, kv.clone(), kv)));
+    }
+
+    #[cfg(feature = "f16")]
+    #[test]
+    fn test_mha_f16_large_inputs() {
+        use crate::nn::modules::Linear;
+        use half::f16;
+
+        fn cast<const I: usize, const O: usize, E1: Dtype, E2: Dtype>(
+            l: &Linear<I, O, E1, TestDevice>,
+        ) -> Linear<I, O, E2, TestDevice>
+        where
+            TestDevice: crate::tensor_ops::ToDtypeKernel<E1, E2>,
+        {
+            Linear {
+                weight: l.weight.clone().to_dtype(),
+                bias: l.bias.clone().to_dtype(),
+            }
+        }
+
+        let dev: TestDevice = Default::default();
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2>, f32>();
+        let mha_f16: MultiHeadAttention<8, 2, 8, 8, f16, _> = MultiHeadAttention::from_projections(
+            cast(&mha.w_q),
+            cast(&mha.w_k),
+            cast(&mha.w_v),
+            cast(&mha.w_o),
+        );
+        let x: Tensor<Rank3<2, 3, 8>, f32, _> = dev.sample_normal() * 1000.0;
+        let x = x.to_dtype::<f16>();
+
+        // q @ k is far outside of f16's range here
+        let y = mha_f16.forward(x.clone()).to_dtype::<f32>().as_vec();
+        assert!(y.iter().all(|v| v.is_finite()));
+        let y_inference = mha_f16.forward_inference((x.clone(), x.clone(), x.clone()));
+        let y_inference = y_inference.to_dtype::<f32>().as_vec();
+
+        // the same weights, rounded to f16, in f32
+        let mha: MultiHeadAttention<8, 2, 8, 8, f32, _> = MultiHeadAttention::from_projections(
+            cast(&mha_f16.w_q),
+            cast(&mha_f16.w_k),
+            cast(&mha_f16.w_v),
+            cast(&mha_f16.w_o),
+        );
+        let truth = mha.forward(x.to_dtype::<f32>()).as_vec();
+        let max = truth.iter().fold(0.0f32, |m, v| m.max(v.abs()));
+        for y in [y, y_inference] {
+            for (a, b) in y.iter().zip(truth.iter()) {
+                assert!((a - b).abs() <= 1e-2 * max, "{a} vs {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_mha_from_projections() {
+        let dev: TestDevice = Default::default();
+
+        let w_q = dev.build_module::<crate::nn::builders::Linear<8, 6>, TestDtype>();
+        let w_k = dev.build_module::<crate::nn::builders::Linear<8, 6>, TestDtype>();
+        let w_v = dev.build_module::<crate::nn::builders::Linear<8, 4>, TestDtype>();
+        let w_o = dev.build_module::<crate::nn::builders::Linear<4, 8>, TestDtype>();
+        let mha2: MultiHeadAttention<8, 2, 6, 4, _, _> =
+            MultiHeadAttention::from_projections(w_q, w_k, w_v, w_o);
+
+        let x: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let y = mha2.forward(x.clone());
+
+        let mha = MultiHeadAttention::<8, 2, 6, 4, _, _>::from_projections(
+            mha2.w_q.clone(),
+            mha2.w_k.clone(),
+            mha2.w_v.clone(),
+            mha2.w_o.clone(),
+        );
+        assert_eq!(y.array(), mha.forward(x).array());
+    }
+
+    #[test]
+    fn test_mha_scales_by_sqrt_head_dim() {
+        let dev: TestDevice = Default::default();
+
+        // K != M, so scaling by M or K instead of K / H gives different results
+        let mha = dev.build_module::<builder::MultiHeadAttention<2, 1, 16, 2>, f64>();
+        let q: Tensor<Rank2<3, 2>, f64, _> = dev.sample_normal();
+        let kv: Tensor<Rank2<4, 2>, f64, _> = dev.sample_normal();
+        let y = mha.forward((q.clone(), kv.clone(), kv.clone()));
+
+        let q = mha.w_q.forward(q);
+        let k = mha.w_k.forward(kv.clone());
+        let v = mha.w_v.forward(kv);
+        let weights = (q.matmul(k.permute()) / (16.0f64).sqrt()).softmax::<Axis<1>>();
+        let expected = mha.w_o.forward(weights.matmul(v));
+        assert_close_to_tensor!(y, expected, 1e-10);
+    }
+
+    #[test]
+    fn test_mha_forward_chunked() {
+        let dev = TestDevice::seed_from_u64(3);
+
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2, 6, 4>, f64>();
+
+        let q: Tensor<Rank3<2, 64, 8>, f64, _> = dev.sample_normal();
+        let kv: Tensor<Rank3<2, 64, 8>, f64, _> = dev.sample_normal();
+        let y = mha.forward((q.clone(), kv.clone(), kv.clone()));
+        for chunk_size in [1, 10, 16, 64, 100] {
+            let y1 = mha.forward_chunked((q.clone(), kv.clone(), kv.clone()), chunk_size);
+            assert_close_to_tensor!(y, y1, 1e-10);
+        }
+
+        let g = mha
+            .forward((q.leaky_trace(), kv.clone(), kv.clone()))
+            .square()
+            .mean()
+            .backward();
+        let g1 = mha
+            .forward_chunked((q.leaky_trace(), kv.clone(), kv), 10)
+            .square()
+            .mean()
+            .backward();
+        assert_close_to_tensor!(g.get(&q), g1.get(&q), 1e-10);
+        assert_close_to_tensor!(g.get(&mha.w_k.weight), g1.get(&mha.w_k.weight), 1e-10);
+        assert_close_to_tensor!(g.get(&mha.w_v.bias), g1.get(&mha.w_v.bias), 1e-10);
+    }
+
+    #[test]
+    fn test_mha_forward_chunked_no_keys() {
+        let dev: TestDevice = Default::default();
+        let mha = dev.build_module::<builder::MultiHeadAttention<8, 2>, TestDtype>();
+
+        let q: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let kv: Tensor<(Const<2>, usize, Const<8>), TestDtype, _> =
+            dev.zeros_like(&(Const, 0, Const));
+        let y = mha.forward_chunked((q, kv.clone(), kv), 4);
+        let bias = mha.w_o.bias.array();
+        assert_eq!(y.array(), [[bias; 3]; 2]);
+    }
+
     #[test]
     fn test_backward_updates_all() {
         let dev: TestDevice = Default::default();
 
         let mut mha = dev.build_module::<builder::MultiHeadAttention<12, 4>, TestDtype>();
+        let before = mha.clone();
 
         let q: Tensor<Rank3<2, 3, 12>, TestDtype, _> = dev.sample_normal();
         let k: Tensor<Rank3<2, 4, 12>, TestDtype, _> = dev.sample_normal();
@@ -316,5 +1637,8 @@ mod tests {
 
         let mut opt = Sgd::new(&mha, Default::default());
         opt.update(&mut mha, &g).expect("");
+        // the key bias adds the same amount to every logit of a query, which the softmax
+        // cancels out, so its gradient is always 0
+        assert_params_changed(&before, &mha, &["w_k.bias"]);
     }
 }
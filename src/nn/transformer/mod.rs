@@ -1,9 +1,15 @@
+mod cross_attn;
 mod decoder;
 mod encoder;
+mod mask;
+mod masked_mha;
 mod mha;
 
+pub use cross_attn::*;
 pub use decoder::*;
 pub use encoder::*;
+pub use mask::*;
+pub use masked_mha::*;
 pub use mha::*;
 
 use num_traits::Float;
@@ -23,8 +29,12 @@ pub mod builder {
         const FF_DIM: usize,
     >;
 
+    pub use super::cross_attn::builder::CrossAttention;
     pub use super::decoder::builder::{TransformerDecoder, TransformerDecoderBlock};
     pub use super::encoder::builder::{TransformerEncoder, TransformerEncoderBlock};
+    #[cfg(feature = "nightly")]
+    pub use super::encoder::builder::{TransformerEncoder4x, TransformerEncoderBlock4x};
+    pub use super::masked_mha::builder::MaskedMultiHeadAttention;
     pub use super::mha::builder::MultiHeadAttention;
 }
 
@@ -122,8 +132,8 @@ where
     type Error = D::Err;
 
     fn try_forward(&self, (src, tgt): (Src, Tgt)) -> Result<Self::Output, D::Err> {
-        let (mem, tape) = self.encoder.try_forward(src)?.split_tape();
-        self.decoder.try_forward((tgt.put_tape(tape), mem))
+        let (mem, tape) = crate::hooks::try_forward(&self.encoder, src)?.split_tape();
+        crate::hooks::try_forward(&self.decoder, (tgt.put_tape(tape), mem))
     }
 }
 
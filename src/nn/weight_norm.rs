@@ -0,0 +1,206 @@
+use num_traits::Float;
+use rand_distr::{uniform::SampleUniform, Uniform};
+
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{linear::Bias1D, modules::Linear, *};
+
+pub mod builder {
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct WeightNorm<const I: usize, const O: usize>;
+}
+
+impl<const I: usize, const O: usize, E: Dtype, D: Device<E>> BuildOnDevice<D, E>
+    for builder::WeightNorm<I, O>
+where
+    WeightNorm<I, O, E, D>: BuildModule<D, E>,
+{
+    type Built = WeightNorm<I, O, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// A [Linear] whose weight is reparameterized as `g * v / ||v||`, as described in
+/// [Weight Normalization](https://arxiv.org/abs/1602.07868). Each row of the weight has its
+/// magnitude `g` and direction `v` learned separately.
+///
+/// The effective weight is computed on every forward, and gradients flow into both `g` and `v`.
+/// [Self::v] is initialized like [Linear::weight] and [Self::g] to ones, so the rows of the
+/// initial weight have unit norm. Use [WeightNorm::from_linear] to start from an existing
+/// weight instead.
+///
+/// Generics:
+/// - `I` The "input" size of vectors & matrices.
+/// - `O` The "output" size of vectors & matrices.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<WeightNorm<5, 2>, f32>();
+/// let _: Tensor<Rank2<10, 2>, f32, _> = model.forward(dev.zeros::<Rank2<10, 5>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeightNorm<const I: usize, const O: usize, E: Dtype, D: Storage<E>> {
+    /// Magnitude of each row of the weight, shape (O, )
+    pub g: Tensor<Rank1<O>, E, D>,
+
+    /// Direction of each row of the weight, shape (O, I)
+    pub v: Tensor<Rank2<O, I>, E, D>,
+
+    /// Bias vector, shape (O, )
+    pub bias: Tensor<Rank1<O>, E, D>,
+}
+
+impl<const I: usize, const O: usize, E: Dtype, D: Storage<E>> NonMutableModule
+    for WeightNorm<I, O, E, D>
+{
+}
+
+impl<const I: usize, const O: usize, E, D: Device<E>> TensorCollection<E, D>
+    for WeightNorm<I, O, E, D>
+where
+    E: Dtype + Float + SampleUniform,
+{
+    type To<E2: Dtype, D2: Device<E2>> = WeightNorm<I, O, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            (
+                Self::tensor("g", |s| &s.g, |s| &mut s.g, TensorOptions::reset_to_ones()),
+                Self::tensor(
+                    "v",
+                    |s| &s.v,
+                    |s| &mut s.v,
+                    TensorOptions::reset_with(|t| {
+                        let b: E = E::ONE / E::from_usize(I).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+                Self::tensor(
+                    "bias",
+                    |s| &s.bias,
+                    |s| &mut s.bias,
+                    TensorOptions::reset_with(|t| {
+                        let b: E = E::ONE / E::from_usize(I).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+            ),
+            |(g, v, bias)| WeightNorm { g, v, bias },
+        )
+    }
+}
+
+impl<const I: usize, const O: usize, E: Dtype + Float, D: Device<E>> WeightNorm<I, O, E, D> {
+    /// Reparameterizes `linear`, so that the effective weight equals `linear.weight`.
+    pub fn from_linear(linear: Linear<I, O, E, D>) -> Self {
+        Self::try_from_linear(linear).unwrap()
+    }
+
+    /// Fallible version of [WeightNorm::from_linear].
+    pub fn try_from_linear(linear: Linear<I, O, E, D>) -> Result<Self, D::Err> {
+        let g = linear
+            .weight
+            .clone()
+            .try_square()?
+            .try_sum::<_, Axis<1>>()?
+            .try_sqrt()?;
+        Ok(Self {
+            g,
+            v: linear.weight,
+            bias: linear.bias,
+        })
+    }
+
+    /// The effective weight `g * v / ||v||`.
+    pub fn weight(&self) -> Tensor<Rank2<O, I>, E, D> {
+        self.try_weight::<NoneTape>().unwrap()
+    }
+
+    fn try_weight<T: Tape<E, D>>(&self) -> Result<Tensor<Rank2<O, I>, E, D, T>, D::Err> {
+        let norm = self
+            .v
+            .retaped::<T>()
+            .try_square()?
+            .try_sum::<_, Axis<1>>()?
+            .try_sqrt()?;
+        let scale = self.g.retaped::<T>().try_div(norm)?;
+        self.v.retaped::<T>().try_mul(scale.try_broadcast()?)
+    }
+}
+
+impl<const I: usize, const O: usize, E, D, T> Module<T> for WeightNorm<I, O, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    T: SplitTape + TryMatMul<Tensor<Rank2<I, O>, E, D, T::Tape>> + HasErr<Err = D::Err>,
+    T::Tape: Tape<E, D>,
+    for<'a> Bias1D<'a, O, E, D>: Module<T::Output, Output = T::Output, Error = D::Err>,
+{
+    type Output = T::Output;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: T) -> Result<Self::Output, D::Err> {
+        let w = self.try_weight::<T::Tape>()?;
+        let o = x.try_matmul(w.try_permute()?)?;
+        Bias1D { beta: &self.bias }.try_forward(o)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_weight_norm_matches_linear() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<builder::WeightNorm<3, 2>, TestDtype>();
+        model.g = dev.tensor([2.0, 0.5]).to_dtype::<TestDtype>();
+        model.v = dev
+            .tensor([[3.0, 0.0, 4.0], [1.0, -1.0, 1.0]])
+            .to_dtype::<TestDtype>();
+
+        let s = 0.5 / 3.0f64.sqrt();
+        assert_close_to_literal!(model.weight(), [[1.2, 0.0, 1.6], [s, -s, s]]);
+
+        let linear = Linear {
+            weight: model.weight(),
+            bias: model.bias.clone(),
+        };
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        assert_close_to_tensor!(model.forward(x.clone()), linear.forward(x.clone()));
+
+        let g = model
+            .forward(x.trace(model.alloc_grads()))
+            .square()
+            .mean()
+            .backward();
+        assert_ne!(g.get(&model.g).array(), [TestDtype::default(); 2]);
+        assert_ne!(g.get(&model.v).array(), [[TestDtype::default(); 3]; 2]);
+        assert_ne!(g.get(&model.bias).array(), [TestDtype::default(); 2]);
+    }
+
+    #[test]
+    fn test_weight_norm_gradients() {
+        let dev: TestDevice = Default::default();
+        let model = WeightNorm::from_linear(Linear {
+            weight: dev.tensor([[3.0, 4.0]]).to_dtype::<TestDtype>(),
+            bias: dev.zeros(),
+        });
+        assert_close_to_literal!(model.g, [5.0]);
+        assert_close_to_literal!(model.weight(), [[3.0, 4.0]]);
+
+        // y = g * (v . x) / ||v||, with x = [1, 0]
+        // dy/dg = v_0 / ||v|| = 0.6
+        // dy/dv = g * (x / ||v|| - (v . x) v / ||v||^3) = [1 - 0.36, -0.48]
+        let x = dev.tensor([1.0, 0.0]).to_dtype::<TestDtype>();
+        let g = model.forward(x.leaky_trace()).sum().backward();
+        assert_close_to_literal!(g.get(&model.g), [0.6]);
+        assert_close_to_literal!(g.get(&model.v), [[0.64, -0.48]]);
+    }
+}
@@ -14,6 +14,19 @@ use crate::{
 
 use super::{Optimizer, OptimizerUpdateError, UnusedTensors, WeightDecay};
 
+#[cfg(feature = "numpy")]
+use crate::{
+    shapes::Rank0,
+    tensor::{numpy::NpzError, AsArray, Cpu, NumpyDtype, TensorFrom, ZerosTensor},
+};
+#[cfg(feature = "numpy")]
+use std::{
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+#[cfg(feature = "numpy")]
+use zip::{result::ZipResult, ZipArchive, ZipWriter};
+
 /// Configuration of hyperparameters for [Adam].
 ///
 /// Changing all default parameters:
@@ -52,6 +65,28 @@ impl Default for AdamConfig {
     }
 }
 
+impl AdamConfig {
+    /// The AdamW configuration from
+    /// [Decoupled Weight Decay Regularization](https://arxiv.org/abs/1711.05101), which
+    /// is the default config with [WeightDecay::Decoupled]. The decay is subtracted from
+    /// the parameters directly, instead of being added to the gradient and scaled by the
+    /// moment estimates like [WeightDecay::L2].
+    ///
+    /// ```rust
+    /// # use dfdx::{prelude::*, optim::*};
+    /// let cfg = AdamConfig {
+    ///     lr: 1e-4,
+    ///     ..AdamConfig::adamw(1e-2)
+    /// };
+    /// ```
+    pub fn adamw(weight_decay: f64) -> Self {
+        Self {
+            weight_decay: Some(WeightDecay::Decoupled(weight_decay)),
+            ..Default::default()
+        }
+    }
+}
+
 /// An implementation of the Adam optimizer from
 /// [Adam: A Method for Stochastic Optimization](https://arxiv.org/abs/1412.6980)
 ///
@@ -93,6 +128,57 @@ impl<M, E: Dtype, D: Storage<E>> Adam<M, E, D> {
             marker: PhantomData,
         }
     }
+
+    /// The number of updates so far, which is used for bias correction. This is only
+    /// incremented by [Optimizer::update()], so gradients accumulated over several
+    /// microbatches count as a single step.
+    pub fn t(&self) -> i32 {
+        self.t
+    }
+}
+
+#[cfg(feature = "numpy")]
+impl<M: TensorCollection<E, D>, E: Dtype + NumpyDtype, D: Device<E>> Adam<M, E, D> {
+    /// Saves the step count and moment buffers to a `.npz` file at `path`, so training
+    /// can be resumed with [Adam::load_optimizer()].
+    ///
+    /// The buffers are named after the parameters of `model`, in the same way
+    /// as [crate::nn::SaveToNpz], e.g. `0.weight.moment1.npy` and `0.weight.moment2.npy`.
+    /// The step count is saved as `t.npy`.
+    ///
+    /// Example:
+    /// ```ignore
+    /// # use dfdx::{prelude::*, optim::*};
+    /// model.save("model.npz")?;
+    /// opt.save_optimizer(&model, "adam.npz")?;
+    /// ```
+    pub fn save_optimizer<P: AsRef<Path>>(&self, model: &M, path: P) -> ZipResult<()> {
+        let f = std::fs::File::create(path)?;
+        let mut zip = ZipWriter::new(BufWriter::new(f));
+        Cpu::default()
+            .tensor(self.t)
+            .write_to_npz(&mut zip, "t".into())?;
+        let buffers = [("moment1", &self.moment1), ("moment2", &self.moment2)];
+        super::npz::write_buffers(model, &buffers, &mut zip)?;
+        zip.finish()?;
+        Ok(())
+    }
+
+    /// Loads the state saved by [Adam::save_optimizer()], associating the buffers with
+    /// the parameters of `model` by name. `model` doesn't need to be the same instance
+    /// that the state was saved with, e.g. it can be freshly built and loaded.
+    pub fn load_optimizer<P: AsRef<Path>>(&mut self, model: &M, path: P) -> Result<(), NpzError> {
+        let f = std::fs::File::open(path)?;
+        let mut zip = ZipArchive::new(BufReader::new(f))?;
+        let mut t: Tensor<Rank0, i32, Cpu> = Cpu::default().zeros();
+        t.read_from_npz(&mut zip, "t".into())?;
+        self.t = t.array();
+        let mut buffers = [
+            ("moment1", &mut self.moment1),
+            ("moment2", &mut self.moment2),
+        ];
+        super::npz::read_buffers(model, &mut buffers, &mut zip)
+    }
 }
 
 pub trait AdamKernel<E: Dtype>: Storage<E> {
@@ -166,7 +252,7 @@ impl<M: TensorCollection<E, D>, D: Device<E>, E: Dtype> Optimizer<M, D, E> for A
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+    use crate::{nn::ZeroGrads, shapes::*, tensor::*, tensor_ops::*, tests::*};
 
     #[test]
     fn test_default_adam_params() {
@@ -302,6 +388,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_adamw_zero_decay_matches_adam() {
+        let dev: TestDevice = Default::default();
+        let mut a: Tensor<Rank1<5>, TestDtype, _> = dev.sample_normal();
+        let mut b = a.clone();
+        let mut adam = Adam::new(&a, Default::default());
+        let mut adamw = Adam::new(&b, AdamConfig::adamw(0.0));
+        for _ in 0..5 {
+            let g = a.leaky_trace().exp().square().mean().backward();
+            adam.update(&mut a, &g).expect("");
+            let g = b.leaky_trace().exp().square().mean().backward();
+            adamw.update(&mut b, &g).expect("");
+        }
+        assert_eq!(a.array(), b.array());
+    }
+
+    #[test]
+    fn test_adamw_linear_loss_decreases() {
+        use crate::nn::{builders::Linear, DeviceBuildExt, Module};
+
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<Linear<2, 2>, TestDtype>();
+        let mut opt = Adam::new(
+            &model,
+            AdamConfig {
+                lr: 1e-2,
+                ..AdamConfig::adamw(1e-2)
+            },
+        );
+        let x = dev
+            .tensor([[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]])
+            .to_dtype::<TestDtype>();
+        let y = dev
+            .tensor([[0.5, -0.5], [-0.5, 0.5], [0.0, 0.0]])
+            .to_dtype::<TestDtype>();
+
+        let mut last = TestDtype::infinity();
+        for _ in 0..10 {
+            let loss = (model.forward(x.leaky_trace()) - y.clone()).square().mean();
+            let loss_value = loss.array();
+            assert!(loss_value < last, "{loss_value:?} >= {last:?}");
+            last = loss_value;
+            let g = loss.backward();
+            opt.update(&mut model, &g).expect("");
+        }
+    }
+
     #[test]
     fn test_unused_tensors() {
         let dev: TestDevice = Default::default();
@@ -309,4 +442,71 @@ mod tests {
         let mut opt = Adam::new(&t, Default::default());
         opt.update(&mut t, &Gradients::leaky()).expect_err("");
     }
+
+    #[test]
+    fn test_adam_step_count_with_grad_accumulation() {
+        let dev: TestDevice = Default::default();
+        let mut t: Tensor<Rank1<5>, TestDtype, _> = dev.ones();
+        let mut opt = Adam::new(&t, Default::default());
+
+        // two microbatches accumulated into the same gradients
+        let mut grads = t.alloc_grads();
+        for x in [[1.0, 2.0, 3.0, 4.0, 5.0], [-1.0, 0.5, 2.0, 1.0, 3.0]] {
+            let x = dev.tensor(x).to_dtype::<TestDtype>();
+            grads = (t.trace(grads) * x).square().mean().backward();
+        }
+        assert_eq!(opt.t(), 0);
+        opt.update(&mut t, &grads).expect("");
+        assert_eq!(opt.t(), 1);
+
+        // with bias correction for step 1, the first update is `lr * sign(g)`
+        assert_close_to_literal!(t, [0.999; 5]);
+    }
+
+    #[cfg(feature = "numpy")]
+    #[test]
+    fn test_adam_save_load_resumes_training() {
+        use crate::nn::{builders::*, DeviceBuildExt, LoadFromNpz, SaveToNpz};
+
+        let dev: TestDevice = Default::default();
+        type Model = (Linear<3, 4>, ReLU, Linear<4, 2>);
+        let mut model = dev.build_module::<Model, TestDtype>();
+        let mut opt = Adam::new(&model, Default::default());
+        let x: Tensor<Rank2<5, 3>, TestDtype, _> = dev.sample_normal();
+        for _ in 0..3 {
+            let g = model.forward(x.leaky_trace()).square().mean().backward();
+            opt.update(&mut model, &g).expect("");
+        }
+
+        let model_file = tempfile::NamedTempFile::new().expect("failed to create tempfile");
+        let opt_file = tempfile::NamedTempFile::new().expect("failed to create tempfile");
+        model.save(model_file.path()).expect("");
+        opt.save_optimizer(&model, opt_file.path()).expect("");
+
+        let names: std::vec::Vec<_> = {
+            let f = std::fs::File::open(opt_file.path()).expect("");
+            let zip = zip::ZipArchive::new(f).expect("");
+            zip.file_names().map(std::string::String::from).collect()
+        };
+        assert!(names.contains(&"t.npy".into()));
+        assert!(names.contains(&"0.weight.moment1.npy".into()));
+        assert!(names.contains(&"2.bias.moment2.npy".into()));
+
+        // a freshly built model has different tensor ids, so the buffers are
+        // matched up by name
+        let mut resumed = dev.build_module::<Model, TestDtype>();
+        resumed.load(model_file.path()).expect("");
+        let mut resumed_opt = Adam::new(&resumed, Default::default());
+        resumed_opt
+            .load_optimizer(&resumed, opt_file.path())
+            .expect("");
+        assert_eq!(resumed_opt.t(), 3);
+
+        let g = model.forward(x.leaky_trace()).square().mean().backward();
+        opt.update(&mut model, &g).expect("");
+        let g = resumed.forward(x.leaky_trace()).square().mean().backward();
+        resumed_opt.update(&mut resumed, &g).expect("");
+        assert_eq!(model.0.weight.array(), resumed.0.weight.array());
+        assert_eq!(model.2.bias.array(), resumed.2.bias.array());
+    }
 }
@@ -8,6 +8,9 @@
 //! - [Adam::new()] with [AdamConfig]
 //! - [RMSprop::new()] with [RMSpropConfig]
 //!
+//! [RecordGradients] implements [Optimizer] without changing any parameters, and instead
+//! records the gradients so they can be inspected.
+//!
 //! # Updating network parameters
 //!
 //! This is done via [Optimizer::update()], where you pass in a mutable [crate::nn::Module], and
@@ -29,18 +32,49 @@
 //! opt.update(&mut model, &grads);
 //! model.zero_grads(&mut grads);
 //! ```
+//!
+//! # Gradient accumulation
+//!
+//! Tracing with the gradients returned from the previous [crate::tensor::Gradients]
+//! accumulates into them, so several microbatches can be combined into one update.
+//! Optimizer state that depends on the number of steps, like the bias correction of
+//! [Adam], is only advanced by [Optimizer::update()], so this counts as a single step:
+//!
+//! ```rust
+//! # use dfdx::{prelude::*, optim::*, losses};
+//! # type MyModel = Linear<5, 2>;
+//! # let dev: Cpu = Default::default();
+//! let mut model = MyModel::build_on_device(&dev);
+//! let mut grads = model.alloc_grads();
+//! let mut opt = Adam::new(&model, Default::default());
+//! for _ in 0..4 {
+//!     # let x: Tensor<Rank1<5>, f32, _> = dev.zeros();
+//!     let y = model.forward(x.traced(grads));
+//!     # let loss = losses::mse_loss(y, dev.zeros());
+//!     grads = loss.backward();
+//! }
+//! opt.update(&mut model, &grads);
+//! model.zero_grads(&mut grads);
+//! assert_eq!(opt.t(), 1);
+//! ```
 
 mod adam;
+#[cfg(feature = "numpy")]
+mod npz;
 mod optimizer;
+mod record_gradients;
 mod rmsprop;
 mod sgd;
 
 pub use adam::{Adam, AdamConfig, AdamKernel};
 pub use optimizer::{Momentum, WeightDecay};
 pub use optimizer::{Optimizer, OptimizerUpdateError, UnusedTensors};
+pub use record_gradients::RecordGradients;
 pub use rmsprop::{RMSprop, RMSpropConfig, RMSpropKernel};
 pub use sgd::{Sgd, SgdConfig, SgdKernel};
 
 pub mod prelude {
+    pub use super::{Adam, AdamConfig, RMSprop, RMSpropConfig, Sgd, SgdConfig};
+    pub use super::{Momentum, RecordGradients, WeightDecay};
     pub use super::{Optimizer, OptimizerUpdateError, UnusedTensors};
 }
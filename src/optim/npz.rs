@@ -0,0 +1,134 @@
+//! Saving & loading the per parameter buffers of optimizers to/from `.npz` files.
+
+use crate::{
+    nn::tensor_collection::*,
+    shapes::{Dtype, Shape},
+    tensor::{
+        numpy::{NpzError, NumpyDtype},
+        Gradients, Storage, Tensor,
+    },
+    tensor_ops::Device,
+};
+
+use std::{
+    format,
+    io::{Read, Seek, Write},
+    string::String,
+};
+use zip::{
+    result::{ZipError, ZipResult},
+    ZipArchive, ZipWriter,
+};
+
+/// The file name of `buffer` for the parameter named `param`, e.g. `0.weight.moment1`.
+fn buffer_name(param: &str, buffer: &str) -> String {
+    if param.is_empty() {
+        buffer.into()
+    } else {
+        format!("{param}.{buffer}")
+    }
+}
+
+/// Writes every buffer that is present for a trainable tensor of `model`.
+pub(super) fn write_buffers<M, E, D, W>(
+    model: &M,
+    buffers: &[(&'static str, &Gradients<E, D>)],
+    zip: &mut ZipWriter<W>,
+) -> ZipResult<()>
+where
+    M: TensorCollection<E, D>,
+    E: Dtype + NumpyDtype,
+    D: Device<E>,
+    W: Write + Seek,
+{
+    M::iter_tensors(&mut RecursiveWalker {
+        m: (model, String::new()),
+        f: &mut WriteBuffers { buffers, zip },
+    })?;
+    Ok(())
+}
+
+/// Reads the buffers of every trainable tensor of `model`. Buffers missing from the
+/// archive are skipped, since optimizers only allocate them on the first update.
+pub(super) fn read_buffers<M, E, D, R>(
+    model: &M,
+    buffers: &mut [(&'static str, &mut Gradients<E, D>)],
+    zip: &mut ZipArchive<R>,
+) -> Result<(), NpzError>
+where
+    M: TensorCollection<E, D>,
+    E: Dtype + NumpyDtype,
+    D: Device<E>,
+    R: Read + Seek,
+{
+    M::iter_tensors(&mut RecursiveWalker {
+        m: (model, String::new()),
+        f: &mut ReadBuffers { buffers, zip },
+    })?;
+    Ok(())
+}
+
+struct WriteBuffers<'a, 'b, E, D: Storage<E>, W: Write + Seek> {
+    buffers: &'a [(&'static str, &'b Gradients<E, D>)],
+    zip: &'a mut ZipWriter<W>,
+}
+
+impl<'a, 'b, E: Dtype + NumpyDtype, D: Device<E>, W: Write + Seek> TensorVisitor<E, D>
+    for WriteBuffers<'a, 'b, E, D, W>
+{
+    type Viewer = (ViewTensorRef, ViewTensorName);
+    type Err = ZipError;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        (p, name): (&Tensor<S, E, D>, String),
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if !opts.do_gradient_update {
+            return Ok(None);
+        }
+        for (buffer, grads) in self.buffers.iter() {
+            if grads.get_ref_checked(p).is_some() {
+                grads
+                    .get(p)
+                    .write_to_npz(self.zip, buffer_name(&name, buffer))?;
+            }
+        }
+        Ok(None)
+    }
+}
+
+struct ReadBuffers<'a, 'b, E, D: Storage<E>, R: Read + Seek> {
+    buffers: &'a mut [(&'static str, &'b mut Gradients<E, D>)],
+    zip: &'a mut ZipArchive<R>,
+}
+
+impl<'a, 'b, E: Dtype + NumpyDtype, D: Device<E>, R: Read + Seek> TensorVisitor<E, D>
+    for ReadBuffers<'a, 'b, E, D, R>
+{
+    type Viewer = (ViewTensorRef, ViewTensorName);
+    type Err = NpzError;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        (p, name): (&Tensor<S, E, D>, String),
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if !opts.do_gradient_update {
+            return Ok(None);
+        }
+        for (buffer, grads) in self.buffers.iter_mut() {
+            let mut t = p.clone();
+            match t.read_from_npz(self.zip, buffer_name(&name, buffer)) {
+                Err(NpzError::MissingArray(_)) => continue,
+                r => r?,
+            }
+            grads.insert(p, t.data.as_ref().clone());
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,147 @@
+use std::{collections::BTreeMap, marker::PhantomData, vec::Vec};
+
+use crate::{
+    nn::tensor_collection::*,
+    shapes::{Dtype, Shape},
+    tensor::{Gradients, Storage, Tensor, UniqueId},
+    tensor_ops::Device,
+};
+
+use super::optimizer::*;
+
+/// An [Optimizer] that doesn't change any parameters, and instead copies each
+/// parameter's gradient to the host so it can be inspected.
+///
+/// Every call to [Optimizer::update()] clears the previously recorded gradients.
+/// Like the other optimizers, parameters without a gradient are reported as
+/// [OptimizerUpdateError::UnusedParams], and all other gradients are still recorded.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::*};
+/// # let dev: Cpu = Default::default();
+/// let mut model = dev.build_module::<Linear<2, 1>, f32>();
+/// let mut recorder = RecordGradients::new(&model);
+/// let grads = model.forward(dev.tensor([1.0, 2.0]).trace(model.alloc_grads())).sum().backward();
+/// recorder.update(&mut model, &grads).unwrap();
+/// assert_eq!(recorder.get(&model.weight), Some([1.0, 2.0].as_slice()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecordGradients<M, E, D> {
+    /// The recorded gradient of each parameter, in the same order as the
+    /// parameter's physical data.
+    pub grads: BTreeMap<UniqueId, Vec<E>>,
+    marker: PhantomData<*const (M, D)>,
+}
+
+impl<M, E, D> RecordGradients<M, E, D> {
+    pub fn new(_model: &M) -> Self {
+        Self {
+            grads: Default::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// The recorded gradient of `t`, if it had one during the last update.
+    pub fn get<S: Shape, T>(&self, t: &Tensor<S, E, D, T>) -> Option<&[E]>
+    where
+        D: Storage<E>,
+    {
+        self.grads.get(&t.id).map(Vec::as_slice)
+    }
+}
+
+impl<E: Dtype, D: Device<E>, M> TensorVisitor<E, D>
+    for (
+        &mut RecordGradients<M, E, D>,
+        &Gradients<E, D>,
+        UnusedTensors,
+    )
+{
+    type Viewer = ViewTensorRef;
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        p: &Tensor<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if !opts.do_gradient_update {
+            return Ok(None);
+        }
+        match self.1.get_ref_checked(p) {
+            None => self.2.add(p),
+            Some(_) => {
+                self.0.grads.insert(p.id, self.1.get(p).as_vec());
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<M: TensorCollection<E, D>, D: Device<E>, E: Dtype> Optimizer<M, D, E>
+    for RecordGradients<M, E, D>
+{
+    fn update(
+        &mut self,
+        module: &mut M,
+        gradients: &Gradients<E, D>,
+    ) -> Result<(), OptimizerUpdateError<D::Err>> {
+        self.grads.clear();
+        let mut op = (self, gradients, Default::default());
+        let result = M::iter_tensors(&mut RecursiveWalker {
+            m: &*module,
+            f: &mut op,
+        });
+        match result {
+            Ok(_) => op.2.into(),
+            Err(e) => Err(OptimizerUpdateError::DeviceError(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_record_linear_gradients() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<Linear<2, 2>, TestDtype>();
+        let weight = model.weight.array();
+        let bias = model.bias.array();
+        let mut recorder = RecordGradients::new(&model);
+
+        // loss = sum(W x + b), so dL/dW[i] = x and dL/db = 1
+        let x = dev.tensor([1.0, -2.0]).to_dtype::<TestDtype>();
+        let grads = model.forward(x.trace(model.alloc_grads())).sum().backward();
+        recorder.update(&mut model, &grads).unwrap();
+
+        assert_eq!(recorder.grads.len(), 2);
+        let w: [TestDtype; 4] = recorder.get(&model.weight).unwrap().try_into().unwrap();
+        let b: [TestDtype; 2] = recorder.get(&model.bias).unwrap().try_into().unwrap();
+        assert_close!(w, [1.0, -2.0, 1.0, -2.0]);
+        assert_close!(b, [1.0, 1.0]);
+        assert_eq!(model.weight.array(), weight);
+        assert_eq!(model.bias.array(), bias);
+    }
+
+    #[test]
+    fn test_record_unused_gradients() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<(Linear<2, 2>, Linear<2, 2>), TestDtype>();
+        let mut recorder = RecordGradients::new(&model);
+
+        let x: Tensor<Rank1<2>, TestDtype, _> = dev.sample_normal();
+        let grads = model.0.forward(x.leaky_trace()).sum().backward();
+        let err = recorder.update(&mut model, &grads).unwrap_err();
+        match err {
+            OptimizerUpdateError::UnusedParams(unused) => assert_eq!(unused.ids.len(), 2),
+            _ => panic!("expected unused params"),
+        }
+        assert!(recorder.get(&model.0.weight).is_some());
+        assert!(recorder.get(&model.1.weight).is_none());
+    }
+}
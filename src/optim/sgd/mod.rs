@@ -14,6 +14,16 @@ use crate::{
 
 use super::optimizer::*;
 
+#[cfg(feature = "numpy")]
+use crate::tensor::{numpy::NpzError, NumpyDtype};
+#[cfg(feature = "numpy")]
+use std::{
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+#[cfg(feature = "numpy")]
+use zip::{result::ZipResult, ZipArchive, ZipWriter};
+
 /// Configuration of hyperparameters for [Sgd].
 ///
 /// Using different learning rate:
@@ -132,6 +142,29 @@ impl<M, E: Dtype, D: Storage<E>> Sgd<M, E, D> {
     }
 }
 
+#[cfg(feature = "numpy")]
+impl<M: TensorCollection<E, D>, E: Dtype + NumpyDtype, D: Device<E>> Sgd<M, E, D> {
+    /// Saves the momentum buffers to a `.npz` file at `path`, so training can be
+    /// resumed with [Sgd::load_optimizer()]. See [crate::optim::Adam::save_optimizer()].
+    ///
+    /// The buffers are named after the parameters of `model`, e.g. `0.weight.velocity.npy`.
+    pub fn save_optimizer<P: AsRef<Path>>(&self, model: &M, path: P) -> ZipResult<()> {
+        let f = std::fs::File::create(path)?;
+        let mut zip = ZipWriter::new(BufWriter::new(f));
+        super::npz::write_buffers(model, &[("velocity", &self.velocity)], &mut zip)?;
+        zip.finish()?;
+        Ok(())
+    }
+
+    /// Loads the state saved by [Sgd::save_optimizer()], associating the buffers with
+    /// the parameters of `model` by name.
+    pub fn load_optimizer<P: AsRef<Path>>(&mut self, model: &M, path: P) -> Result<(), NpzError> {
+        let f = std::fs::File::open(path)?;
+        let mut zip = ZipArchive::new(BufReader::new(f))?;
+        super::npz::read_buffers(model, &mut [("velocity", &mut self.velocity)], &mut zip)
+    }
+}
+
 pub trait SgdKernel<E: Dtype>: Storage<E> {
     fn update(
         &self,
@@ -448,4 +481,36 @@ mod tests {
         let mut opt = Sgd::new(&t, Default::default());
         opt.update(&mut t, &Gradients::leaky()).expect_err("");
     }
+
+    #[cfg(feature = "numpy")]
+    #[test]
+    fn test_sgd_save_load_momentum() {
+        use crate::nn::{builders::*, DeviceBuildExt};
+
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<Linear<3, 2>, TestDtype>();
+        let cfg = SgdConfig {
+            momentum: Some(Momentum::Classic(0.5)),
+            ..Default::default()
+        };
+        let mut opt = Sgd::new(&model, cfg);
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        for _ in 0..2 {
+            let g = model.forward(x.leaky_trace()).square().mean().backward();
+            opt.update(&mut model, &g).expect("");
+        }
+
+        let file = tempfile::NamedTempFile::new().expect("failed to create tempfile");
+        opt.save_optimizer(&model, file.path()).expect("");
+        let mut resumed = model.clone();
+        let mut opt2 = Sgd::new(&resumed, cfg);
+        opt2.load_optimizer(&resumed, file.path()).expect("");
+
+        let g = model.forward(x.leaky_trace()).square().mean().backward();
+        opt.update(&mut model, &g).expect("");
+        let g = resumed.forward(x.leaky_trace()).square().mean().backward();
+        opt2.update(&mut resumed, &g).expect("");
+        assert_eq!(model.weight.array(), resumed.weight.array());
+        assert_eq!(model.bias.array(), resumed.bias.array());
+    }
 }
@@ -0,0 +1,138 @@
+use super::shape::{Dtype, SafeZeros, Unit};
+
+/// A complex number with `f32` real and imaginary parts.
+///
+/// This can be used as the element type of tensors on the [crate::tensor::Cpu], which
+/// supports elementwise `+`, `-`, `*`, [crate::tensor::Tensor::conj()], and
+/// [crate::tensor::Tensor::fft()]/[crate::tensor::Tensor::ifft()] on it.
+///
+/// The ordering from [PartialOrd] is lexicographic (real part first), and only exists
+/// so that complex numbers can be stored in tensors.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// let a = Complex32::new(1.0, 2.0);
+/// let b = Complex32::new(3.0, -1.0);
+/// assert_eq!(a * b, Complex32::new(5.0, 5.0));
+/// assert_eq!(a.conj(), Complex32::new(1.0, -2.0));
+/// ```
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Complex32 {
+    pub re: f32,
+    pub im: f32,
+}
+
+impl Complex32 {
+    pub const fn new(re: f32, im: f32) -> Self {
+        Self { re, im }
+    }
+
+    /// The complex conjugate `re - im * i`.
+    pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// The squared magnitude `re^2 + im^2`.
+    pub fn norm_sqr(self) -> f32 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// The magnitude `sqrt(re^2 + im^2)`.
+    pub fn norm(self) -> f32 {
+        num_traits::Float::hypot(self.re, self.im)
+    }
+}
+
+impl From<f32> for Complex32 {
+    fn from(re: f32) -> Self {
+        Self::new(re, 0.0)
+    }
+}
+
+impl std::ops::Add for Complex32 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl std::ops::Sub for Complex32 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl std::ops::Mul for Complex32 {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl std::ops::Div for Complex32 {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let d = rhs.norm_sqr();
+        let n = self * rhs.conj();
+        Self::new(n.re / d, n.im / d)
+    }
+}
+
+impl std::ops::Neg for Complex32 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl std::ops::AddAssign for Complex32 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl std::ops::SubAssign for Complex32 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl std::ops::MulAssign for Complex32 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl std::ops::DivAssign for Complex32 {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl num_traits::FromPrimitive for Complex32 {
+    fn from_i64(n: i64) -> Option<Self> {
+        Some((n as f32).into())
+    }
+    fn from_u64(n: u64) -> Option<Self> {
+        Some((n as f32).into())
+    }
+    fn from_f64(n: f64) -> Option<Self> {
+        Some((n as f32).into())
+    }
+}
+
+#[cfg(feature = "cuda")]
+unsafe impl cudarc::driver::ValidAsZeroBits for Complex32 {}
+#[cfg(feature = "cuda")]
+unsafe impl cudarc::driver::DeviceRepr for Complex32 {}
+
+impl SafeZeros for Complex32 {}
+impl Unit for Complex32 {
+    const ONE: Self = Self::new(1.0, 0.0);
+}
+impl Dtype for Complex32 {}
@@ -11,6 +11,7 @@
 
 mod axes;
 mod broadcasts;
+mod complex;
 mod permutes;
 mod realize;
 mod replace_dim;
@@ -24,12 +25,13 @@ pub(crate) use broadcasts::{
 };
 pub(crate) use permutes::{PermuteShapeTo, PermuteStridesTo};
 pub(crate) use realize::RealizeShapeTo;
-pub(crate) use replace_dim::{RemoveDimTo, ReplaceDimTo};
+pub(crate) use replace_dim::{KeepDimShape, RemoveDimTo, ReplaceDimTo};
 
 pub(crate) use same_numel::AssertSameNumel;
 pub(crate) use slice::SliceShape;
 
 pub use axes::{Axes2, Axes3, Axes4, Axes5, Axes6, Axis, HasAxes};
+pub use complex::Complex32;
 pub use shape::{Array, Const, ConstDim, Dim};
 pub use shape::{ConstShape, HasShape, Shape};
 pub use shape::{Dtype, HasDtype, HasUnitType, Unit};
@@ -1,6 +1,7 @@
 use super::{
     axes::{Axes, Axis},
-    shape::{Dim, Shape},
+    broadcasts::ReduceShape,
+    shape::{Const, Dim, Shape},
 };
 
 /// Marker for shapes that can be indexed and have a dimension removed
@@ -89,6 +90,24 @@ pub trait ReplaceDimTo<Dst: Shape, Idx: Shape>: Shape {
     }
 }
 
+/// Marker for shapes that can have the dimension at `Ax` replaced with [Const<1>], which is
+/// the shape returned by the keepdim reductions like [crate::tensor::Tensor::sum_axis_keepdim].
+pub trait KeepDimShape<Ax: Axes<Array = [isize; 1]>>: Shape + ReduceShape<Ax> {
+    type KeepDim: Shape;
+
+    #[inline]
+    fn keep_dim(&self) -> Self::KeepDim {
+        let ax = Ax::as_array()[0] as usize;
+        let mut dims = self.concrete();
+        dims[ax] = 1;
+        let mut dst_dims: <Self::KeepDim as Shape>::Concrete = Default::default();
+        for i in 0..Self::NUM_DIMS {
+            dst_dims[i] = dims[i];
+        }
+        Self::KeepDim::from_concrete(&dst_dims).unwrap()
+    }
+}
+
 macro_rules! replace {
     (($($DimVars:tt),*), $Ax:ty, $Dst:ty, $Idx:ty) => {
 impl<$($DimVars: Dim, )* New: Dim> ReplaceDimTo<$Dst, $Idx> for ($($DimVars, )*) {
@@ -111,6 +130,9 @@ macro_rules! replace_and_remove_all {
     (@ [$($befores:ident)*] [$cur:ident $($afters:ident)*] [$idx:tt $($idxs:tt)*]) => {
         replace!(($($befores,)* $cur $(,$afters)*), Axis<$idx>, ($($befores,)* New, $($afters),*), ($($befores,)* New,));
         removed!(($($befores,)* $cur $(,$afters)*), Axis<$idx>, ($($befores,)* $($afters,)*), ($($befores,)*));
+        impl<$($befores: Dim, )* $cur: Dim, $($afters: Dim),*> KeepDimShape<Axis<$idx>> for ($($befores,)* $cur, $($afters,)*) {
+            type KeepDim = ($($befores,)* Const<1>, $($afters,)*);
+        }
 
         replace_and_remove_all!(@ [$($befores)* $cur] [$($afters)*] [$($idxs)*]);
     };
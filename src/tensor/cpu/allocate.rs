@@ -151,6 +151,13 @@ impl<E: Unit> OneFillStorage<E> for Cpu {
     }
 }
 
+impl<E: Unit> FillStorage<E> for Cpu {
+    fn try_fill_with(&self, storage: &mut Self::Vec, value: E) -> Result<(), Self::Err> {
+        storage.fill(value);
+        Ok(())
+    }
+}
+
 impl<E: Unit> SampleTensor<E> for Cpu {
     fn try_sample_like<S: HasShape, D: Distribution<E>>(
         &self,
@@ -194,6 +201,14 @@ impl<E: Unit> CopySlice<E> for Cpu {
     fn copy_into<S: Shape, T>(src: &Tensor<S, E, Self, T>, dst: &mut [E]) {
         dst.copy_from_slice(src.data.as_ref());
     }
+    fn clone_data_into<S: Shape, T, R>(
+        src: &Tensor<S, E, Self, T>,
+        dst: &mut Tensor<S, E, Self, R>,
+    ) {
+        std::sync::Arc::make_mut(&mut dst.data).copy_from_slice(src.data.as_ref());
+        dst.shape = src.shape;
+        dst.strides = src.strides;
+    }
 }
 
 impl<E: Unit> TensorFromVec<E> for Cpu {
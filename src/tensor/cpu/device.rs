@@ -151,6 +151,15 @@ impl RandomU64 for Cpu {
     }
 }
 
+impl WithRngSeed for Cpu {
+    fn with_rng_seed(&self, seed: u64) -> Self {
+        Self {
+            rng: Arc::new(Mutex::new(StdRng::seed_from_u64(seed))),
+            cache: self.cache.clone(),
+        }
+    }
+}
+
 impl<E: Unit> Storage<E> for Cpu {
     type Vec = CachableVec<E>;
 
@@ -110,6 +110,14 @@ impl<E: Unit> OneFillStorage<E> for Cuda {
     }
 }
 
+impl<E: Unit> FillStorage<E> for Cuda {
+    fn try_fill_with(&self, storage: &mut Self::Vec, value: E) -> Result<(), Self::Err> {
+        self.dev
+            .htod_copy_into(std::vec![value; storage.len()], &mut storage.data)?;
+        Ok(())
+    }
+}
+
 impl<E: Unit> SampleTensor<E> for Cuda
 where
     Cpu: SampleTensor<E>,
@@ -173,6 +181,23 @@ impl<E: Unit> CopySlice<E> for Cuda {
             .dtoh_sync_copy_into(&storage.data, dst)
             .unwrap();
     }
+    fn clone_data_into<S: Shape, T, R>(
+        src: &Tensor<S, E, Self, T>,
+        dst: &mut Tensor<S, E, Self, R>,
+    ) {
+        assert_eq!(
+            src.data.len(),
+            dst.data.len(),
+            "Tensors must have same number of elements in their *physical* Storage<E>."
+        );
+        let storage = Arc::make_mut(&mut dst.data);
+        dst.device
+            .dev
+            .dtod_copy(&src.data.data, &mut storage.data)
+            .unwrap();
+        dst.shape = src.shape;
+        dst.strides = src.strides;
+    }
 }
 
 impl<E: Unit> TensorFromVec<E> for Cuda {
@@ -2,6 +2,7 @@ use crate::shapes::{Shape, Unit};
 use crate::tensor::cpu::{Cpu, CpuError};
 use crate::tensor::{
     cache::TensorCache, Cache, HasErr, NoneTape, RandomU64, Storage, Synchronize, Tensor,
+    WithRngSeed,
 };
 
 use cudarc::driver::{DevicePtr, DevicePtrMut, DeviceRepr};
@@ -245,6 +246,15 @@ impl RandomU64 for Cuda {
     }
 }
 
+impl WithRngSeed for Cuda {
+    fn with_rng_seed(&self, seed: u64) -> Self {
+        Self {
+            cpu: self.cpu.with_rng_seed(seed),
+            ..self.clone()
+        }
+    }
+}
+
 impl Cache for Cuda {
     fn try_enable_cache(&self) -> Result<(), Self::Err> {
         self.cache.enable();
@@ -2,7 +2,7 @@
 #![allow(clippy::type_complexity)]
 
 use std::collections::{BTreeMap, BTreeSet};
-use std::{boxed::Box, vec::Vec};
+use std::{boxed::Box, format, string::String, vec::Vec};
 
 use super::tensorlike::Tensorlike;
 use super::{storage_traits::Storage, unique_id, Tensor, UniqueId};
@@ -64,6 +64,11 @@ impl<E, D: Storage<E>> Gradients<E, D> {
         Ok(())
     }
 
+    /// Inserts `data` as the gradient for `t`, replacing any existing gradient.
+    pub(crate) fn insert<S: Shape, T>(&mut self, t: &Tensor<S, E, D, T>, data: D::Vec) {
+        self.gradient_by_id.insert(t.id, data);
+    }
+
     /// Drops all gradients except for the ids specified in the parameter.
     pub fn retain_leafs(&mut self, ids: &[UniqueId]) {
         self.leaf_ids
@@ -80,6 +85,24 @@ impl<E, D: Storage<E>> Gradients<E, D> {
         }
     }
 
+    /// Adds each gradient in `other` to the gradient with the same id in `self` with `add`,
+    /// and moves over the gradients that `self` doesn't have yet.
+    pub(crate) fn try_accumulate<Err>(
+        &mut self,
+        other: Self,
+        mut add: impl FnMut(&mut D::Vec, &D::Vec) -> Result<(), Err>,
+    ) -> Result<(), Err> {
+        for (id, grad) in other.gradient_by_id {
+            match self.gradient_by_id.entry(id) {
+                std::collections::btree_map::Entry::Vacant(e) => {
+                    e.insert(grad);
+                }
+                std::collections::btree_map::Entry::Occupied(mut e) => add(e.get_mut(), &grad)?,
+            }
+        }
+        Ok(())
+    }
+
     /// Returns a reference to the underlying gradient if found.
     pub(crate) fn get_ref_checked<S: Shape, T>(&self, t: &Tensor<S, E, D, T>) -> Option<&D::Vec> {
         self.gradient_by_id.get(&t.id)
@@ -99,6 +122,20 @@ impl<E, D: Storage<E>> Gradients<E, D> {
         self.gradient_by_id.get(&t.id()).unwrap()
     }
 
+    /// Removes the gradient for `t` and transforms it into a tensor without copying it, so
+    /// it can be updated in place and put back with [Gradients::insert].
+    pub(crate) fn take<S: Shape, T>(&mut self, t: &Tensor<S, E, D, T>) -> Option<Tensor<S, E, D>> {
+        let buf = self.gradient_by_id.remove(&t.id)?;
+        Some(Tensor {
+            id: unique_id(),
+            data: std::sync::Arc::new(buf),
+            shape: t.shape,
+            strides: t.strides,
+            device: t.device.clone(),
+            tape: Default::default(),
+        })
+    }
+
     /// Clones the gradient and transforms it into a tensor.
     ///
     /// # Panics
@@ -177,11 +214,36 @@ impl<E, D: Storage<E>> Gradients<E, D> {
     }
 }
 
+impl<E: crate::shapes::Dtype + num_traits::Float, D: Storage<E>> Gradients<E, D> {
+    /// Panics if any gradient contains a NaN or infinite value. The panic message contains
+    /// the [UniqueId] of the tensor the gradient belongs to, the first non-finite value,
+    /// and its flat index into the gradient.
+    ///
+    /// `device` is used to copy each gradient to the host, so this should only be
+    /// used while debugging.
+    pub fn assert_all_finite(&self, device: &D) {
+        for (id, grad) in self.gradient_by_id.iter() {
+            let numel = device.len(grad);
+            let t: Tensor<(usize,), E, D> = Tensor {
+                id: *id,
+                data: std::sync::Arc::new(grad.clone()),
+                shape: (numel,),
+                strides: [1],
+                device: device.clone(),
+                tape: Default::default(),
+            };
+            if let Some((i, v)) = super::stats::first_non_finite(&t.as_vec()) {
+                panic!("Gradient of tensor {id:?} contains non-finite value {v:?} at index {i}");
+            }
+        }
+    }
+}
+
 /// Contains a [Gradients] and list of backward operations.
 pub struct OwnedTape<E, D: Storage<E>> {
     /// A list of (Time, BackwardOp) pairs. The Time is used to ensure operations
     /// from merged tapes are executed in the correct order.
-    pub(crate) operations: Vec<(UniqueId, BackwardOp<E, D, D::Err>)>,
+    pub(crate) operations: Vec<(UniqueId, &'static str, BackwardOp<E, D, D::Err>)>,
     pub(crate) gradients: Gradients<E, D>,
 }
 
@@ -211,14 +273,146 @@ impl<E, D: Storage<E>> OwnedTape<E, D> {
         // We must ensure that the operations are sorted in execution time order.
         // Otherwise an backward operation may not be executed in the right order
         // if multiple tapes were merged together.
-        self.operations.sort_by_key(|(k, _)| *k);
-        for (_, operation) in self.operations.drain(..).rev() {
+        self.operations.sort_by_key(|(k, _, _)| *k);
+        for (_, name, operation) in self.operations.drain(..).rev() {
+            crate::hooks::run_backward_hooks(name);
             (operation)(&mut self.gradients)?;
         }
         Ok(self.gradients)
     }
 }
 
+/// Counts of what an [OwnedTape] is currently holding on to. See [OwnedTape::stats()].
+///
+/// Gradients are mostly allocated lazily during the backward pass, so before calling
+/// backward `num_gradients` typically only includes gradients allocated up front
+/// (e.g. with [crate::nn::ZeroGrads::alloc_grads]). Tensors saved by backward
+/// operations for use in the backward pass are not included in `gradient_bytes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TapeStats {
+    /// Number of recorded backward operations.
+    pub num_operations: usize,
+    /// Number of allocated gradients.
+    pub num_gradients: usize,
+    /// Estimated number of bytes held by the allocated gradients.
+    pub gradient_bytes: usize,
+}
+
+impl<E, D: Storage<E>> OwnedTape<E, D> {
+    /// Returns [TapeStats] for this tape. `device` is used to query the size of gradients.
+    pub fn stats(&self, device: &D) -> TapeStats {
+        let gradient_bytes = self
+            .gradients
+            .gradient_by_id
+            .values()
+            .map(|g| device.len(g) * std::mem::size_of::<E>())
+            .sum();
+        TapeStats {
+            num_operations: self.operations.len(),
+            num_gradients: self.gradients.gradient_by_id.len(),
+            gradient_bytes,
+        }
+    }
+}
+
+impl<S: Shape, E, D: Storage<E>> Tensor<S, E, D, OwnedTape<E, D>> {
+    /// Returns [TapeStats] for the tape of this tensor. Useful for understanding how much
+    /// memory a graph holds on to.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let x: Tensor<Rank1<3>, f32, _> = dev.zeros();
+    /// let y = x.leaky_trace().exp().sum();
+    /// assert_eq!(y.tape_stats().num_operations, 2);
+    /// ```
+    pub fn tape_stats(&self) -> TapeStats {
+        self.tape.stats(&self.device)
+    }
+}
+
+impl<E, D: Storage<E>> OwnedTape<E, D> {
+    /// Renders the recorded backward operations as a [DOT](https://graphviz.org/doc/info/lang.html)
+    /// graph, with one node per operation in the order they were recorded. Useful for
+    /// checking whether an operation was actually recorded on the tape.
+    ///
+    /// Backward operations are stored as closures, so each node is labeled with the
+    /// (shortened) name of the function that recorded it, e.g. `try_binary_op<BinaryMulKernelOp, ...>`.
+    /// Operands are not recorded, so nodes are connected in recording order rather than
+    /// by data dependency.
+    pub fn to_dot(&self) -> String {
+        let mut ops: Vec<_> = self
+            .operations
+            .iter()
+            .map(|(id, name, _)| (*id, *name))
+            .collect();
+        ops.sort_by_key(|(id, _)| *id);
+        let mut dot = String::from("digraph tape {\n    node [shape=box];\n");
+        for (i, (id, name)) in ops.iter().enumerate() {
+            let label = short_op_name(name).replace('"', "\\\"");
+            dot.push_str(&format!("    op{i} [label=\"{label}\\nid={id:?}\"];\n"));
+            if i > 0 {
+                dot.push_str(&format!("    op{} -> op{i};\n", i - 1));
+            }
+        }
+        dot.push('}');
+        dot
+    }
+}
+
+impl<S: Shape, E, D: Storage<E>> Tensor<S, E, D, OwnedTape<E, D>> {
+    /// Renders the tape of this tensor as a DOT graph. See [OwnedTape::to_dot()].
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let x: Tensor<Rank1<3>, f32, _> = dev.zeros();
+    /// let y = x.leaky_trace().exp().sum();
+    /// println!("{}", y.tape_to_dot());
+    /// ```
+    pub fn tape_to_dot(&self) -> String {
+        self.tape.to_dot()
+    }
+}
+
+/// Shortens the type name of a backward op closure, e.g.
+/// `dfdx::tensor_ops::utilities::ops::try_unary_op<dfdx::tensor_ops::exp::ExpKernelOp, ...>::{{closure}}`
+/// becomes `try_unary_op<ExpKernelOp, ...>`.
+fn short_op_name(name: &str) -> String {
+    let name = name.trim_end_matches("::{{closure}}");
+
+    // the function that recorded the op is the last top level path segment
+    let mut depth = 0;
+    let mut start = 0;
+    let bytes = name.as_bytes();
+    for i in 0..bytes.len() {
+        match bytes[i] {
+            b'<' => depth += 1,
+            b'>' => depth -= 1,
+            b':' if depth == 0 && bytes.get(i + 1) == Some(&b':') => start = i + 2,
+            _ => {}
+        }
+    }
+    let name = &name[start..];
+
+    // strip module paths from all the types
+    let mut short = String::with_capacity(name.len());
+    let mut path_start = 0;
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            short.truncate(path_start);
+        } else {
+            short.push(c);
+            if !(c.is_alphanumeric() || c == '_') {
+                path_start = short.len();
+            }
+        }
+    }
+    short
+}
+
 type BackwardOp<E, D, Err> = Box<dyn FnOnce(&mut Gradients<E, D>) -> Result<(), Err>>;
 
 /// Contains nothing. When [Tape::add_backward_op] is called, this struct does nothing.
@@ -240,7 +434,8 @@ impl<E, D: Storage<E>> Tape<E, D> for OwnedTape<E, D> {
     where
         F: 'static + FnOnce(&mut Gradients<E, D>) -> Result<(), D::Err>,
     {
-        self.operations.push((unique_id(), Box::new(operation)));
+        self.operations
+            .push((unique_id(), std::any::type_name::<F>(), Box::new(operation)));
     }
 }
 
@@ -253,6 +448,23 @@ impl<E, D: Storage<E>> Tape<E, D> for NoneTape {
     }
 }
 
+/// A tape that is only tracking gradients if it is `Some`, which is decided at runtime.
+/// See [Tensor::requires_grad()].
+///
+/// Since whether it tracks isn't known at compile time, operations always keep around
+/// what they need for the backward pass, even when this is `None`.
+impl<E, D: Storage<E>> Tape<E, D> for Option<OwnedTape<E, D>> {
+    const OWNS_TAPE: bool = true;
+    fn add_backward_op<F>(&mut self, operation: F)
+    where
+        F: 'static + FnOnce(&mut Gradients<E, D>) -> Result<(), D::Err>,
+    {
+        if let Some(tape) = self {
+            tape.add_backward_op(operation);
+        }
+    }
+}
+
 /// Combine two things
 pub trait Merge<T: ?Sized> {
     /// Merges `T` into `self`
@@ -286,3 +498,85 @@ impl<E, D: Storage<E>> Merge<OwnedTape<E, D>> for OwnedTape<E, D> {
         self
     }
 }
+
+impl<E, D: Storage<E>> Merge<NoneTape> for Option<OwnedTape<E, D>> {
+    fn merge(self, _: NoneTape) -> Self {
+        self
+    }
+}
+
+impl<E, D: Storage<E>> Merge<OwnedTape<E, D>> for Option<OwnedTape<E, D>> {
+    fn merge(self, other: OwnedTape<E, D>) -> Self {
+        Some(match self {
+            Some(tape) => tape.merge(other),
+            None => other,
+        })
+    }
+}
+
+impl<E, D: Storage<E>> Merge<Option<OwnedTape<E, D>>> for Option<OwnedTape<E, D>> {
+    fn merge(self, other: Self) -> Self {
+        match other {
+            Some(other) => self.merge(other),
+            None => self,
+        }
+    }
+}
+
+impl<E, D: Storage<E>> Merge<Option<OwnedTape<E, D>>> for OwnedTape<E, D> {
+    fn merge(self, other: Option<OwnedTape<E, D>>) -> Self {
+        match other {
+            Some(other) => self.merge(other),
+            None => self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{nn::ZeroGrads, shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_tape_stats_matmuls() {
+        let dev: TestDevice = Default::default();
+        let w: Tensor<Rank2<4, 4>, TestDtype, _> = dev.sample_normal();
+        let mut x = dev.sample_normal::<Rank2<2, 4>>().leaky_trace();
+        for _ in 0..5 {
+            x = x.matmul(w.clone());
+        }
+        let stats = x.tape_stats();
+        assert_eq!(stats.num_operations, 5);
+        assert_eq!(stats.num_gradients, 0);
+        assert_eq!(stats.gradient_bytes, 0);
+    }
+
+    #[test]
+    fn test_tape_stats_with_allocated_grads() {
+        let dev: TestDevice = Default::default();
+        let w: Tensor<Rank2<4, 4>, TestDtype, _> = dev.sample_normal();
+        let grads = w.alloc_grads();
+        let y = w.traced(grads).matmul(dev.sample_normal::<Rank2<4, 3>>());
+        let stats = y.tape_stats();
+        assert_eq!(stats.num_operations, 1);
+        assert_eq!(stats.num_gradients, 1);
+        assert_eq!(stats.gradient_bytes, 16 * std::mem::size_of::<TestDtype>());
+    }
+
+    #[test]
+    fn test_tape_to_dot() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let c: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let y = a.leaky_trace() * b + c;
+        let dot = y.tape_to_dot();
+        // labels come from `std::any::type_name`, which isn't stable across compiler
+        // versions, so only the structure of the graph is checked
+        assert!(dot.starts_with("digraph tape {"));
+        assert!(dot.ends_with('}'));
+        assert_eq!(dot.matches(" [label=\"").count(), 2);
+        assert_eq!(dot.matches("\\nid=").count(), 2);
+        assert_eq!(dot.matches(" -> ").count(), 1);
+        assert!(dot.contains("op0 -> op1;"));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::shapes::{Axis, ReduceShape, ReduceStridesTo, Shape, Unit};
+
+use super::{Tensor, TensorFromVec};
+
+impl<S: Shape, E: Unit, D: TensorFromVec<E>, T> Tensor<S, E, D, T> {
+    /// Reduces axis `I` with a custom function, calling `f` on every lane along the axis,
+    /// e.g. on every row of a 2d tensor when `I` is 1.
+    ///
+    /// This copies the data to the host, and nothing is recorded on a tape, so it is
+    /// meant for preprocessing and metrics rather than training.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// let r: Tensor<Rank1<2>, f32, _> = t.map_axis::<1>(|row| row[0] * row[2]);
+    /// assert_eq!(r.array(), [3.0, 24.0]);
+    /// let r: Tensor<Rank1<3>, f32, _> = t.map_axis::<0>(|col| col[1] - col[0]);
+    /// assert_eq!(r.array(), [3.0, 3.0, 3.0]);
+    /// ```
+    pub fn map_axis<const I: isize>(
+        &self,
+        mut f: impl FnMut(&[E]) -> E,
+    ) -> Tensor<<S as ReduceShape<Axis<I>>>::Reduced, E, D>
+    where
+        S: ReduceShape<Axis<I>>,
+    {
+        let dims = self.shape.concrete();
+        let axis = I as usize;
+        let size = dims[axis];
+        let inner: usize = dims.into_iter().skip(axis + 1).product();
+        let outer: usize = dims.into_iter().take(axis).product();
+
+        let data = self.as_vec();
+        let mut lane = std::vec::Vec::with_capacity(size);
+        let mut out = std::vec::Vec::with_capacity(outer * inner);
+        for o in 0..outer {
+            for i in 0..inner {
+                lane.clear();
+                lane.extend((0..size).map(|k| data[(o * size + k) * inner + i]));
+                out.push(f(&lane));
+            }
+        }
+        self.device.tensor_from_vec(out, self.shape.reduced())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_map_axis_l2_norm() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<4, 5>, f64, _> = dev.sample_normal();
+        let norms = t.map_axis::<1>(|row| row.iter().map(|v| v * v).sum::<f64>().sqrt());
+        let expected = t.clone().square().sum::<Rank1<4>, _>().sqrt();
+        assert_close_to_tensor!(norms, expected, 1e-10);
+
+        // the middle axis of a 3d tensor
+        let t: Tensor<Rank3<2, 3, 4>, f64, _> = dev.sample_normal();
+        let maxes = t.map_axis::<1>(|lane| lane.iter().cloned().fold(f64::NEG_INFINITY, f64::max));
+        assert_eq!(maxes.array(), t.max::<Rank2<2, 4>, _>().array());
+    }
+}
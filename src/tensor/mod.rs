@@ -117,12 +117,59 @@
 //! If you re-use the same gradients object without zero-ing out the gradients, you can
 //! implement gradient accumulation!
 //!
+//! # Cloning
+//!
+//! Cloning a [Tensor] is cheap: the underlying data is reference counted, so a clone shares
+//! the same allocation (and the same [UniqueId]) as the original. The data is only copied
+//! if one of the clones is mutated in place while the allocation is still shared.
+//!
+//! Since clones have the same id, using multiple clones of a tensor in a graph accumulates
+//! all of their gradients into the same entry of [Gradients]:
+//!
+//! ```rust
+//! # use dfdx::prelude::*;
+//! # let dev: Cpu = Default::default();
+//! let x = dev.tensor([1.0, 2.0, 3.0]);
+//! let g = (x.leaky_trace().square() + x.clone()).sum().backward();
+//! assert_eq!(g.get(&x).array(), [3.0, 5.0, 7.0]);
+//! ```
+//!
+//! Note that a single binary op can't take two clones of the same tensor (e.g. `x.clone() * x`),
+//! since both of its inputs would need mutable access to the same gradient.
+//!
 //! # Serialization using numpy
 //!
-//! See [Tensor::save_to_npy] and [Tensor::load_from_npy].
+//! See [Tensor::save_to_npy] and [Tensor::load_from_npy]. To (de)serialize in memory
+//! instead of to a file, use [Tensor::save_to_npy_bytes] and [Tensor::load_from_npy_bytes].
 //!
 //! You can also use [Tensor::write_to_npz] and [Tensor::read_from_npz] when working with
-//! zip archives.
+//! zip archives, e.g. to store several named arrays in one `.npz` file like `np.savez`:
+//!
+//! ```ignore
+//! # use dfdx::prelude::*;
+//! # let dev: Cpu = Default::default();
+//! let a: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+//! let b: Tensor<Rank1<5>, f32, _> = dev.sample_normal();
+//! let mut zip = zip::ZipWriter::new(std::fs::File::create("arrays.npz").unwrap());
+//! a.write_to_npz(&mut zip, "a".into()).unwrap();
+//! b.write_to_npz(&mut zip, "b".into()).unwrap();
+//! zip.finish().unwrap();
+//!
+//! let mut zip = zip::ZipArchive::new(std::fs::File::open("arrays.npz").unwrap()).unwrap();
+//! let mut a: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+//! a.read_from_npz(&mut zip, "a".into()).unwrap();
+//! ```
+//!
+//! A missing name is reported as [NpzError::MissingArray].
+//!
+//! To load an array saved with a different shape but the same number of elements (e.g.
+//! `(1, N)` into a `Rank1<N>`), use [Tensor::load_from_npy_reshaped].
+//!
+//! # Converting to and from ndarray
+//!
+//! With the `ndarray` feature enabled, [Tensor::as_ndarray] borrows a [Cpu] tensor as an
+//! `ndarray` view, [Tensor::to_ndarray] copies any tensor into an owned array, and
+//! [Tensor::from_ndarray] creates a tensor from an array.
 //!
 //! # Allocation Caching
 //!
@@ -142,13 +189,17 @@ pub(crate) mod cpu;
 pub(crate) mod cuda;
 mod ghost;
 mod gradients;
+mod map_axis;
 mod masks;
+#[cfg(feature = "ndarray")]
+mod ndarray;
 #[cfg(feature = "numpy")]
 pub(crate) mod numpy;
 #[cfg(feature = "numpy")]
-pub use numpy::NumpyDtype;
+pub use numpy::{Endian, NpyError, NpzError, NumpyDtype};
 #[cfg(feature = "safetensors")]
 pub mod safetensors;
+mod stats;
 mod tensorlike;
 mod unique_id;
 
@@ -156,7 +207,7 @@ pub(crate) mod storage_traits;
 mod tensor_impls;
 
 pub(crate) use ghost::GhostTensor;
-pub(crate) use storage_traits::{OneFillStorage, ZeroFillStorage};
+pub(crate) use storage_traits::{FillStorage, OneFillStorage, ZeroFillStorage};
 pub(crate) use tensorlike::Tensorlike;
 
 pub use cpu::{Cpu, CpuError};
@@ -171,7 +222,7 @@ pub use cuda::{Cuda, CudaError};
 pub type AutoDevice = Cuda;
 
 pub use storage_traits::{AsArray, CopySlice, TensorFrom, TensorFromVec};
-pub use storage_traits::{Cache, HasErr, RandomU64, Storage, Synchronize};
+pub use storage_traits::{Cache, HasErr, RandomU64, Storage, Synchronize, WithRngSeed};
 pub use storage_traits::{OnesTensor, SampleTensor, TriangleTensor, ZerosTensor};
 
 pub use tensor_impls::{PutTape, SplitTape, Tensor, Trace, WithEmptyTape};
@@ -180,12 +231,14 @@ pub use tensor_impls::{Tensor0D, Tensor1D, Tensor2D, Tensor3D, Tensor4D, Tensor5
 pub(crate) use unique_id::unique_id;
 pub use unique_id::UniqueId;
 
-pub use gradients::{Gradients, Merge, NoneTape, OwnedTape, Tape};
+pub use gradients::{Gradients, Merge, NoneTape, OwnedTape, Tape, TapeStats};
+pub use stats::TensorStats;
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::shapes::*;
+    use crate::tensor_ops::*;
     use crate::tests::*;
     use std::collections::HashSet;
 
@@ -225,6 +278,52 @@ mod tests {
         assert_eq!(t1.id, t2.id);
     }
 
+    #[test]
+    fn test_clone_shares_data() {
+        let dev: TestDevice = Default::default();
+        let t1: Tensor<Rank1<32>, f32, _> = dev.ones();
+        let mut t2 = t1.clone();
+        assert!(std::sync::Arc::ptr_eq(&t1.data, &t2.data));
+
+        // mutating a shared clone copies the data first
+        t2.fill_with_zeros();
+        assert!(!std::sync::Arc::ptr_eq(&t1.data, &t2.data));
+        assert_eq!(t1.array(), [1.0; 32]);
+        assert_eq!(t2.array(), [0.0; 32]);
+    }
+
+    #[test]
+    fn test_clone_data_into_reuses_buffer() {
+        let dev: TestDevice = Default::default();
+        let src: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+        let mut dst: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+        let dst_id = dst.id;
+        let dst_ptr = std::sync::Arc::as_ptr(&dst.data);
+
+        src.clone_data_into(&mut dst);
+        assert_eq!(dst.array(), src.array());
+        assert_eq!(dst.id, dst_id);
+        assert!(std::ptr::eq(std::sync::Arc::as_ptr(&dst.data), dst_ptr));
+        assert!(!std::sync::Arc::ptr_eq(&src.data, &dst.data));
+    }
+
+    #[test]
+    fn test_detach_straight_through() {
+        let dev: TestDevice = Default::default();
+        let x = dev.tensor([0.2, 0.7, 1.4, -1.6]).to_dtype::<TestDtype>();
+        let rounded =
+            dev.tensor_from_vec(x.as_vec().into_iter().map(|v| v.round()).collect(), x.shape);
+
+        // forward uses round(x), backward passes the gradient straight to x
+        let offset = (rounded - x.clone()).detach();
+        assert_ne!(offset.id, x.id);
+        let y = x.leaky_trace() + offset;
+        assert_close_to_literal!(y, [0.0, 1.0, 1.0, -2.0]);
+
+        let g = y.square().sum().backward();
+        assert_close_to_literal!(g.get(&x), [0.0, 2.0, 2.0, -4.0]);
+    }
+
     #[test]
     fn test_ids_with_split_and_put() {
         let dev: TestDevice = Default::default();
@@ -250,6 +349,14 @@ mod tests {
         assert_eq!(x.array(), [[1.0; 2]; 3]);
     }
 
+    #[test]
+    fn test_fill() {
+        let dev: TestDevice = Default::default();
+        let mut x: Tensor<Rank2<3, 2>, f32, _> = dev.zeros();
+        x.fill(3.0);
+        assert_eq!(x.array(), [[3.0; 2]; 3]);
+    }
+
     #[test]
     fn test_convert_array() {
         let dev: TestDevice = Default::default();
@@ -267,6 +374,43 @@ mod tests {
         assert_eq!(t.array(), [[1.0, 2.0], [3.0, 4.0]]);
     }
 
+    #[test]
+    fn test_vec_round_trip() {
+        let dev: TestDevice = Default::default();
+
+        let t0: Tensor<Rank0, f32, _> = dev.sample_normal();
+        let t0_2: Tensor<Rank0, f32, _> = dev.tensor(t0.as_vec().as_slice());
+        assert_eq!(t0.array(), t0_2.array());
+
+        let t1: Tensor<Rank1<3>, f32, _> = dev.sample_normal();
+        let t1_2: Tensor<Rank1<3>, f32, _> = dev.tensor(t1.as_vec().as_slice());
+        assert_eq!(t1.array(), t1_2.array());
+
+        let t2: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+        let t2_2: Tensor<Rank2<2, 3>, f32, _> = dev.tensor(t2.as_vec().as_slice());
+        assert_eq!(t2.array(), t2_2.array());
+
+        let t3: Tensor<Rank3<2, 3, 4>, f32, _> = dev.sample_normal();
+        let t3_2: Tensor<Rank3<2, 3, 4>, f32, _> = dev.tensor(t3.as_vec().as_slice());
+        assert_eq!(t3.array(), t3_2.array());
+
+        let t4: Tensor<Rank4<2, 3, 4, 5>, f32, _> = dev.sample_normal();
+        let t4_2: Tensor<Rank4<2, 3, 4, 5>, f32, _> = dev.tensor(t4.as_vec().as_slice());
+        assert_eq!(t4.array(), t4_2.array());
+
+        // row major order
+        let t: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([1.0, 2.0, 3.0, 4.0].as_slice());
+        assert_eq!(t.array(), [[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!(t.as_vec(), [1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_tensor_from_slice_wrong_len() {
+        let dev: TestDevice = Default::default();
+        let r: Result<Tensor<Rank2<2, 3>, f32, _>, _> = dev.try_tensor([1.0; 5].as_slice());
+        assert!(r.is_err());
+    }
+
     #[test]
     fn fuzz_test_rand() {
         let dev: TestDevice = Default::default();
@@ -0,0 +1,130 @@
+use ::ndarray::{
+    ArrayBase, ArrayD, ArrayViewD, Data, Dimension, ErrorKind, IxDyn, ShapeBuilder, ShapeError,
+};
+
+use super::{Cpu, Storage, Tensor, TensorFromVec};
+use crate::shapes::{Shape, Unit};
+
+impl<S: Shape, E: Unit, T> Tensor<S, E, Cpu, T> {
+    /// Borrows the data of this tensor as an [ndarray] view, without copying.
+    ///
+    /// The view uses the same strides as the tensor, so broadcasted dimensions have a stride of 0.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// let view = t.as_ndarray();
+    /// assert_eq!(view.shape(), &[2, 3]);
+    /// assert_eq!(view[[1, 2]], 6.0);
+    /// ```
+    pub fn as_ndarray(&self) -> ArrayViewD<'_, E> {
+        let shape = IxDyn(self.shape.concrete().as_ref());
+        let strides = IxDyn(self.strides.as_ref());
+        // SAFETY: every index within the shape & strides of a tensor is within its data.
+        // Broadcasted strides alias elements, which is fine for an immutable view.
+        unsafe { ArrayViewD::from_shape_ptr(shape.strides(strides), self.data.data.as_ptr()) }
+    }
+}
+
+impl<S: Shape, E: Unit, D: Storage<E>, T> Tensor<S, E, D, T> {
+    /// Copies this tensor into an owned [ndarray] array in row major order.
+    pub fn to_ndarray(&self) -> ArrayD<E> {
+        let shape: std::vec::Vec<usize> = self.shape.concrete().into();
+        ArrayD::from_shape_vec(shape, self.as_vec()).unwrap()
+    }
+}
+
+impl<S: Shape, E: Unit, D: TensorFromVec<E>> Tensor<S, E, D> {
+    /// Creates a tensor on `device` from an [ndarray] array.
+    ///
+    /// Returns a [ShapeError] if the number of dimensions or any
+    /// [crate::shapes::ConstDim] of `S` does not match the shape of `arr`.
+    /// The elements are copied in row major order, so sliced, transposed or otherwise
+    /// non-contiguous arrays are supported.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let arr = ndarray::arr2(&[[1.0f32, 2.0], [3.0, 4.0]]);
+    /// let t = Tensor::<Rank2<2, 2>, f32, _>::from_ndarray(&dev, arr).unwrap();
+    /// assert_eq!(t.array(), [[1.0, 2.0], [3.0, 4.0]]);
+    /// ```
+    pub fn from_ndarray<A: Data<Elem = E>, Dm: Dimension>(
+        device: &D,
+        arr: ArrayBase<A, Dm>,
+    ) -> Result<Self, ShapeError> {
+        if arr.ndim() != S::NUM_DIMS {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape));
+        }
+        let mut concrete: S::Concrete = Default::default();
+        for (i, &size) in arr.shape().iter().enumerate() {
+            concrete[i] = size;
+        }
+        let shape = S::from_concrete(&concrete)
+            .ok_or_else(|| ShapeError::from_kind(ErrorKind::IncompatibleShape))?;
+        let data = match arr.as_slice() {
+            Some(s) => s.to_vec(),
+            None => arr.iter().cloned().collect(),
+        };
+        Ok(device.tensor_from_vec(data, shape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_ndarray_round_trip() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+
+        let arr: ::ndarray::Array2<TestDtype> = t.to_ndarray().into_dimensionality().unwrap();
+        assert_eq!(arr.shape(), &[2, 3]);
+        assert_eq!(arr[[1, 2]], t.array()[1][2]);
+
+        let r = Tensor::<Rank2<2, 3>, TestDtype, _>::from_ndarray(&dev, arr.clone()).unwrap();
+        assert_eq!(r.array(), t.array());
+
+        // column major layout is copied in logical order
+        let r = Tensor::<Rank2<3, 2>, TestDtype, _>::from_ndarray(&dev, arr.t()).unwrap();
+        assert_eq!(r.array(), t.clone().permute().array());
+
+        // dynamic dimensions take their size from the array
+        let r = Tensor::<(usize, Const<3>), TestDtype, _>::from_ndarray(&dev, arr.view()).unwrap();
+        assert_eq!(r.shape(), &(2, Const));
+
+        // owned arrays that only use part of their buffer only copy the visible elements
+        let r = Tensor::<Rank2<1, 3>, TestDtype, _>::from_ndarray(
+            &dev,
+            arr.clone().slice_move(::ndarray::s![1.., ..]),
+        )
+        .unwrap();
+        assert_eq!(r.array(), [t.array()[1]]);
+        let r = Tensor::<Rank2<2, 2>, TestDtype, _>::from_ndarray(
+            &dev,
+            arr.clone().slice_move(::ndarray::s![.., 1..]),
+        )
+        .unwrap();
+        let [a, b] = t.array();
+        assert_eq!(r.array(), [[a[1], a[2]], [b[1], b[2]]]);
+
+        assert!(Tensor::<Rank2<3, 2>, TestDtype, _>::from_ndarray(&dev, arr.clone()).is_err());
+        assert!(Tensor::<Rank1<6>, TestDtype, _>::from_ndarray(&dev, arr).is_err());
+    }
+
+    #[test]
+    fn test_as_ndarray_broadcasted() {
+        let dev: Cpu = Default::default();
+        let t = dev.tensor([1.0f32, 2.0, 3.0]);
+        let b: Tensor<Rank2<2, 3>, f32, _> = t.broadcast();
+        let view = b.as_ndarray();
+        assert_eq!(view.shape(), &[2, 3]);
+        assert_eq!(view.strides(), &[0, 1]);
+        assert_eq!(
+            view.iter().cloned().collect::<std::vec::Vec<_>>(),
+            b.as_vec()
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::shapes::{Dtype, HasShape, Shape};
+use crate::shapes::{HasShape, Shape, Unit};
 
 use super::{CopySlice, Tensor};
 
@@ -15,7 +15,7 @@ use zip::result::{ZipError, ZipResult};
 const MAGIC_NUMBER: &[u8] = b"\x93NUMPY";
 const VERSION: &[u8] = &[1, 0];
 
-impl<S: Shape, E: Dtype + NumpyDtype, D: CopySlice<E>, T> Tensor<S, E, D, T> {
+impl<S: Shape, E: Unit + NumpyDtype, D: CopySlice<E>, T> Tensor<S, E, D, T> {
     /// Writes `data` to a new file in a zip archive named `filename`.
     pub fn write_to_npz<W: Write + Seek>(
         &self,
@@ -31,6 +31,8 @@ impl<S: Shape, E: Dtype + NumpyDtype, D: CopySlice<E>, T> Tensor<S, E, D, T> {
     }
 
     /// Reads `data` from a file already in a zip archive named `filename`.
+    ///
+    /// Returns [NpzError::MissingArray] if the archive has no such file.
     pub fn read_from_npz<R: Read + Seek>(
         &mut self,
         r: &mut zip::ZipArchive<R>,
@@ -39,14 +41,18 @@ impl<S: Shape, E: Dtype + NumpyDtype, D: CopySlice<E>, T> Tensor<S, E, D, T> {
         if !filename.ends_with(".npy") {
             filename.push_str(".npy");
         }
-        let mut f = r
-            .by_name(&filename)
-            .expect(&std::format!("'{}' not found", filename));
+        let mut f = match r.by_name(&filename) {
+            Err(ZipError::FileNotFound) => return Err(NpzError::MissingArray(filename)),
+            f => f?,
+        };
         self.read_from(&mut f)?;
         Ok(())
     }
 
     /// Attemps to load the data from a `.npy` file at `path`
+    ///
+    /// Only the data is overwritten, any tape on the tensor is kept as is. Returns
+    /// [NpyError::WrongShape] if the saved shape is not the shape of this tensor.
     pub fn load_from_npy<P: AsRef<Path>>(&mut self, path: P) -> Result<(), NpyError> {
         let mut f = BufReader::new(File::open(path)?);
         self.read_from(&mut f)
@@ -58,13 +64,53 @@ impl<S: Shape, E: Dtype + NumpyDtype, D: CopySlice<E>, T> Tensor<S, E, D, T> {
         self.write_to(&mut f)
     }
 
+    /// Attempts to load the data from the contents of a `.npy` file held in memory.
+    pub fn load_from_npy_bytes(&mut self, mut bytes: &[u8]) -> Result<(), NpyError> {
+        self.read_from(&mut bytes)
+    }
+
+    /// Serializes the tensor to the contents of a `.npy` file, without touching disk.
+    pub fn save_to_npy_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.write_to(&mut buf)
+            .expect("writing to a Vec<u8> can't fail");
+        buf
+    }
+
+    /// Like [Tensor::load_from_npy], but accepts any saved shape with the same number of
+    /// elements as this tensor, e.g. a `(1, 5)` array can be loaded into a `Rank1<5>` tensor.
+    /// Values are loaded in row major order.
+    pub fn load_from_npy_reshaped<P: AsRef<Path>>(&mut self, path: P) -> Result<(), NpyError> {
+        let mut f = BufReader::new(File::open(path)?);
+        self.read_from_reshaped(&mut f)
+    }
+
+    /// Like [Tensor::load_from_npy_bytes], but accepts any saved shape with the same number of
+    /// elements as this tensor. See [Tensor::load_from_npy_reshaped].
+    pub fn load_from_npy_bytes_reshaped(&mut self, mut bytes: &[u8]) -> Result<(), NpyError> {
+        self.read_from_reshaped(&mut bytes)
+    }
+
+    pub(crate) fn read_from_reshaped<R: Read>(&mut self, r: &mut R) -> Result<(), NpyError> {
+        let (endian, shape) = read_header_any_shape::<R, E>(r)?;
+        let numel = self.shape().num_elements();
+        if shape.iter().product::<usize>() != numel {
+            return Err(NpyError::WrongNumElements {
+                expected: numel,
+                found: shape,
+            });
+        }
+        let mut buf = std::vec![Default::default(); numel];
+        E::read_endian_slice(r, endian, &mut buf)?;
+        D::copy_from(self, &buf);
+        Ok(())
+    }
+
     pub(crate) fn read_from<R: Read>(&mut self, r: &mut R) -> Result<(), NpyError> {
         let endian = read_header::<R, E>(r, self.shape().concrete().into_iter().collect())?;
         let numel = self.shape().num_elements();
-        let mut buf = Vec::with_capacity(numel);
-        for _ in 0..numel {
-            buf.push(E::read_endian(r, endian)?);
-        }
+        let mut buf = std::vec![Default::default(); numel];
+        E::read_endian_slice(r, endian, &mut buf)?;
         D::copy_from(self, &buf);
         Ok(())
     }
@@ -93,7 +139,9 @@ fn write_header<W: Write, E: NumpyDtype>(
     write!(
         &mut header,
         "{{'descr': '{}{}', 'fortran_order': False, 'shape': ({}), }}",
+        // numpy uses '|' for types where byte order doesn't apply
         match endian {
+            _ if std::mem::size_of::<E>() == 1 => '|',
             Endian::Big => '>',
             Endian::Little => '<',
             Endian::Native => '=',
@@ -122,6 +170,76 @@ fn write_header<W: Write, E: NumpyDtype>(
 }
 
 fn read_header<R: Read, E: NumpyDtype>(r: &mut R, shape: Vec<usize>) -> Result<Endian, NpyError> {
+    let (endian, found) = read_header_any_shape::<R, E>(r)?;
+    if found != shape {
+        return Err(NpyError::WrongShape {
+            expected: shape,
+            found,
+        });
+    }
+    Ok(endian)
+}
+
+/// Reads the header of an array with any shape, and returns that shape.
+fn read_header_any_shape<R: Read, E: NumpyDtype>(
+    r: &mut R,
+) -> Result<(Endian, Vec<usize>), NpyError> {
+    let (endian, header, mut i) = read_header_until_shape::<R, E>(r)?;
+    let mut shape = Vec::new();
+    while header.get(i).map_or(false, u8::is_ascii_digit) {
+        let start = i;
+        while header.get(i).map_or(false, u8::is_ascii_digit) {
+            i += 1;
+        }
+        let dim = String::from_utf8(header[start..i].to_vec())?
+            .parse()
+            .map_err(|_| NpyError::ParsingMismatch {
+                expected: b"dimension size".to_vec(),
+                found: header[start..i].to_vec(),
+                expected_str: "dimension size".to_string(),
+                found_str: String::from_utf8_lossy(&header[start..i]).to_string(),
+            })?;
+        shape.push(dim);
+        if header.get(i) == Some(&b',') {
+            i += 1;
+        }
+        if header.get(i) == Some(&b' ') {
+            i += 1;
+        }
+    }
+    expect(&header, i, b"), }")?;
+    Ok((endian, shape))
+}
+
+/// Reads the header of a 2d array with `features` columns, and returns the
+/// number of rows it contains.
+pub(crate) fn read_header_rows<R: Read, E: NumpyDtype>(
+    r: &mut R,
+    features: usize,
+) -> Result<(Endian, usize), NpyError> {
+    let (endian, header, mut i) = read_header_until_shape::<R, E>(r)?;
+    let start = i;
+    while header.get(i).map_or(false, u8::is_ascii_digit) {
+        i += 1;
+    }
+    let num_rows = String::from_utf8(header[start..i].to_vec())?
+        .parse()
+        .map_err(|_| NpyError::ParsingMismatch {
+            expected: b"number of rows".to_vec(),
+            found: header[start..i].to_vec(),
+            expected_str: "number of rows".to_string(),
+            found_str: String::from_utf8_lossy(&header[start..i]).to_string(),
+        })?;
+    let i = expect(&header, i, std::format!(", {features}").as_bytes())?;
+    expect(&header, i, b"), }")?;
+    Ok((endian, num_rows))
+}
+
+/// Parses everything in the header up to and including `'shape': (`, and returns
+/// the index of the first character of the shape.
+fn read_header_until_shape<R: Read, E: NumpyDtype>(
+    r: &mut R,
+) -> Result<(Endian, Vec<u8>, usize), NpyError> {
     let mut magic = [0; 6];
     r.read_exact(&mut magic)?;
     if magic != MAGIC_NUMBER {
@@ -130,29 +248,53 @@ fn read_header<R: Read, E: NumpyDtype>(r: &mut R, shape: Vec<usize>) -> Result<E
 
     let mut version = [0; 2];
     r.read_exact(&mut version)?;
-    if version != VERSION {
-        return Err(NpyError::InvalidVersion(version));
-    }
 
-    let mut header_len_bytes = [0; 2];
-    r.read_exact(&mut header_len_bytes)?;
-    let header_len = u16::from_le_bytes(header_len_bytes);
+    // version 1.0 uses a 2 byte header length, while 2.0 and 3.0 use 4 bytes.
+    // 3.0 only differs from 2.0 by allowing utf8 in the header, which is already
+    // how the header is parsed.
+    let header_len = match version {
+        [1, 0] => {
+            let mut header_len_bytes = [0; 2];
+            r.read_exact(&mut header_len_bytes)?;
+            u16::from_le_bytes(header_len_bytes) as usize
+        }
+        [2, 0] | [3, 0] => {
+            let mut header_len_bytes = [0; 4];
+            r.read_exact(&mut header_len_bytes)?;
+            u32::from_le_bytes(header_len_bytes) as usize
+        }
+        _ => return Err(NpyError::InvalidVersion(version)),
+    };
 
-    let mut header: Vec<u8> = std::vec![0; header_len as usize];
+    let mut header: Vec<u8> = std::vec![0; header_len];
     r.read_exact(&mut header)?;
 
     let mut i = 0;
-    i = expect(&header, i, b"{'descr': '")?;
+    i = expect(&header, i, b"{'descr': ")?;
+    if header.get(i) == Some(&b'[') {
+        return Err(NpyError::UnsupportedStructuredDtype);
+    }
+    i = expect(&header, i, b"'")?;
 
     let endian = match header[i] {
         b'>' => Endian::Big,
         b'<' => Endian::Little,
-        b'=' => Endian::Native,
+        b'=' | b'|' => Endian::Native,
         _ => return Err(NpyError::InvalidAlignment),
     };
     i += 1;
 
-    i = expect(&header, i, E::NUMPY_DTYPE_STR.as_bytes())?;
+    let descr_len = header[i..]
+        .iter()
+        .position(|&c| c == b'\'')
+        .unwrap_or(header.len() - i);
+    if &header[i..i + descr_len] != E::NUMPY_DTYPE_STR.as_bytes() {
+        return Err(NpyError::WrongDtype {
+            expected: E::NUMPY_DTYPE_STR.to_string(),
+            found: String::from_utf8_lossy(&header[i..i + descr_len]).to_string(),
+        });
+    }
+    i += descr_len;
     i = expect(&header, i, b"', ")?;
 
     // fortran order
@@ -160,11 +302,8 @@ fn read_header<R: Read, E: NumpyDtype>(r: &mut R, shape: Vec<usize>) -> Result<E
 
     // shape
     i = expect(&header, i, b"'shape': (")?;
-    let shape_str = to_shape_str(shape);
-    i = expect(&header, i, shape_str.as_bytes())?;
-    expect(&header, i, b"), }")?;
 
-    Ok(endian)
+    Ok((endian, header, i))
 }
 
 fn expect(buf: &[u8], i: usize, chars: &[u8]) -> Result<usize, NpyError> {
@@ -185,6 +324,7 @@ fn expect(buf: &[u8], i: usize, chars: &[u8]) -> Result<usize, NpyError> {
     Ok(i + chars.len())
 }
 
+/// Byte order of the values in a `.npy` file.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Endian {
     Big,
@@ -202,6 +342,42 @@ pub trait NumpyDtype: Sized {
     const NUMPY_DTYPE_STR: &'static str;
     fn read_endian<R: Read>(r: &mut R, endian: Endian) -> io::Result<Self>;
     fn write_endian<W: Write>(&self, w: &mut W, endian: Endian) -> io::Result<()>;
+
+    /// Fills `dst` with consecutive values from `r`. By default this reads one value
+    /// at a time with [NumpyDtype::read_endian].
+    fn read_endian_slice<R: Read>(r: &mut R, endian: Endian, dst: &mut [Self]) -> io::Result<()> {
+        for v in dst.iter_mut() {
+            *v = Self::read_endian(r, endian)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fills `dst` with a single read of its raw bytes, and then swaps the bytes of each
+/// value if `endian` is not the byte order of this machine.
+///
+/// # Safety
+/// Every bit pattern must be a valid `E`.
+unsafe fn read_raw<R: Read, E: Copy>(
+    r: &mut R,
+    endian: Endian,
+    dst: &mut [E],
+    swap_bytes: fn(E) -> E,
+) -> io::Result<()> {
+    let bytes =
+        std::slice::from_raw_parts_mut(dst.as_mut_ptr() as *mut u8, std::mem::size_of_val(dst));
+    r.read_exact(bytes)?;
+    let swap = match endian {
+        Endian::Native => false,
+        Endian::Little => cfg!(target_endian = "big"),
+        Endian::Big => cfg!(target_endian = "little"),
+    };
+    if swap {
+        for v in dst.iter_mut() {
+            *v = swap_bytes(*v);
+        }
+    }
+    Ok(())
 }
 
 impl NumpyDtype for f32 {
@@ -222,6 +398,14 @@ impl NumpyDtype for f32 {
             Endian::Native => w.write_all(&self.to_ne_bytes()),
         }
     }
+    fn read_endian_slice<R: Read>(r: &mut R, endian: Endian, dst: &mut [Self]) -> io::Result<()> {
+        // SAFETY: every bit pattern is a valid float
+        unsafe {
+            read_raw(r, endian, dst, |v| {
+                Self::from_bits(v.to_bits().swap_bytes())
+            })
+        }
+    }
 }
 
 impl NumpyDtype for f64 {
@@ -242,6 +426,62 @@ impl NumpyDtype for f64 {
             Endian::Native => w.write_all(&self.to_ne_bytes()),
         }
     }
+    fn read_endian_slice<R: Read>(r: &mut R, endian: Endian, dst: &mut [Self]) -> io::Result<()> {
+        // SAFETY: every bit pattern is a valid float
+        unsafe {
+            read_raw(r, endian, dst, |v| {
+                Self::from_bits(v.to_bits().swap_bytes())
+            })
+        }
+    }
+}
+
+macro_rules! npy_int {
+    ($Int:ty, $Descr:literal) => {
+        impl NumpyDtype for $Int {
+            const NUMPY_DTYPE_STR: &'static str = $Descr;
+            fn read_endian<R: Read>(r: &mut R, endian: Endian) -> io::Result<Self> {
+                let mut bytes = [0; std::mem::size_of::<$Int>()];
+                r.read_exact(&mut bytes)?;
+                Ok(match endian {
+                    Endian::Big => Self::from_be_bytes(bytes),
+                    Endian::Little => Self::from_le_bytes(bytes),
+                    Endian::Native => Self::from_ne_bytes(bytes),
+                })
+            }
+            fn write_endian<W: Write>(&self, w: &mut W, endian: Endian) -> io::Result<()> {
+                match endian {
+                    Endian::Big => w.write_all(&self.to_be_bytes()),
+                    Endian::Little => w.write_all(&self.to_le_bytes()),
+                    Endian::Native => w.write_all(&self.to_ne_bytes()),
+                }
+            }
+            fn read_endian_slice<R: Read>(
+                r: &mut R,
+                endian: Endian,
+                dst: &mut [Self],
+            ) -> io::Result<()> {
+                // SAFETY: every bit pattern is a valid integer
+                unsafe { read_raw(r, endian, dst, Self::swap_bytes) }
+            }
+        }
+    };
+}
+
+npy_int!(i32, "i4");
+npy_int!(i64, "i8");
+npy_int!(u8, "u1");
+
+impl NumpyDtype for bool {
+    const NUMPY_DTYPE_STR: &'static str = "b1";
+    fn read_endian<R: Read>(r: &mut R, _: Endian) -> io::Result<Self> {
+        let mut bytes = [0; 1];
+        r.read_exact(&mut bytes)?;
+        Ok(bytes[0] != 0)
+    }
+    fn write_endian<W: Write>(&self, w: &mut W, _: Endian) -> io::Result<()> {
+        w.write_all(&[*self as u8])
+    }
 }
 
 #[derive(Debug)]
@@ -267,6 +507,29 @@ pub enum NpyError {
 
     /// Unexpected alignment for [Endian].
     InvalidAlignment,
+
+    /// The `descr` is a list of fields (a structured/record dtype), which
+    /// can't be loaded into a tensor.
+    UnsupportedStructuredDtype,
+
+    /// The saved shape doesn't match the shape of the tensor.
+    WrongShape {
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+
+    /// The saved shape doesn't have the expected number of elements.
+    WrongNumElements {
+        expected: usize,
+        found: Vec<usize>,
+    },
+
+    /// The saved dtype doesn't match the dtype of the tensor, e.g. an `i4`
+    /// array loaded into an `f32` tensor.
+    WrongDtype {
+        expected: String,
+        found: String,
+    },
 }
 
 impl std::fmt::Display for NpyError {
@@ -285,6 +548,18 @@ impl std::fmt::Display for NpyError {
                 "error while parsing: expected {expected_str} found {found_str}"
             ),
             NpyError::InvalidAlignment => write!(fmt, "invalid alignment"),
+            NpyError::UnsupportedStructuredDtype => {
+                write!(fmt, "structured dtypes are not supported")
+            }
+            NpyError::WrongShape { expected, found } => {
+                write!(fmt, "expected shape {expected:?}, found shape {found:?}")
+            }
+            NpyError::WrongNumElements { expected, found } => {
+                write!(fmt, "expected {expected} elements, found shape {found:?}")
+            }
+            NpyError::WrongDtype { expected, found } => {
+                write!(fmt, "expected dtype {expected}, found {found}")
+            }
         }
     }
 }
@@ -328,6 +603,9 @@ pub enum NpzError {
 
     /// Something went wrong with loading data from a `.npy` file
     Npy(NpyError),
+
+    /// The archive doesn't contain a `.npy` file with this name.
+    MissingArray(String),
 }
 
 impl std::fmt::Display for NpzError {
@@ -335,6 +613,7 @@ impl std::fmt::Display for NpzError {
         match self {
             NpzError::Zip(err) => write!(fmt, "{err}"),
             NpzError::Npy(err) => write!(fmt, "{err}"),
+            NpzError::MissingArray(name) => write!(fmt, "'{name}' not found in archive"),
         }
     }
 }
@@ -344,6 +623,7 @@ impl std::error::Error for NpzError {
         match self {
             NpzError::Zip(err) => Some(err),
             NpzError::Npy(err) => Some(err),
+            NpzError::MissingArray(_) => None,
         }
     }
 }
@@ -369,7 +649,12 @@ impl From<std::io::Error> for NpzError {
 #[cfg(test)]
 mod tests {
     use crate::{
-        tensor::{AsArray, TensorFrom},
+        shapes::{Rank1, Rank2},
+        tensor::{
+            AsArray, OnesTensor, OwnedTape, SampleTensor, TensorFrom, TensorFromVec, Trace,
+            ZerosTensor,
+        },
+        tensor_ops::ChooseFrom,
         tests::TestDevice,
     };
 
@@ -459,6 +744,341 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_2d_f32_bytes_round_trip() {
+        let dev: TestDevice = Default::default();
+        let x = dev.tensor([[0.0f32, 1.0, 2.0], [3.0, 4.0, -5.0]]);
+
+        let file = NamedTempFile::new().expect("failed to create tempfile");
+        x.save_to_npy(file.path()).expect("Saving failed");
+        let mut expected = Vec::new();
+        File::open(file.path())
+            .expect("No file found")
+            .read_to_end(&mut expected)
+            .expect("Reading failed");
+
+        let bytes = x.save_to_npy_bytes();
+        assert_eq!(bytes, expected);
+
+        let mut value = dev.tensor([[0.0f32; 3]; 2]);
+        value.load_from_npy_bytes(&bytes).expect("Loading failed");
+        assert_eq!(value.array(), x.array());
+
+        dev.tensor([[0.0f32; 2]; 3])
+            .load_from_npy_bytes(&bytes)
+            .expect_err("");
+        dev.tensor([[0.0f32; 3]; 2])
+            .load_from_npy_bytes(&bytes[..bytes.len() - 1])
+            .expect_err("");
+    }
+
+    #[test]
+    fn test_1d_f32_load_v2_and_v3() {
+        let dev: TestDevice = Default::default();
+
+        // `np.lib.format.write_array(f, np.array([1, 2, -3], dtype='<f4'), version=(2, 0))`
+        let mut v2: Vec<u8> = std::vec![147, 78, 85, 77, 80, 89, 2, 0, 116, 0, 0, 0];
+        v2.extend_from_slice(b"{'descr': '<f4', 'fortran_order': False, 'shape': (3,), }");
+        v2.resize(12 + 115, b' ');
+        v2.push(b'\n');
+        assert_eq!(v2.len() % 64, 0);
+        for v in [1.0f32, 2.0, -3.0] {
+            v2.extend_from_slice(&v.to_le_bytes());
+        }
+
+        let mut value = dev.tensor([0.0f32; 3]);
+        value.load_from_npy_bytes(&v2).expect("Loading v2 failed");
+        assert_eq!(value.array(), [1.0, 2.0, -3.0]);
+
+        let mut v3 = v2.clone();
+        v3[6] = 3;
+        let mut value = dev.tensor([0.0f32; 3]);
+        value.load_from_npy_bytes(&v3).expect("Loading v3 failed");
+        assert_eq!(value.array(), [1.0, 2.0, -3.0]);
+
+        let mut v4 = v2;
+        v4[6] = 4;
+        assert!(matches!(
+            dev.tensor([0.0f32; 3]).load_from_npy_bytes(&v4),
+            Err(NpyError::InvalidVersion([4, 0]))
+        ));
+    }
+
+    #[test]
+    fn test_load_v2_header_longer_than_u16() {
+        let dev: TestDevice = Default::default();
+
+        // version 2.0 exists for headers that don't fit in a u16 length
+        let header_len = 70_016u32;
+        assert!(header_len > u16::MAX as u32);
+        let mut v2: Vec<u8> = MAGIC_NUMBER.to_vec();
+        v2.extend_from_slice(&[2, 0]);
+        v2.extend_from_slice(&header_len.to_le_bytes());
+        v2.extend_from_slice(b"{'descr': '<f8', 'fortran_order': False, 'shape': (2,), }");
+        v2.resize(12 + header_len as usize - 1, b' ');
+        v2.push(b'\n');
+        for v in [0.5f64, -4.0] {
+            v2.extend_from_slice(&v.to_le_bytes());
+        }
+
+        let mut value = dev.tensor([0.0f64; 2]);
+        value.load_from_npy_bytes(&v2).expect("Loading v2 failed");
+        assert_eq!(value.array(), [0.5, -4.0]);
+
+        // saving still writes version 1.0, which loads back
+        let bytes = value.save_to_npy_bytes();
+        assert_eq!(&bytes[6..8], &[1, 0]);
+        let mut loaded = dev.tensor([0.0f64; 2]);
+        loaded
+            .load_from_npy_bytes(&bytes)
+            .expect("Loading v1 failed");
+        assert_eq!(loaded.array(), [0.5, -4.0]);
+    }
+
+    #[test]
+    fn test_load_overflowing_shape() {
+        let dev: TestDevice = Default::default();
+
+        let header_len = 118u16;
+        let mut v1: Vec<u8> = MAGIC_NUMBER.to_vec();
+        v1.extend_from_slice(&[1, 0]);
+        v1.extend_from_slice(&header_len.to_le_bytes());
+        v1.extend_from_slice(
+            b"{'descr': '<f4', 'fortran_order': False, 'shape': (99999999999999999999999,), }",
+        );
+        v1.resize(10 + header_len as usize - 1, b' ');
+        v1.push(b'\n');
+
+        let mut value = dev.tensor([0.0f32; 3]);
+        assert!(matches!(
+            value.load_from_npy_bytes(&v1),
+            Err(NpyError::ParsingMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_structured_dtype_load() {
+        let dev: TestDevice = Default::default();
+
+        // `np.save(f, np.zeros(2, dtype=[('a', '<f4'), ('b', '<f4')]))`
+        let mut bytes: Vec<u8> = std::vec![147, 78, 85, 77, 80, 89, 1, 0, 118, 0];
+        bytes.extend_from_slice(
+            b"{'descr': [('a', '<f4'), ('b', '<f4')], 'fortran_order': False, 'shape': (2,), }",
+        );
+        bytes.resize(10 + 117, b' ');
+        bytes.push(b'\n');
+        assert_eq!(bytes.len() % 64, 0);
+        bytes.extend_from_slice(&[0; 16]);
+
+        assert!(matches!(
+            dev.tensor([0.0f32; 2]).load_from_npy_bytes(&bytes),
+            Err(NpyError::UnsupportedStructuredDtype)
+        ));
+        assert!(matches!(
+            dev.tensor([[0.0f32; 2]; 2]).load_from_npy_bytes(&bytes),
+            Err(NpyError::UnsupportedStructuredDtype)
+        ));
+    }
+
+    #[test]
+    fn test_large_f64_round_trip() {
+        let dev: TestDevice = Default::default();
+        let numel = 10_000;
+        let data: Vec<f64> = (0..numel).map(|i| i as f64 * 0.5 - 100.0).collect();
+        let x = dev.tensor_from_vec(data.clone(), (numel,));
+        let mut y: Tensor<(usize,), f64, _> = dev.zeros_like(&(numel,));
+        y.load_from_npy_bytes(&x.save_to_npy_bytes())
+            .expect("Loading failed");
+        assert_eq!(y.as_vec(), data);
+    }
+
+    #[test]
+    fn test_read_slice_matches_read_endian() {
+        let values = [1.5f32, -2.25, 3.0e-7, f32::MAX];
+        for endian in [Endian::Big, Endian::Little, Endian::Native] {
+            let mut bytes = Vec::new();
+            for v in values {
+                v.write_endian(&mut bytes, endian).unwrap();
+            }
+            let mut bulk = [0.0f32; 4];
+            f32::read_endian_slice(&mut bytes.as_slice(), endian, &mut bulk).unwrap();
+            let mut r = bytes.as_slice();
+            let single: Vec<f32> = (0..4)
+                .map(|_| f32::read_endian(&mut r, endian).unwrap())
+                .collect();
+            assert_eq!(bulk, values);
+            assert_eq!(single, values);
+        }
+    }
+
+    #[test]
+    fn test_load_reshaped() {
+        let dev: TestDevice = Default::default();
+        let data = [1.0f32, 2.0, 3.0, 4.0, 5.0];
+
+        let row = dev.tensor([data]);
+        let mut flat: Tensor<Rank1<5>, f32, _> = dev.zeros();
+        assert!(flat.load_from_npy_bytes(&row.save_to_npy_bytes()).is_err());
+        flat.load_from_npy_bytes_reshaped(&row.save_to_npy_bytes())
+            .expect("Loading failed");
+        assert_eq!(flat.array(), data);
+
+        let file = NamedTempFile::new().expect("failed to create tempfile");
+        dev.tensor(data)
+            .save_to_npy(file.path())
+            .expect("Saving failed");
+        let mut row: Tensor<Rank2<1, 5>, f32, _> = dev.zeros();
+        row.load_from_npy_reshaped(file.path())
+            .expect("Loading failed");
+        assert_eq!(row.array(), [data]);
+
+        let mut wrong: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+        match wrong.load_from_npy_reshaped(file.path()) {
+            Err(NpyError::WrongNumElements { expected, found }) => {
+                assert_eq!(expected, 6);
+                assert_eq!(found, [5]);
+            }
+            _ => panic!("expected WrongNumElements"),
+        }
+    }
+
+    #[test]
+    fn test_1d_bool_round_trip() {
+        let dev: TestDevice = Default::default();
+        let mask = dev.tensor([true, false, false, true, true]);
+
+        let bytes = mask.save_to_npy_bytes();
+        let header = String::from_utf8(bytes[10..bytes.len() - 5].to_vec()).unwrap();
+        assert!(header.starts_with("{'descr': '|b1', 'fortran_order': False, 'shape': (5,), }"));
+        assert_eq!(&bytes[bytes.len() - 5..], &[1, 0, 0, 1, 1]);
+
+        // numpy only writes 0 or 1, but any nonzero byte is true
+        let mut bytes = bytes;
+        let n = bytes.len();
+        bytes[n - 2] = 2;
+
+        let mut loaded = dev.tensor([false; 5]);
+        loaded.load_from_npy_bytes(&bytes).expect("Loading failed");
+        assert_eq!(loaded.array(), mask.array());
+
+        let ones = dev.ones_like(&loaded);
+        let zeros = dev.zeros_like(&loaded);
+        let mask_f32: Tensor<_, f32, _> = loaded.choose(ones, zeros);
+        assert_eq!(mask_f32.array(), [1.0, 0.0, 0.0, 1.0, 1.0]);
+
+        dev.tensor([0.0f32; 5])
+            .load_from_npy_bytes(&bytes)
+            .expect_err("");
+    }
+
+    #[test]
+    fn test_1d_i32_round_trip() {
+        let dev: TestDevice = Default::default();
+        let labels = dev.tensor([3i32, -1, 0, i32::MAX]);
+
+        let bytes = labels.save_to_npy_bytes();
+        let header = String::from_utf8(bytes[10..bytes.len() - 16].to_vec()).unwrap();
+        assert!(header.starts_with("{'descr': '<i4', 'fortran_order': False, 'shape': (4,), }"));
+
+        let mut loaded = dev.tensor([0i32; 4]);
+        loaded.load_from_npy_bytes(&bytes).expect("Loading failed");
+        assert_eq!(loaded.array(), labels.array());
+
+        let wide = dev.tensor([i64::MIN, 7]);
+        let mut loaded = dev.tensor([0i64; 2]);
+        loaded
+            .load_from_npy_bytes(&wide.save_to_npy_bytes())
+            .expect("Loading failed");
+        assert_eq!(loaded.array(), wide.array());
+    }
+
+    #[test]
+    fn test_2d_u8_round_trip() {
+        let dev: TestDevice = Default::default();
+        let pixels = dev.tensor([[0u8, 127, 255], [1, 2, 3]]);
+
+        let file = NamedTempFile::new().expect("failed to create tempfile");
+        pixels.save_to_npy(file.path()).expect("Saving failed");
+
+        let mut loaded = dev.tensor([[0u8; 3]; 2]);
+        loaded.load_from_npy(file.path()).expect("Loading failed");
+        assert_eq!(loaded.array(), pixels.array());
+
+        let bytes = pixels.save_to_npy_bytes();
+        let header = String::from_utf8(bytes[10..bytes.len() - 6].to_vec()).unwrap();
+        assert!(header.starts_with("{'descr': '|u1', 'fortran_order': False, 'shape': (2, 3), }"));
+    }
+
+    #[test]
+    fn test_load_wrong_dtype() {
+        let dev: TestDevice = Default::default();
+        let bytes = dev.tensor([1i32, 2, 3, 4]).save_to_npy_bytes();
+
+        let mut floats = dev.tensor([0.0f32; 4]);
+        match floats.load_from_npy_bytes(&bytes) {
+            Err(NpyError::WrongDtype { expected, found }) => {
+                assert_eq!(expected, "f4");
+                assert_eq!(found, "i4");
+            }
+            _ => panic!("expected WrongDtype"),
+        }
+    }
+
+    #[test]
+    fn test_npz_multiple_named_arrays() {
+        let dev: TestDevice = Default::default();
+        let a = dev.tensor([[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let b = dev.tensor([-1.0f32, 0.5]);
+
+        let file = NamedTempFile::new().expect("failed to create tempfile");
+        {
+            let mut zip = zip::ZipWriter::new(File::create(file.path()).unwrap());
+            a.write_to_npz(&mut zip, "a".to_string()).unwrap();
+            b.write_to_npz(&mut zip, "b.npy".to_string()).unwrap();
+            zip.finish().unwrap();
+        }
+
+        let mut zip = zip::ZipArchive::new(File::open(file.path()).unwrap()).unwrap();
+        let mut names: Vec<&str> = zip.file_names().collect();
+        names.sort_unstable();
+        assert_eq!(names, ["a.npy", "b.npy"]);
+
+        let mut loaded_b = dev.tensor([0.0f32; 2]);
+        loaded_b.read_from_npz(&mut zip, "b".to_string()).unwrap();
+        assert_eq!(loaded_b.array(), b.array());
+        let mut loaded_a = dev.tensor([[0.0f32; 3]; 2]);
+        loaded_a.read_from_npz(&mut zip, "a".to_string()).unwrap();
+        assert_eq!(loaded_a.array(), a.array());
+
+        match loaded_a.read_from_npz(&mut zip, "c".to_string()) {
+            Err(NpzError::MissingArray(name)) => assert_eq!(name, "c.npy"),
+            _ => panic!("expected MissingArray"),
+        }
+    }
+
+    #[test]
+    fn test_2d_round_trip_with_tape() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<3, 4>, f32, _> = dev.sample_normal();
+
+        let file = NamedTempFile::new().expect("failed to create tempfile");
+        let traced: Tensor<_, _, _, OwnedTape<f32, _>> = x.leaky_trace();
+        traced.save_to_npy(file.path()).expect("Saving failed");
+
+        let mut loaded: Tensor<Rank2<3, 4>, f32, _, OwnedTape<f32, _>> = dev.zeros().leaky_traced();
+        loaded.load_from_npy(file.path()).expect("Loading failed");
+        assert_eq!(loaded.array(), x.array());
+
+        let mut transposed: Tensor<Rank2<4, 3>, f32, _> = dev.zeros();
+        match transposed.load_from_npy(file.path()) {
+            Err(NpyError::WrongShape { expected, found }) => {
+                assert_eq!(expected, [4, 3]);
+                assert_eq!(found, [3, 4]);
+            }
+            _ => panic!("expected WrongShape"),
+        }
+    }
+
     #[test]
     fn test_0d_f32_load() {
         let dev: TestDevice = Default::default();
@@ -0,0 +1,178 @@
+use num_traits::Float;
+
+use crate::shapes::{Dtype, Shape};
+
+use super::{Storage, Tensor};
+
+/// Summary statistics of the values of a tensor. See [Tensor::summary()].
+///
+/// **NaN policy**: NaN values are counted in [TensorStats::nan_count] and are
+/// otherwise **ignored**, so `min`, `max`, `mean` and `std` are computed over the
+/// non-NaN values only. If there are no non-NaN values, all of them are NaN.
+/// Infinities are not ignored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TensorStats<E> {
+    pub min: E,
+    pub max: E,
+    pub mean: E,
+    /// Population standard deviation (divides by `N`, not `N - 1`).
+    pub std: E,
+    pub nan_count: usize,
+}
+
+impl<S: Shape, E: Dtype + Float, D: Storage<E>, T> Tensor<S, E, D, T> {
+    /// Computes [TensorStats] of all the values in this tensor in a single pass. Useful for
+    /// catching exploding or NaN activations while debugging.
+    ///
+    /// This copies the data to the host, so it should not be used in hot loops.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([1.0, f32::NAN, 3.0]);
+    /// let stats = t.summary();
+    /// assert_eq!(stats.nan_count, 1);
+    /// assert_eq!(stats.min, 1.0);
+    /// assert_eq!(stats.max, 3.0);
+    /// assert_eq!(stats.mean, 2.0);
+    /// ```
+    pub fn summary(&self) -> TensorStats<E> {
+        let mut min = E::nan();
+        let mut max = E::nan();
+        let mut nan_count = 0;
+
+        // welford's algorithm, accumulated in f64 for stability
+        let mut n = 0usize;
+        let mut mean = 0.0f64;
+        let mut m2 = 0.0f64;
+
+        for v in self.as_vec() {
+            if v.is_nan() {
+                nan_count += 1;
+                continue;
+            }
+            // `Float::min`/`Float::max` return the non-NaN argument, so the
+            // initial NaN is replaced by the first value.
+            min = min.min(v);
+            max = max.max(v);
+
+            let x = v.to_f64().unwrap();
+            n += 1;
+            let delta = x - mean;
+            mean += delta / n as f64;
+            m2 += delta * (x - mean);
+        }
+
+        let (mean, std) = if n == 0 {
+            (E::nan(), E::nan())
+        } else {
+            let std = (m2 / n as f64).sqrt();
+            (E::from_f64(mean).unwrap(), E::from_f64(std).unwrap())
+        };
+
+        TensorStats {
+            min,
+            max,
+            mean,
+            std,
+            nan_count,
+        }
+    }
+
+    /// Panics if this tensor contains a NaN or infinite value. The panic message contains
+    /// the first non-finite value and its index.
+    ///
+    /// ```rust,should_panic
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, 2.0], [f32::INFINITY, 3.0]]);
+    /// t.assert_all_finite(); // panics with "... inf at index [1, 0]"
+    /// ```
+    pub fn assert_all_finite(&self) {
+        let dims = self.shape.concrete();
+        if let Some((i, v)) = first_non_finite(&self.as_vec()) {
+            let mut index = dims;
+            let mut rem = i;
+            for d in (0..S::NUM_DIMS).rev() {
+                index[d] = rem % dims[d];
+                rem /= dims[d];
+            }
+            panic!("Tensor contains non-finite value {v:?} at index {index:?}");
+        }
+    }
+}
+
+/// Returns the first NaN or infinite value along with its index.
+pub(crate) fn first_non_finite<E: Float>(data: &[E]) -> Option<(usize, E)> {
+    data.iter()
+        .copied()
+        .enumerate()
+        .find(|(_, v)| !v.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+    use num_traits::ToPrimitive;
+
+    #[test]
+    fn test_summary_with_nan() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, -2.0, f64::NAN], [3.0, 4.0, 0.0]])
+            .to_dtype::<TestDtype>();
+        let stats = t.summary();
+        assert_eq!(stats.nan_count, 1);
+        let actual = [stats.min, stats.max, stats.mean, stats.std].map(|v| v.to_f64().unwrap());
+        assert_close!(actual, [-2.0, 4.0, 1.2, 2.1354156], 1e-3);
+    }
+
+    #[test]
+    fn test_summary_all_nan() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<3>, TestDtype, _> = dev.zeros();
+        let t = t.clone() / t;
+        let stats = t.summary();
+        assert_eq!(stats.nan_count, 3);
+        assert!(stats.min.is_nan());
+        assert!(stats.max.is_nan());
+        assert!(stats.mean.is_nan());
+        assert!(stats.std.is_nan());
+    }
+
+    #[test]
+    #[should_panic = "Tensor contains non-finite value inf at index [1, 0]"]
+    fn test_assert_all_finite_inf() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0], [f64::INFINITY, 3.0]])
+            .to_dtype::<TestDtype>();
+        t.assert_all_finite();
+    }
+
+    #[test]
+    fn test_assert_all_finite_ok() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        t.assert_all_finite();
+    }
+
+    #[test]
+    #[should_panic = "Gradient of tensor"]
+    fn test_gradients_assert_all_finite() {
+        let dev: TestDevice = Default::default();
+        let a = dev.tensor([1.0, 0.0, 4.0]).to_dtype::<TestDtype>();
+        let g = a.leaky_trace().sqrt().sum().backward();
+        g.assert_all_finite(&dev);
+    }
+
+    #[test]
+    fn test_summary_0d() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor(2.0).to_dtype::<TestDtype>();
+        let stats = t.summary();
+        assert_eq!(stats.nan_count, 0);
+        let actual = [stats.min, stats.max, stats.mean, stats.std].map(|v| v.to_f64().unwrap());
+        assert_close!(actual, [2.0, 2.0, 2.0, 0.0]);
+    }
+}
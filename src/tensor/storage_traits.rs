@@ -21,6 +21,14 @@ pub trait RandomU64 {
     fn random_u64(&self) -> u64;
 }
 
+/// A device that can make a copy of itself with a separately seeded random number generator.
+pub trait WithRngSeed: Sized {
+    /// Returns a copy of the device with its own random number generator, seeded with
+    /// `seed`. Everything else, like the allocation cache, is shared with `self`, and the
+    /// random number generator of `self` is left untouched.
+    fn with_rng_seed(&self, seed: u64) -> Self;
+}
+
 /// Something that can store nd arrays for a given [Shape] and [Dtype]
 pub trait Storage<E>: 'static + std::fmt::Debug + Default + Clone + HasErr {
     /// Generic Storage type
@@ -103,6 +111,10 @@ impl<S: Shape, E, D: Storage<E>, T> AllocGrad for Tensor<S, E, D, T> {
 pub trait CopySlice<E>: Storage<E> {
     fn copy_from<S: Shape, T>(dst: &mut Tensor<S, E, Self, T>, src: &[E]);
     fn copy_into<S: Shape, T>(src: &Tensor<S, E, Self, T>, dst: &mut [E]);
+    fn clone_data_into<S: Shape, T, R>(
+        src: &Tensor<S, E, Self, T>,
+        dst: &mut Tensor<S, E, Self, R>,
+    );
 }
 
 impl<S: Shape, E, D: CopySlice<E>, T> Tensor<S, E, D, T> {
@@ -133,6 +145,25 @@ impl<S: Shape, E, D: CopySlice<E>, T> Tensor<S, E, D, T> {
     pub fn copy_into(&self, dst: &mut [E]) {
         D::copy_into(self, dst);
     }
+
+    /// Copy the data of `self` into `dst`, reusing the allocation of `dst` instead of
+    /// allocating a new one like [Clone] does. `dst` keeps its id, and takes the strides
+    /// of `self` - **panics** if the *physical* number of elements differs.
+    ///
+    /// If the data of `dst` is shared with other tensors, it is still copied before being
+    /// written to.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+    /// let mut buf: Tensor<Rank2<2, 2>, f32, _> = dev.zeros();
+    /// t.clone_data_into(&mut buf);
+    /// assert_eq!(buf.array(), [[1.0, 2.0], [3.0, 4.0]]);
+    /// ```
+    pub fn clone_data_into(&self, dst: &mut Self) {
+        D::clone_data_into(self, dst);
+    }
 }
 
 /// Construct tensors filled with zeros.
@@ -225,6 +256,10 @@ pub trait OneFillStorage<E>: Storage<E> {
     fn try_fill_with_ones(&self, storage: &mut Self::Vec) -> Result<(), Self::Err>;
 }
 
+pub trait FillStorage<E>: Storage<E> {
+    fn try_fill_with(&self, storage: &mut Self::Vec, value: E) -> Result<(), Self::Err>;
+}
+
 /// Build upper & lower triangle tensors.
 pub trait TriangleTensor<E>: Storage<E> {
     /// Build a tensor containing the upper triangle part of each lowest 2D matrix
@@ -575,6 +610,14 @@ impl<E, S: ConstShape, D: TensorFromVec<E>> TensorFrom<Vec<E>, S, E> for D {
     }
 }
 
+/// Copies a row-major slice into a tensor. Returns an error instead of panicking if the
+/// length of the slice doesn't match the number of elements of `S`.
+impl<E: Copy, S: ConstShape, D: TensorFromVec<E>> TensorFrom<&[E], S, E> for D {
+    fn try_tensor(&self, src: &[E]) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_tensor_from_vec(src.to_vec(), S::default())
+    }
+}
+
 impl<E, S: Shape, D: TensorFromVec<E>> TensorFrom<(Vec<E>, S), S, E> for D {
     fn try_tensor(&self, (src, shape): (Vec<E>, S)) -> Result<Tensor<S, E, Self>, Self::Err> {
         self.try_tensor_from_vec(src, shape)
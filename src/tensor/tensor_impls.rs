@@ -101,6 +101,30 @@ impl<S: Shape, E: Unit, F: Unit, D: Storage<F> + Storage<E>> Trace<E, D>
     }
 }
 
+impl<S: Shape, E, D: Storage<E>> Tensor<S, E, D, NoneTape> {
+    /// Decides at runtime whether operations on this tensor are recorded. When
+    /// `requires_grad` is `true`, this is the same as [Trace::leaky_traced()], and
+    /// otherwise nothing is recorded and backward returns no gradients.
+    ///
+    /// The tape is an `Option<OwnedTape>`, so the result has the same type either way:
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let a = dev.tensor([1.0f32, 2.0, 3.0]);
+    /// let g = a.clone().requires_grad(true).square().sum().backward();
+    /// assert_eq!(g.get(&a).array(), [2.0, 4.0, 6.0]);
+    ///
+    /// // no gradients are computed
+    /// let _ = a.clone().requires_grad(false).square().sum().backward();
+    /// ```
+    ///
+    /// Operations that combine it with a tensor that is tracked still record a
+    /// gradient for this tensor, just like for a tensor with [NoneTape].
+    pub fn requires_grad(self, requires_grad: bool) -> Tensor<S, E, D, Option<OwnedTape<E, D>>> {
+        self.put_tape(requires_grad.then(Default::default))
+    }
+}
+
 impl<S: Shape, E, D: Storage<E>, T> Tensor<S, E, D, T> {
     /// Clone and insert a new tape of type `New` into the tensor
     pub fn retaped<New: Tape<E, D>>(&self) -> Tensor<S, E, D, New> {
@@ -118,6 +142,33 @@ impl<S: Shape, E, D: Storage<E>, T> Tensor<S, E, D, T> {
     pub fn device(&self) -> &D {
         &self.device
     }
+
+    /// Clones self without its tape and with a new [UniqueId], so the result is not
+    /// connected to the graph that computed `self`. Gradients of the result are never
+    /// added to the gradient of `self`, even if both end up on the same tape. The data
+    /// is shared with `self` until either of them is mutated.
+    ///
+    /// Use [Trace::leaky_trace()] or [Trace::trace()] on the result to start a new graph
+    /// with it as a leaf, e.g. for the output of a target network:
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let x = dev.tensor([1.0f32, 2.0]);
+    /// let y = x.leaky_trace().square();
+    /// let leaf = y.detach();
+    /// let g = leaf.leaky_trace().sum().backward();
+    /// assert_eq!(g.get(&leaf).array(), [1.0; 2]);
+    /// ```
+    pub fn detach(&self) -> Tensor<S, E, D> {
+        Tensor {
+            id: unique_id(),
+            data: self.data.clone(),
+            shape: self.shape,
+            strides: self.strides,
+            device: self.device.clone(),
+            tape: NoneTape,
+        }
+    }
 }
 
 /// Put a tape of type `T` into the tensor
@@ -224,6 +275,26 @@ impl<S: Shape, E: Dtype, D: OneFillStorage<E>, T> Tensor<S, E, D, T> {
     }
 }
 
+impl<S: Shape, E: Dtype, D: FillStorage<E>, T> Tensor<S, E, D, T> {
+    /// Sets every element of the tensor to `value`
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mut t: Tensor<Rank1<3>, f32, _> = dev.zeros();
+    /// t.fill(2.0);
+    /// assert_eq!(t.array(), [2.0; 3]);
+    /// ```
+    pub fn fill(&mut self, value: E) {
+        self.try_fill(value).unwrap()
+    }
+    /// Fallible version of [Tensor::fill]
+    pub fn try_fill(&mut self, value: E) -> Result<(), D::Err> {
+        self.device
+            .try_fill_with(Arc::make_mut(&mut self.data), value)
+    }
+}
+
 impl<S: Shape, E: Unit, D: SampleTensor<E>, T> Tensor<S, E, D, T> {
     /// Fills the tensor with random data from the distribution
     pub fn fill_with_distr<Distr: Distribution<E>>(&mut self, distr: Distr) {
@@ -0,0 +1,86 @@
+use super::*;
+use crate::{shapes::*, tensor::*};
+
+/// Reduction along multiple axes using the maximum absolute value.
+pub trait AbsMaxTo: HasErr + HasShape {
+    /// Max of absolute values reduction, i.e. the infinity norm. This is cheaper than
+    /// an L2 norm when deciding whether values need clipping.
+    ///
+    /// **Pytorch equivalent**: `t.abs().amax(Ax)`
+    ///
+    /// **NOTE** Like [MaxTo::max], this evenly distributes gradients between all equal
+    /// maximum values.
+    ///
+    /// Reducing all axes gives the infinity norm of the whole tensor:
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, -7.0, 3.0], [2.0, 5.0, -4.0]]);
+    /// let norm: f32 = t.abs_max::<Rank0, _>().array();
+    /// assert_eq!(norm, 7.0);
+    /// ```
+    ///
+    /// Reducing a single axis:
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// # let t = dev.tensor([[1.0, -7.0, 3.0], [2.0, 5.0, -4.0]]);
+    /// let r = t.abs_max::<Rank1<2>, _>(); // or `abs_max::<_, Axis<1>>()`
+    /// assert_eq!(r.array(), [7.0, 5.0]);
+    /// ```
+    fn abs_max<Dst: Shape, Ax: Axes>(self) -> Self::WithShape<Dst>
+    where
+        Self::Shape: ReduceShapeTo<Dst, Ax>,
+    {
+        self.try_abs_max().unwrap()
+    }
+    /// Fallible version of [AbsMaxTo::abs_max]
+    fn try_abs_max<Dst: Shape, Ax: Axes>(self) -> Result<Self::WithShape<Dst>, Self::Err>
+    where
+        Self::Shape: ReduceShapeTo<Dst, Ax>;
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> AbsMaxTo for Tensor<S, E, D, T> {
+    fn try_abs_max<Dst: Shape, Ax: Axes>(self) -> Result<Self::WithShape<Dst>, Self::Err>
+    where
+        Self::Shape: ReduceShapeTo<Dst, Ax>,
+    {
+        self.try_abs()?.try_max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_abs_max_all() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, -7.0, 3.0], [2.0, 5.0, -4.0]])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().abs_max::<Rank0, _>();
+        assert_close_to_literal!(r, 7.0);
+        let g = r.backward();
+        assert_close_to_literal!(g.get(&t), [[0.0, -1.0, 0.0], [0.0; 3]]);
+    }
+
+    #[test]
+    fn test_abs_max_axis() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, -7.0, 3.0], [2.0, 5.0, -4.0]])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().abs_max::<_, Axis<1>>();
+        assert_close_to_literal!(r, [7.0, 5.0]);
+        let r0 = t.clone().abs_max::<Rank1<3>, _>();
+        assert_close_to_literal!(r0, [2.0, 7.0, 4.0]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [[0.0, -1.0, 0.0], [0.0, 1.0, 0.0]]);
+
+        let t: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let r = t.clone().abs_max::<Rank1<2>, _>();
+        assert_close_to_tensor!(r, t.abs().max::<Rank1<2>, _>());
+    }
+}
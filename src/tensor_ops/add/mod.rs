@@ -3,7 +3,7 @@ mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
 
-use super::ops::*;
+use super::{axpy::AxpyKernel, ops::*};
 use crate::{
     shapes::*,
     tensor::{HasErr, Merge, Storage, Tape, Tensor},
@@ -97,6 +97,47 @@ where
     }
 }
 
+impl<S: Shape, E: Dtype, D: BinaryKernel<BinaryAddKernelOp, E>> Tensor<S, E, D> {
+    /// Computes `self + rhs` and writes the result into `out`, re-using its allocation
+    /// instead of allocating a new tensor. Nothing is recorded on a tape. See [add].
+    ///
+    /// `out` must be contiguous and have the same shape as `self` and `rhs`.
+    pub fn add_into(&self, rhs: &Self, out: &mut Self) {
+        self.try_add_into(rhs, out).unwrap()
+    }
+
+    /// Fallible version of [Tensor::add_into]
+    pub fn try_add_into(&self, rhs: &Self, out: &mut Self) -> Result<(), D::Err> {
+        try_binary_op_into(BinaryAddKernelOp, self, rhs, out)
+    }
+}
+
+impl<S: Shape, E: Dtype, D> std::ops::AddAssign for Tensor<S, E, D>
+where
+    D: AxpyKernel<E> + BinaryKernel<BinaryAddKernelOp, E>,
+{
+    /// Adds `rhs` to `self` in place with [Tensor::axpy], without a tape. If the strides of
+    /// `self` and `rhs` are different (e.g. `rhs` is broadcasted), a new tensor is allocated.
+    fn add_assign(&mut self, rhs: Self) {
+        *self += &rhs;
+    }
+}
+
+impl<S: Shape, E: Dtype, D> std::ops::AddAssign<&Tensor<S, E, D>> for Tensor<S, E, D>
+where
+    D: AxpyKernel<E> + BinaryKernel<BinaryAddKernelOp, E>,
+{
+    /// Adds `rhs` to `self` in place with [Tensor::axpy], without a tape. If the strides of
+    /// `self` and `rhs` are different (e.g. `rhs` is broadcasted), a new tensor is allocated.
+    fn add_assign(&mut self, rhs: &Tensor<S, E, D>) {
+        if self.strides == rhs.strides {
+            self.axpy(1.0, rhs, 1.0);
+        } else {
+            *self = self.clone() + rhs.clone();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
@@ -219,4 +260,60 @@ mod tests {
         let g = r.exp().sum().backward();
         assert_close_to_literal!(g.get(&x), [[1.6487212; 2]; 3]);
     }
+
+    #[test]
+    fn test_add_into() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let mut out: Tensor<Rank2<2, 3>, TestDtype, _> = dev.zeros();
+        let ptr = std::sync::Arc::as_ptr(&out.data);
+
+        a.add_into(&b, &mut out);
+        assert_eq!(out.array(), (a.clone() + b.clone()).array());
+
+        // broadcasted inputs
+        let c: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let c = c.broadcast::<Rank2<2, 3>, _>();
+        b.add_into(&c, &mut out);
+        assert_eq!(out.array(), (b + c).array());
+
+        assert_eq!(std::sync::Arc::as_ptr(&out.data), ptr);
+    }
+
+    #[test]
+    fn test_add_assign_accumulates() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let c: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+
+        let mut acc: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.zeros();
+        acc += a.clone();
+        acc += &b;
+        acc += c.clone();
+        assert_close_to_tensor!(acc, a.clone() + b.clone() + c.clone());
+    }
+
+    #[test]
+    fn test_add_assign_in_place() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+
+        let mut acc = a.clone() + b.clone();
+        let ptr = std::sync::Arc::as_ptr(&acc.data);
+        acc += &a;
+        assert_eq!(std::sync::Arc::as_ptr(&acc.data), ptr);
+        assert_close_to_tensor!(acc, a.clone() + b.clone() + a.clone());
+
+        // a broadcasted rhs has different strides, so a new tensor is allocated
+        let c: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let c = c.broadcast::<Rank2<2, 3>, _>();
+        let mut acc = a.clone();
+        acc += &c;
+        assert_close_to_tensor!(acc, a.clone() + c.clone());
+        acc += c.clone();
+        assert_close_to_tensor!(acc, a + c.clone() + c);
+    }
 }
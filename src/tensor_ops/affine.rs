@@ -0,0 +1,105 @@
+use crate::{
+    shapes::{Axes, BroadcastShapeTo, Dtype, Shape},
+    tensor::{HasErr, Tape, Tensor},
+};
+
+use super::{BroadcastTo, Device, TryAdd, TryMul};
+
+/// Scales and shifts `x` by per-feature `gamma` and `beta`: `x * gamma + beta`.
+/// `gamma` and `beta` are broadcast along the axes of `x` they don't have, so a
+/// `(C,)` gamma is applied to every row of a `(B, C)` or `(B, S, C)` input.
+///
+/// This is the affine transform at the end of normalization layers like
+/// [crate::nn::modules::LayerNorm1D]. Gradients flow to `x`, `gamma`, and `beta`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let x = dev.tensor([[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+/// let gamma = dev.tensor([2.0, -1.0, 0.0]);
+/// let beta = dev.tensor([0.5, 0.0, 1.0]);
+/// let r = affine(x, &gamma, &beta);
+/// assert_eq!(r.array(), [[2.5, -2.0, 1.0], [8.5, -5.0, 1.0]]);
+/// ```
+///
+/// If the axis `gamma` is broadcast along is ambiguous (e.g. a square input), specify it:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let x: Tensor<Rank2<3, 3>, f32, _> = dev.ones();
+/// let gamma = dev.tensor([1.0, 2.0, 3.0]);
+/// let r = x.affine::<_, Axis<0>>(&gamma, &dev.zeros());
+/// assert_eq!(r.array(), [[1.0, 2.0, 3.0]; 3]);
+/// ```
+pub fn affine<S: Shape, P, Ax: Axes, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    x: Tensor<S, E, D, T>,
+    gamma: &Tensor<P, E, D>,
+    beta: &Tensor<P, E, D>,
+) -> Tensor<S, E, D, T>
+where
+    P: Shape + BroadcastShapeTo<S, Ax>,
+{
+    x.affine(gamma, beta)
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [affine]
+    pub fn affine<P, Ax: Axes>(self, gamma: &Tensor<P, E, D>, beta: &Tensor<P, E, D>) -> Self
+    where
+        P: Shape + BroadcastShapeTo<S, Ax>,
+    {
+        self.try_affine(gamma, beta).unwrap()
+    }
+
+    /// See [affine]
+    pub fn try_affine<P, Ax: Axes>(
+        self,
+        gamma: &Tensor<P, E, D>,
+        beta: &Tensor<P, E, D>,
+    ) -> Result<Self, <Self as HasErr>::Err>
+    where
+        P: Shape + BroadcastShapeTo<S, Ax>,
+    {
+        let shape = self.shape;
+        self.try_mul(gamma.retaped::<T>().try_broadcast_like(&shape)?)?
+            .try_add(beta.retaped::<T>().try_broadcast_like(&shape)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_affine_batched() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[1.0, -2.0, 0.5], [3.0, 0.0, -1.0]])
+            .to_dtype::<TestDtype>();
+        let gamma = dev.tensor([2.0, 0.5, -1.0]).to_dtype::<TestDtype>();
+        let beta = dev.tensor([0.1, 0.2, 0.3]).to_dtype::<TestDtype>();
+
+        let r = x.leaky_trace().affine(&gamma, &beta);
+        assert_close_to_literal!(r, [[2.1, -0.8, -0.2], [6.1, 0.2, 1.3]]);
+
+        // weight each output so every gradient depends on its position
+        let w = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let g = (r * w).sum().backward();
+        assert_close_to_literal!(g.get(&x), [[2.0, 1.0, -3.0], [8.0, 2.5, -6.0]]);
+        assert_close_to_literal!(g.get(&gamma), [13.0, -4.0, -4.5]);
+        assert_close_to_literal!(g.get(&beta), [5.0, 7.0, 9.0]);
+    }
+
+    #[test]
+    fn test_affine_3d() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let gamma: Tensor<Rank1<4>, TestDtype, _> = dev.sample_normal();
+        let beta: Tensor<Rank1<4>, TestDtype, _> = dev.sample_normal();
+        let r = affine(x.clone(), &gamma, &beta);
+        let expected = x * gamma.broadcast() + beta.broadcast();
+        assert_close_to_tensor!(r, expected);
+    }
+}
@@ -0,0 +1,139 @@
+#include "cuda_utils.cuh"
+
+struct AttentionLogitsOp {
+    // batch, heads, s1, s2, head_dim
+    size_t dims[5];
+    size_t q_strides[4];
+    size_t k_strides[4];
+    double scale;
+    bool shift;
+};
+
+// One thread per row of the output. ACC is the type the dot products are accumulated
+// in, which is at least float.
+template<typename T, typename ACC>
+__device__ void attention_logits_fwd(
+    const AttentionLogitsOp op,
+    const T *q,
+    const T *k,
+    T *out
+) {
+    const size_t heads = op.dims[1];
+    const size_t s1 = op.dims[2];
+    const size_t s2 = op.dims[3];
+    const size_t head_dim = op.dims[4];
+    const size_t num_rows = op.dims[0] * heads * s1;
+    const ACC scale = op.scale;
+
+    for (size_t row = blockIdx.x * blockDim.x + threadIdx.x; row < num_rows; row += blockDim.x * gridDim.x) {
+        const size_t i = row % s1;
+        const size_t h = (row / s1) % heads;
+        const size_t b = row / (s1 * heads);
+        const T *q_row = q + b * op.q_strides[0] + h * op.q_strides[1] + i * op.q_strides[2];
+        const T *k_mat = k + b * op.k_strides[0] + h * op.k_strides[1];
+        T *y = out + row * s2;
+
+        // without the shift, the first pass that finds the max is skipped
+        ACC max = op.shift ? -INFINITY : 0.0;
+        for (size_t pass = op.shift ? 0 : 1; pass < 2; pass++) {
+            for (size_t j = 0; j < s2; j++) {
+                ACC dot = 0.0;
+                for (size_t d = 0; d < head_dim; d++) {
+                    dot += (ACC)q_row[d * op.q_strides[3]] * (ACC)k_mat[d * op.k_strides[2] + j * op.k_strides[3]];
+                }
+                dot *= scale;
+                if (pass == 0) {
+                    max = maxg(max, dot);
+                } else {
+                    y[j] = dot - max;
+                }
+            }
+        }
+    }
+}
+
+// One thread per element of grad_q: grad_q += scale * grad_out @ k^T
+template<typename T, typename ACC>
+__device__ void attention_logits_bwd_q(
+    const AttentionLogitsOp op,
+    const T *k,
+    T *grad_q,
+    const T *grad_out
+) {
+    const size_t heads = op.dims[1];
+    const size_t s1 = op.dims[2];
+    const size_t s2 = op.dims[3];
+    const size_t head_dim = op.dims[4];
+    const size_t numel = op.dims[0] * heads * s1 * head_dim;
+
+    for (size_t idx = blockIdx.x * blockDim.x + threadIdx.x; idx < numel; idx += blockDim.x * gridDim.x) {
+        const size_t d = idx % head_dim;
+        const size_t i = (idx / head_dim) % s1;
+        const size_t h = (idx / (head_dim * s1)) % heads;
+        const size_t b = idx / (head_dim * s1 * heads);
+        const T *go = grad_out + ((b * heads + h) * s1 + i) * s2;
+        const T *k_row = k + b * op.k_strides[0] + h * op.k_strides[1] + d * op.k_strides[2];
+
+        ACC acc = 0.0;
+        for (size_t j = 0; j < s2; j++) {
+            acc += (ACC)go[j] * (ACC)k_row[j * op.k_strides[3]];
+        }
+        const size_t i_q = b * op.q_strides[0] + h * op.q_strides[1] + i * op.q_strides[2] + d * op.q_strides[3];
+        grad_q[i_q] += (T)(acc * (ACC)op.scale);
+    }
+}
+
+// One thread per element of grad_k: grad_k += scale * q^T @ grad_out
+template<typename T, typename ACC>
+__device__ void attention_logits_bwd_k(
+    const AttentionLogitsOp op,
+    const T *q,
+    T *grad_k,
+    const T *grad_out
+) {
+    const size_t heads = op.dims[1];
+    const size_t s1 = op.dims[2];
+    const size_t s2 = op.dims[3];
+    const size_t head_dim = op.dims[4];
+    const size_t numel = op.dims[0] * heads * head_dim * s2;
+
+    for (size_t idx = blockIdx.x * blockDim.x + threadIdx.x; idx < numel; idx += blockDim.x * gridDim.x) {
+        const size_t j = idx % s2;
+        const size_t d = (idx / s2) % head_dim;
+        const size_t h = (idx / (s2 * head_dim)) % heads;
+        const size_t b = idx / (s2 * head_dim * heads);
+        const T *go = grad_out + (b * heads + h) * s1 * s2 + j;
+        const T *q_col = q + b * op.q_strides[0] + h * op.q_strides[1] + d * op.q_strides[3];
+
+        ACC acc = 0.0;
+        for (size_t i = 0; i < s1; i++) {
+            acc += (ACC)go[i * s2] * (ACC)q_col[i * op.q_strides[2]];
+        }
+        const size_t i_k = b * op.k_strides[0] + h * op.k_strides[1] + d * op.k_strides[2] + j * op.k_strides[3];
+        grad_k[i_k] += (T)(acc * (ACC)op.scale);
+    }
+}
+
+#define ATTENTION_LOGITS(TY, ACC, FWD, BWD_Q, BWD_K) \
+extern "C" __global__ void FWD( \
+    const AttentionLogitsOp op, \
+    const TY *q, \
+    const TY *k, \
+    TY *out \
+) { attention_logits_fwd<TY, ACC>(op, q, k, out); } \
+extern "C" __global__ void BWD_Q( \
+    const AttentionLogitsOp op, \
+    const TY *k, \
+    TY *grad_q, \
+    const TY *grad_out \
+) { attention_logits_bwd_q<TY, ACC>(op, k, grad_q, grad_out); } \
+extern "C" __global__ void BWD_K( \
+    const AttentionLogitsOp op, \
+    const TY *q, \
+    TY *grad_k, \
+    const TY *grad_out \
+) { attention_logits_bwd_k<TY, ACC>(op, q, grad_k, grad_out); }
+
+ATTENTION_LOGITS(__half, float, attention_logits_fwd_f16, attention_logits_bwd_q_f16, attention_logits_bwd_k_f16);
+ATTENTION_LOGITS(float, float, attention_logits_fwd_f32, attention_logits_bwd_q_f32, attention_logits_bwd_k_f32);
+ATTENTION_LOGITS(double, double, attention_logits_fwd_f64, attention_logits_bwd_q_f64, attention_logits_bwd_k_f64);
@@ -0,0 +1,121 @@
+use crate::{
+    shapes::{Dim, Dtype, Shape},
+    tensor::*,
+};
+
+use num_traits::Float;
+use std::{sync::Arc, vec::Vec};
+
+use super::AttentionLogitsOp;
+
+/// Whether `q @ k` can overflow the dtype, see [super::AttentionLogitsKernel::UPCAST].
+trait Upcast {
+    const UPCAST: bool;
+}
+#[cfg(feature = "f16")]
+impl Upcast for half::f16 {
+    const UPCAST: bool = true;
+}
+impl Upcast for f32 {
+    const UPCAST: bool = false;
+}
+impl Upcast for f64 {
+    const UPCAST: bool = false;
+}
+
+impl<E: Dtype + Float + Upcast> super::AttentionLogitsKernel<E> for Cpu {
+    const UPCAST: bool = E::UPCAST;
+
+    fn forward<B: Dim, H: Dim, S1: Dim, K: Dim, S2: Dim>(
+        &self,
+        op: AttentionLogitsOp,
+        q: &Tensor<(B, H, S1, K), E, Self>,
+        k: &Tensor<(B, H, K, S2), E, Self>,
+    ) -> Result<Tensor<(B, H, S1, S2), E, Self>, Self::Err> {
+        let [batch, heads, s1, s2, head_dim] = op.dims;
+        let [qs0, qs1, qs2, qs3] = op.q_strides;
+        let [ks0, ks1, ks2, ks3] = op.k_strides;
+        let shape = (q.shape.0, q.shape.1, q.shape.2, k.shape.3);
+        let mut out = self.try_alloc_zeros::<E>(shape.num_elements())?;
+        let mut row: Vec<f64> = std::vec![0.0; s2];
+        let mut o = 0;
+        for b in 0..batch {
+            for h in 0..heads {
+                for i in 0..s1 {
+                    let q_row = b * qs0 + h * qs1 + i * qs2;
+                    for (j, logit) in row.iter_mut().enumerate() {
+                        let k_col = b * ks0 + h * ks1 + j * ks3;
+                        let mut dot = 0.0;
+                        for d in 0..head_dim {
+                            let q_i = q.data[q_row + d * qs3].to_f64().unwrap();
+                            let k_i = k.data[k_col + d * ks2].to_f64().unwrap();
+                            dot += q_i * k_i;
+                        }
+                        *logit = op.scale * dot;
+                    }
+                    let max = if op.shift {
+                        row.iter().fold(f64::NEG_INFINITY, |m, &l| m.max(l))
+                    } else {
+                        0.0
+                    };
+                    for &logit in row.iter() {
+                        out[o] = E::from_f64(logit - max).unwrap();
+                        o += 1;
+                    }
+                }
+            }
+        }
+        Ok(Tensor {
+            id: unique_id(),
+            data: Arc::new(out),
+            shape,
+            strides: shape.strides(),
+            device: self.clone(),
+            tape: Default::default(),
+        })
+    }
+
+    fn backward<B: Dim, H: Dim, S1: Dim, K: Dim, S2: Dim>(
+        &self,
+        op: AttentionLogitsOp,
+        q: &Tensor<(B, H, S1, K), E, Self>,
+        grad_q: &mut Self::Vec,
+        k: &Tensor<(B, H, K, S2), E, Self>,
+        grad_k: &mut Self::Vec,
+        grad_out: &Self::Vec,
+    ) -> Result<(), Self::Err> {
+        let [batch, heads, s1, s2, head_dim] = op.dims;
+        let [qs0, qs1, qs2, qs3] = op.q_strides;
+        let [ks0, ks1, ks2, ks3] = op.k_strides;
+        for b in 0..batch {
+            for h in 0..heads {
+                let go = |i: usize, j: usize| {
+                    grad_out[((b * heads + h) * s1 + i) * s2 + j]
+                        .to_f64()
+                        .unwrap()
+                };
+                for d in 0..head_dim {
+                    for i in 0..s1 {
+                        let mut acc = 0.0;
+                        for j in 0..s2 {
+                            let k_i = k.data[b * ks0 + h * ks1 + d * ks2 + j * ks3];
+                            acc += go(i, j) * k_i.to_f64().unwrap();
+                        }
+                        let g = &mut grad_q[b * qs0 + h * qs1 + i * qs2 + d * qs3];
+                        *g += E::from_f64(op.scale * acc).unwrap();
+                    }
+                    for j in 0..s2 {
+                        let mut acc = 0.0;
+                        for i in 0..s1 {
+                            let q_i = q.data[b * qs0 + h * qs1 + i * qs2 + d * qs3];
+                            acc += go(i, j) * q_i.to_f64().unwrap();
+                        }
+                        let g = &mut grad_k[b * ks0 + h * ks1 + d * ks2 + j * ks3];
+                        *g += E::from_f64(op.scale * acc).unwrap();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
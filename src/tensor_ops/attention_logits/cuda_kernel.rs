@@ -0,0 +1,90 @@
+use crate::{
+    shapes::{Dim, Dtype, Shape},
+    tensor::*,
+};
+
+use cudarc::driver::{DeviceRepr, LaunchAsync};
+
+unsafe impl DeviceRepr for super::AttentionLogitsOp {}
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/attention_logits.ptx"));
+
+trait HasCudaKernel<E> {
+    const UPCAST: bool;
+    const FNS: &'static [&'static str];
+}
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const UPCAST: bool = true;
+    const FNS: &'static [&'static str] = &[
+        "attention_logits_fwd_f16",
+        "attention_logits_bwd_q_f16",
+        "attention_logits_bwd_k_f16",
+    ];
+}
+impl HasCudaKernel<f32> for Cuda {
+    const UPCAST: bool = false;
+    const FNS: &'static [&'static str] = &[
+        "attention_logits_fwd_f32",
+        "attention_logits_bwd_q_f32",
+        "attention_logits_bwd_k_f32",
+    ];
+}
+impl HasCudaKernel<f64> for Cuda {
+    const UPCAST: bool = false;
+    const FNS: &'static [&'static str] = &[
+        "attention_logits_fwd_f64",
+        "attention_logits_bwd_q_f64",
+        "attention_logits_bwd_k_f64",
+    ];
+}
+
+impl<E: Dtype> super::AttentionLogitsKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    const UPCAST: bool = <Self as HasCudaKernel<E>>::UPCAST;
+
+    fn forward<B: Dim, H: Dim, S1: Dim, K: Dim, S2: Dim>(
+        &self,
+        op: super::AttentionLogitsOp,
+        q: &Tensor<(B, H, S1, K), E, Self>,
+        k: &Tensor<(B, H, K, S2), E, Self>,
+    ) -> Result<Tensor<(B, H, S1, S2), E, Self>, Self::Err> {
+        if !self.dev.has_func(Self::FNS[0], Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::FNS[0], Self::FNS)?;
+        }
+
+        let shape = (q.shape.0, q.shape.1, q.shape.2, k.shape.3);
+        let mut out = unsafe { self.alloc_empty::<E>(shape.num_elements()) }?;
+        let num_rows = op.dims[0] * op.dims[1] * op.dims[2];
+        let fwd = self.dev.get_func(Self::FNS[0], Self::FNS[0]).unwrap();
+        let cfg = launch_cfg::<128>(num_rows as u32);
+        let params = (op, q.data.as_ref(), k.data.as_ref(), &mut out);
+        unsafe { fwd.launch(cfg, params) }?;
+        Ok(self.build_tensor(shape, shape.strides(), out))
+    }
+
+    fn backward<B: Dim, H: Dim, S1: Dim, K: Dim, S2: Dim>(
+        &self,
+        op: super::AttentionLogitsOp,
+        q: &Tensor<(B, H, S1, K), E, Self>,
+        grad_q: &mut Self::Vec,
+        k: &Tensor<(B, H, K, S2), E, Self>,
+        grad_k: &mut Self::Vec,
+        grad_out: &Self::Vec,
+    ) -> Result<(), Self::Err> {
+        let [batch, heads, s1, s2, head_dim] = op.dims;
+
+        let bwd_q = self.dev.get_func(Self::FNS[0], Self::FNS[1]).unwrap();
+        let cfg = launch_cfg::<128>((batch * heads * s1 * head_dim) as u32);
+        let params = (op, k.data.as_ref(), grad_q, grad_out);
+        unsafe { bwd_q.launch(cfg, params) }?;
+
+        let bwd_k = self.dev.get_func(Self::FNS[0], Self::FNS[2]).unwrap();
+        let cfg = launch_cfg::<128>((batch * heads * head_dim * s2) as u32);
+        let params = (op, q.data.as_ref(), grad_k, grad_out);
+        unsafe { bwd_k.launch(cfg, params) }?;
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    shapes::*,
+    tensor::{Merge, PutTape, SplitTape, Storage, Tape, Tensor},
+};
+
+use super::{Device, TryMatMul, TryMul};
+
+mod cpu_kernel;
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+/// Sizes and strides of the batched `q @ k` product, see [try_attention_logits].
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct AttentionLogitsOp {
+    /// `[batch, heads, s1, s2, head_dim]`
+    pub dims: [usize; 5],
+    pub q_strides: [usize; 4],
+    pub k_strides: [usize; 4],
+    pub scale: f64,
+    /// Whether each row of the output is shifted by its max.
+    pub shift: bool,
+}
+
+impl AttentionLogitsOp {
+    fn new<B: Dim, H: Dim, S1: Dim, K: Dim, S2: Dim, E, D: Storage<E>>(
+        q: &Tensor<(B, H, S1, K), E, D>,
+        k: &Tensor<(B, H, K, S2), E, D>,
+        scale: f64,
+        shift: bool,
+    ) -> Self {
+        let (b, h, s1, d) = q.shape;
+        Self {
+            dims: [b.size(), h.size(), s1.size(), k.shape.3.size(), d.size()],
+            q_strides: q.strides,
+            k_strides: k.strides,
+            scale,
+            shift,
+        }
+    }
+}
+
+pub trait AttentionLogitsKernel<E: Dtype>: Storage<E> {
+    /// Whether `q @ k` can overflow `E`, so attention should compute its logits with
+    /// this kernel instead of a matmul in `E`. See [try_attention_scores].
+    const UPCAST: bool;
+
+    /// Computes `scale * q @ k`, optionally with each row shifted by its max, accumulating
+    /// in at least f32. The output has standard strides.
+    #[allow(clippy::type_complexity)]
+    fn forward<B: Dim, H: Dim, S1: Dim, K: Dim, S2: Dim>(
+        &self,
+        op: AttentionLogitsOp,
+        q: &Tensor<(B, H, S1, K), E, Self>,
+        k: &Tensor<(B, H, K, S2), E, Self>,
+    ) -> Result<Tensor<(B, H, S1, S2), E, Self>, Self::Err>;
+
+    fn backward<B: Dim, H: Dim, S1: Dim, K: Dim, S2: Dim>(
+        &self,
+        op: AttentionLogitsOp,
+        q: &Tensor<(B, H, S1, K), E, Self>,
+        grad_q: &mut Self::Vec,
+        k: &Tensor<(B, H, K, S2), E, Self>,
+        grad_k: &mut Self::Vec,
+        grad_out: &Self::Vec,
+    ) -> Result<(), Self::Err>;
+}
+
+/// Attention logits `scale * q @ k` computed in at least f32 precision and converted back
+/// to `E` at the end. With `shift`, the max of each row is subtracted before converting.
+///
+/// For f16 the product of large queries and keys can overflow before it is scaled. Since
+/// softmax is invariant to shifting a row by a constant, the shifted output can be used in
+/// place of the logits, and only very negative entries (which have softmax ~0) lose
+/// precision. The row max is treated as a constant during backprop, like in
+/// [super::softmax()].
+#[allow(clippy::type_complexity)]
+pub(crate) fn try_attention_logits<B: Dim, H: Dim, S1: Dim, K: Dim, S2: Dim, E, D, T, R>(
+    q: Tensor<(B, H, S1, K), E, D, T>,
+    k: Tensor<(B, H, K, S2), E, D, R>,
+    scale: f64,
+    shift: bool,
+) -> Result<Tensor<(B, H, S1, S2), E, D, T>, D::Err>
+where
+    E: Dtype,
+    D: AttentionLogitsKernel<E>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    assert_eq!(q.shape.0, k.shape.0);
+    assert_eq!(q.shape.1, k.shape.1);
+    assert_eq!(q.shape.3, k.shape.2);
+    let (q, q_tape) = q.split_tape();
+    let (k, k_tape) = k.split_tape();
+    let mut tape = q_tape.merge(k_tape);
+    let op = AttentionLogitsOp::new(&q, &k, scale, shift);
+    let out = q.device.forward(op, &q, &k)?;
+    let q_ghost = q.ghost();
+    let k_ghost = k.ghost();
+    let out_ghost = out.ghost();
+    tape.add_backward_op(move |grads| {
+        grads.try_alloc_for(&q_ghost)?;
+        grads.try_alloc_for(&k_ghost)?;
+        grads.try_alloc_for(&out_ghost)?;
+        let (grad_q, grad_k, grad_out) = grads.muts_and_ref(&q_ghost, &k_ghost, &out_ghost);
+        q.device.backward(op, &q, grad_q, &k, grad_k, grad_out)
+    });
+    Ok(out.put_tape(tape))
+}
+
+/// The logits `scale * q @ k` that attention applies softmax to. Devices that set
+/// [AttentionLogitsKernel::UPCAST] for `E` compute them with [try_attention_logits], the
+/// others with a matmul in `E`. `shift` is passed on to [try_attention_logits], so the
+/// result is only equal to the logits up to a per row constant when it is set.
+#[allow(clippy::type_complexity)]
+pub(crate) fn try_attention_scores<B: Dim, H: Dim, S1: Dim, K: Dim, S2: Dim, E, D, T>(
+    q: Tensor<(B, H, S1, K), E, D, T>,
+    k: Tensor<(B, H, K, S2), E, D, T>,
+    scale: f64,
+    shift: bool,
+) -> Result<Tensor<(B, H, S1, S2), E, D, T>, D::Err>
+where
+    E: Dtype,
+    D: Device<E>,
+    T: Tape<E, D> + Merge<T>,
+{
+    if <D as AttentionLogitsKernel<E>>::UPCAST {
+        try_attention_logits(q, k, scale, shift)
+    } else if scale == 1.0 {
+        q.try_matmul(k)
+    } else {
+        q.try_matmul(k)?.try_mul(E::from_f64(scale).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_attention_logits_matches_matmul() {
+        let dev: TestDevice = Default::default();
+        let q: Tensor<Rank4<2, 3, 4, 5>, TestDtype, _> = dev.sample_normal();
+        let k: Tensor<Rank4<2, 3, 6, 5>, TestDtype, _> = dev.sample_normal();
+        let k_t = k.clone().permute::<Rank4<2, 3, 5, 6>, _>();
+
+        let logits = try_attention_logits(q.leaky_trace(), k_t.clone(), 0.5, true).unwrap();
+        let truth = q.leaky_trace().matmul(k_t) * 0.5;
+        let max = truth.retaped::<NoneTape>().max::<_, Axis<3>>();
+        assert_close_to_tensor!(logits, truth.retaped::<NoneTape>() - max.broadcast());
+
+        let w: Tensor<Rank4<2, 3, 4, 6>, TestDtype, _> = dev.sample_normal();
+        let g = (logits.softmax::<Axis<3>>() * w.clone()).sum().backward();
+        let g_truth = (truth.softmax::<Axis<3>>() * w).sum().backward();
+        assert_close_to_tensor!(g.get(&q), g_truth.get(&q));
+        assert_close_to_tensor!(g.get(&k), g_truth.get(&k));
+    }
+}
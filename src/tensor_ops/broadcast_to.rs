@@ -1,5 +1,7 @@
 use crate::{shapes::*, tensor::*};
 
+use super::{Device, ReshapeTo};
+
 /// Broadcast self into a new shape.
 ///
 /// **pytorch equivalent** `torch.broadcast_to`.
@@ -76,6 +78,35 @@ impl<S: Shape, E, D: Storage<E>, T: Tape<E, D>> BroadcastTo for Tensor<S, E, D,
     }
 }
 
+impl<N: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(Const<1>, N), E, D, T> {
+    /// Expands a single row into `m` identical rows, so `(1, N)` becomes `(M, N)`. Like
+    /// [BroadcastTo::broadcast], no data is copied, and the gradient of every row is summed
+    /// back into the original row.
+    ///
+    /// **pytorch equivalent** `t.expand(m, -1)`.
+    ///
+    /// A `(N, )` tensor can be broadcast directly with [BroadcastTo::broadcast_like].
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let a: Tensor<Rank2<1, 3>, f32, _> = dev.tensor([[1.0, 2.0, 3.0]]);
+    /// let b: Tensor<Rank2<2, 3>, f32, _> = a.expand(Const::<2>);
+    /// assert_eq!(b.array(), [[1.0, 2.0, 3.0], [1.0, 2.0, 3.0]]);
+    /// ```
+    pub fn expand<M: Dim>(self, m: M) -> Tensor<(M, N), E, D, T> {
+        self.try_expand(m).unwrap()
+    }
+
+    /// Fallible version of [Tensor::expand]
+    #[allow(clippy::type_complexity)]
+    pub fn try_expand<M: Dim>(self, m: M) -> Result<Tensor<(M, N), E, D, T>, D::Err> {
+        let n = self.shape.1;
+        self.try_reshape_like(&(n,))?
+            .try_broadcast_like::<_, Axis<0>>(&(m, n))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,4 +202,50 @@ mod tests {
             .backward();
         assert_close_to_tensor!(g.get(&a), a.exp() / 3.0);
     }
+
+    #[test]
+    fn test_expand_row() {
+        let dev: TestDevice = Default::default();
+        let a = dev.tensor([[1.0, 2.0, 3.0]]).to_dtype::<TestDtype>();
+        let r: Tensor<Rank2<4, 3>, TestDtype, _, _> = a.leaky_trace().expand(Const);
+        assert_close_to_literal!(r, [[1.0, 2.0, 3.0]; 4]);
+
+        // the gradient of each row is summed back into the single row
+        let w = dev
+            .tensor([
+                [1.0, 0.0, -1.0],
+                [2.0, 0.0, -1.0],
+                [3.0, 1.0, -1.0],
+                [4.0, 1.0, -1.0],
+            ])
+            .to_dtype::<TestDtype>();
+        let g = (r * w).sum().backward();
+        assert_close_to_literal!(g.get(&a), [[10.0, 2.0, -4.0]]);
+
+        let r = a.leaky_trace().expand(5);
+        assert_eq!(r.shape(), &(5, Const::<3>));
+    }
+
+    #[test]
+    fn test_broadcast_first_axis_sub_and_mul() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let b = dev.tensor([1.0, 1.0, 2.0]).to_dtype::<TestDtype>();
+
+        // subtracting a per feature vector from every row of a batch
+        let r = x.leaky_trace() - b.leaky_trace().broadcast::<Rank2<2, 3>, _>();
+        assert_close_to_literal!(r, [[0.0, 1.0, 1.0], [3.0, 4.0, 4.0]]);
+        let g = r.square().sum().backward();
+        assert_close_to_literal!(g.get(&x), [[0.0, 2.0, 2.0], [6.0, 8.0, 8.0]]);
+        assert_close_to_literal!(g.get(&b), [-6.0, -10.0, -10.0]);
+
+        // multiplying, the rhs gradient is the lhs summed over the broadcast axis
+        let r = x.leaky_trace() * b.leaky_trace().broadcast::<Rank2<2, 3>, _>();
+        assert_close_to_literal!(r, [[1.0, 2.0, 6.0], [4.0, 5.0, 12.0]]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&x), [[1.0, 1.0, 2.0], [1.0, 1.0, 2.0]]);
+        assert_close_to_literal!(g.get(&b), [5.0, 7.0, 9.0]);
+    }
 }
@@ -8,6 +8,8 @@ use crate::{
     tensor::{HasErr, Merge, PutTape, SplitTape, Storage, Tape, Tensor},
 };
 
+use super::{Device, TryMul};
+
 pub trait ChooseKernel<E: Dtype>: Storage<E> + Storage<bool> {
     fn forward<S: Shape>(
         &self,
@@ -92,6 +94,47 @@ impl<
     }
 }
 
+/// Replaces the elements of `t` where `mask` is true with `value`, and keeps the rest.
+/// Equivalent to `torch.masked_fill` from pytorch.
+///
+/// The gradient is passed through at the positions that are kept, and is zero at the
+/// positions that are filled. Filling with `f64::NEG_INFINITY` before a softmax gives
+/// those positions exactly zero probability.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank1<3>, f32, _> = dev.tensor([1.0, 2.0, 3.0]);
+/// let mask = dev.tensor([false, true, false]);
+/// let r = t.masked_fill(&mask, -1.0);
+/// assert_eq!(r.array(), [1.0, -1.0, 3.0]);
+/// ```
+pub fn masked_fill<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    mask: &Tensor<S, bool, D>,
+    value: impl Into<f64>,
+) -> Tensor<S, E, D, T> {
+    t.masked_fill(mask, value)
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [masked_fill]
+    pub fn masked_fill(self, mask: &Tensor<S, bool, D>, value: impl Into<f64>) -> Self {
+        self.try_masked_fill(mask, value).unwrap()
+    }
+
+    /// See [masked_fill]
+    pub fn try_masked_fill(
+        self,
+        mask: &Tensor<S, bool, D>,
+        value: impl Into<f64>,
+    ) -> Result<Self, D::Err> {
+        let fill: Tensor<S, E, D> = self.device.try_ones_like(&self.shape)?;
+        let fill = fill.try_mul(E::from_f64(value.into()).unwrap())?;
+        (!mask).try_choose(self, fill)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,4 +201,42 @@ mod tests {
             [[b_array[0][0].exp(), 0.0], [0.0, b_array[1][1].exp()]]
         );
     }
+
+    #[test]
+    fn test_masked_fill() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let mask = dev.tensor([[true, false, false], [false, false, true]]);
+        let r = t.leaky_trace().masked_fill(&mask, 0.5);
+        assert_eq!(r.array(), [[0.5, 2.0, 3.0], [4.0, 5.0, 0.5]]);
+        let g = r.exp().sum().backward();
+        let e = t.clone().exp().array();
+        assert_eq!(
+            g.get(&t).array(),
+            [[0.0, e[0][1], e[0][2]], [e[1][0], e[1][1], 0.0]]
+        );
+    }
+
+    #[test]
+    fn test_masked_fill_neg_inf_softmax() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<4>, f32, _> = dev.tensor([1.0, 2.0, 3.0, 4.0]);
+        let mask = dev.tensor([false, true, false, true]);
+        let probs = t
+            .leaky_trace()
+            .masked_fill(&mask, f64::NEG_INFINITY)
+            .softmax::<Axis<0>>();
+        let p = probs.array();
+        assert_eq!(p[1], 0.0);
+        assert_eq!(p[3], 0.0);
+        assert!((p[0] + p[2] - 1.0).abs() < 1e-6);
+
+        let w = dev.tensor([1.0, -2.0, 3.0, -4.0]);
+        let g = (probs * w).sum().backward();
+        let g = g.get(&t).array();
+        assert_eq!(g[1], 0.0);
+        assert_eq!(g[3], 0.0);
+        assert!(g[0] != 0.0 && g[2] != 0.0);
+        assert!(g.iter().all(|v| v.is_finite()));
+    }
 }
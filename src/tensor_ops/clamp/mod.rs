@@ -31,6 +31,42 @@ pub fn clamp<S: Shape, E: Dtype, D: UnaryKernel<ClampKernelOp<E>, E>, T: Tape<E,
     t.clamp(min, max)
 }
 
+/// Clamp all elements to be at least `min`. Elements above `min` pass through unchanged,
+/// and get a gradient of 1, while saturated elements get a gradient of 0.
+///
+/// Example:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, -0.5, 0.0, 0.5, 1.0]);
+/// let r = t.clamp_min(-0.5);
+/// assert_eq!(r.array(), [-0.5, -0.5, 0.0, 0.5, 1.0]);
+/// ```
+pub fn clamp_min<S: Shape, E: Dtype, D: UnaryKernel<ClampKernelOp<E>, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    min: impl Into<f64>,
+) -> Tensor<S, E, D, T> {
+    t.clamp_min(min)
+}
+
+/// Clamp all elements to be at most `max`. Elements below `max` pass through unchanged,
+/// and get a gradient of 1, while saturated elements get a gradient of 0.
+///
+/// Example:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, -0.5, 0.0, 0.5, 1.0]);
+/// let r = t.clamp_max(0.5);
+/// assert_eq!(r.array(), [-1.0, -0.5, 0.0, 0.5, 0.5]);
+/// ```
+pub fn clamp_max<S: Shape, E: Dtype, D: UnaryKernel<ClampKernelOp<E>, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    max: impl Into<f64>,
+) -> Tensor<S, E, D, T> {
+    t.clamp_max(max)
+}
+
 impl<S: Shape, E: Dtype, D: UnaryKernel<ClampKernelOp<E>, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
     /// See [clamp]
     pub fn clamp(self, min: impl Into<f64>, max: impl Into<f64>) -> Self {
@@ -46,6 +82,22 @@ impl<S: Shape, E: Dtype, D: UnaryKernel<ClampKernelOp<E>, E>, T: Tape<E, D>> Ten
             self,
         )
     }
+    /// See [clamp_min]
+    pub fn clamp_min(self, min: impl Into<f64>) -> Self {
+        self.try_clamp_min(min).unwrap()
+    }
+    /// See [clamp_min]
+    pub fn try_clamp_min(self, min: impl Into<f64>) -> Result<Self, D::Err> {
+        self.try_clamp(min, f64::INFINITY)
+    }
+    /// See [clamp_max]
+    pub fn clamp_max(self, max: impl Into<f64>) -> Self {
+        self.try_clamp_max(max).unwrap()
+    }
+    /// See [clamp_max]
+    pub fn try_clamp_max(self, max: impl Into<f64>) -> Result<Self, D::Err> {
+        self.try_clamp(f64::NEG_INFINITY, max)
+    }
 }
 
 #[cfg(test)]
@@ -63,4 +115,28 @@ mod tests {
         let g = r.exp().mean().backward();
         assert_close_to_literal!(g.get(&t), [[0.06131324, 0.16666667, 0.45304698], [0.0; 3]]);
     }
+
+    #[test]
+    fn test_clamp_min() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 100.0])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().clamp_min(-0.5);
+        assert_close_to_literal!(r, [-0.5, -0.5, 0.0, 1.0, 100.0]);
+        let g = (r * 2.0).sum().backward();
+        assert_close_to_literal!(g.get(&t), [0.0, 0.0, 2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_clamp_max() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([-100.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().clamp_max(0.5);
+        assert_close_to_literal!(r, [-100.0, -1.0, 0.0, 0.5, 0.5]);
+        let g = (r * 2.0).sum().backward();
+        assert_close_to_literal!(g.get(&t), [2.0, 2.0, 2.0, 0.0, 0.0]);
+    }
 }
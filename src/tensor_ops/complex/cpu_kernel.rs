@@ -0,0 +1,121 @@
+use std::borrow::Cow;
+
+use crate::{
+    shapes::{Complex32, Shape, Unit},
+    tensor::{unique_id, Cpu, Tensor, Tensorlike},
+    tensor_ops::{cpu_kernels::BinaryDerivative, ops::UnaryKernel},
+};
+
+// Gradients follow the same convention as pytorch: for a real valued loss `L`, the
+// gradient of `z = x + iy` is `dL/dx + i dL/dy`. For holomorphic ops this is
+// `grad_out * conj(f'(z))`, which is why [BinaryDerivative] returns conjugated derivatives.
+
+impl UnaryKernel<super::ConjKernelOp, Complex32> for Cpu {
+    const BACKWARD_WITHOUT_INP: bool = false;
+    const BACKWARD_WITHOUT_DATA: bool = true;
+
+    fn forward<S: Shape>(
+        &self,
+        _: super::ConjKernelOp,
+        inp: Cow<Tensor<S, Complex32, Self>>,
+    ) -> Result<Tensor<S, Complex32, Self>, Self::Err> {
+        let mut out = match inp {
+            Cow::Borrowed(inp) => Tensor {
+                id: unique_id(),
+                data: inp.data.clone(),
+                shape: inp.shape,
+                strides: inp.strides,
+                device: self.clone(),
+                tape: Default::default(),
+            },
+            Cow::Owned(mut inp) => {
+                inp.id = unique_id();
+                inp
+            }
+        };
+        for x in out.buf_iter_mut() {
+            *x = x.conj();
+        }
+        Ok(out)
+    }
+
+    /// `conj` isn't holomorphic, so its gradient is the conjugate of the incoming
+    /// gradient rather than a product with a derivative.
+    fn backward<S: Shape>(
+        &self,
+        _: super::ConjKernelOp,
+        _: &impl Tensorlike<S, Complex32, Self>,
+        grad_inp: &mut Self::Vec,
+        _: &impl Tensorlike<S, Complex32, Self>,
+        grad_out: &Self::Vec,
+    ) -> Result<(), Self::Err> {
+        for (i, x) in grad_inp.iter_mut().enumerate() {
+            *x += grad_out[i].conj();
+        }
+        Ok(())
+    }
+}
+
+impl BinaryDerivative<Complex32> for crate::tensor_ops::add::BinaryAddKernelOp {
+    const HAS_CONST_DF: bool = true;
+    #[inline(always)]
+    fn f(&self, &x: &Complex32, &y: &Complex32) -> Complex32 {
+        x + y
+    }
+    #[inline(always)]
+    fn dfdx(&self, _: &Complex32, _: &Complex32) -> Complex32 {
+        Complex32::ONE
+    }
+    #[inline(always)]
+    fn dfdy(&self, _: &Complex32, _: &Complex32) -> Complex32 {
+        Complex32::ONE
+    }
+    #[inline(always)]
+    fn const_dfdx(&self) -> Complex32 {
+        Complex32::ONE
+    }
+    #[inline(always)]
+    fn const_dfdy(&self) -> Complex32 {
+        Complex32::ONE
+    }
+}
+
+impl BinaryDerivative<Complex32> for crate::tensor_ops::sub::BinarySubKernelOp {
+    const HAS_CONST_DF: bool = true;
+    #[inline(always)]
+    fn f(&self, &x: &Complex32, &y: &Complex32) -> Complex32 {
+        x - y
+    }
+    #[inline(always)]
+    fn dfdx(&self, _: &Complex32, _: &Complex32) -> Complex32 {
+        Complex32::ONE
+    }
+    #[inline(always)]
+    fn dfdy(&self, _: &Complex32, _: &Complex32) -> Complex32 {
+        -Complex32::ONE
+    }
+    #[inline(always)]
+    fn const_dfdx(&self) -> Complex32 {
+        Complex32::ONE
+    }
+    #[inline(always)]
+    fn const_dfdy(&self) -> Complex32 {
+        -Complex32::ONE
+    }
+}
+
+impl BinaryDerivative<Complex32> for crate::tensor_ops::mul::BinaryMulKernelOp {
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &Complex32, &y: &Complex32) -> Complex32 {
+        x * y
+    }
+    #[inline(always)]
+    fn dfdx(&self, _x: &Complex32, &y: &Complex32) -> Complex32 {
+        y.conj()
+    }
+    #[inline(always)]
+    fn dfdy(&self, &x: &Complex32, _y: &Complex32) -> Complex32 {
+        x.conj()
+    }
+}
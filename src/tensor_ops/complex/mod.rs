@@ -0,0 +1,253 @@
+//! Operations on [Complex32] tensors. These are currently only implemented for
+//! [crate::tensor::Cpu].
+//!
+//! Gradients use the same convention as pytorch: for a real valued loss `L`, the gradient
+//! of `z = x + iy` is `dL/dx + i dL/dy`.
+
+mod cpu_kernel;
+
+use num_traits::Float;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ConjKernelOp;
+
+/// Elementwise complex conjugate `re - im * i`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let a = dev.tensor([Complex32::new(1.0, 2.0), Complex32::new(0.0, -1.0)]);
+/// let b = dev.tensor([Complex32::new(3.0, 1.0), Complex32::new(2.0, 0.5)]);
+/// // conjugate multiply
+/// let r = a * b.conj();
+/// assert_eq!(r.array(), [Complex32::new(5.0, 5.0), Complex32::new(-0.5, -2.0)]);
+/// ```
+pub fn conj<S: Shape, D: UnaryKernel<ConjKernelOp, Complex32>, T: Tape<Complex32, D>>(
+    t: Tensor<S, Complex32, D, T>,
+) -> Tensor<S, Complex32, D, T> {
+    t.conj()
+}
+
+impl<S: Shape, D: UnaryKernel<ConjKernelOp, Complex32>, T: Tape<Complex32, D>>
+    Tensor<S, Complex32, D, T>
+{
+    /// See [conj]
+    pub fn conj(self) -> Self {
+        self.try_conj().unwrap()
+    }
+    /// See [conj]
+    pub fn try_conj(self) -> Result<Self, D::Err> {
+        try_unary_op(ConjKernelOp, self)
+    }
+}
+
+impl<N: Dim, D: Storage<Complex32> + TensorFromVec<Complex32>> Tensor<(N,), Complex32, D> {
+    /// The discrete fourier transform `X[k] = sum_n x[n] * e^(-2 pi i k n / N)`.
+    ///
+    /// Uses a radix-2 FFT when the length is a power of 2, and a direct `O(N^2)` DFT
+    /// otherwise. This is computed on the host and is not tracked by tapes.
+    ///
+    /// **Pytorch equivalent**: `torch.fft.fft(t)`
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let x = dev.tensor([1.0, 0.0, 0.0, 0.0].map(Complex32::from));
+    /// assert_eq!(x.fft().array(), [Complex32::ONE; 4]);
+    /// ```
+    pub fn fft(&self) -> Self {
+        self.try_fft().unwrap()
+    }
+
+    /// Fallible version of [Tensor::fft]
+    pub fn try_fft(&self) -> Result<Self, D::Err> {
+        let data = transform(&self.as_vec(), false);
+        self.device.try_tensor_from_vec(data, self.shape)
+    }
+
+    /// The inverse discrete fourier transform `x[n] = 1/N sum_k X[k] * e^(2 pi i k n / N)`,
+    /// so that `t.fft().ifft()` is `t`. See [Tensor::fft].
+    ///
+    /// **Pytorch equivalent**: `torch.fft.ifft(t)`
+    pub fn ifft(&self) -> Self {
+        self.try_ifft().unwrap()
+    }
+
+    /// Fallible version of [Tensor::ifft]
+    pub fn try_ifft(&self) -> Result<Self, D::Err> {
+        let data = transform(&self.as_vec(), true);
+        self.device.try_tensor_from_vec(data, self.shape)
+    }
+}
+
+/// Computes the (inverse) DFT in f64.
+fn transform(data: &[Complex32], inverse: bool) -> std::vec::Vec<Complex32> {
+    let n = data.len();
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut x: std::vec::Vec<(f64, f64)> =
+        data.iter().map(|c| (c.re as f64, c.im as f64)).collect();
+
+    if n.is_power_of_two() {
+        // bit reversal permutation
+        let bits = n.trailing_zeros();
+        for i in 0..n {
+            let j = i
+                .reverse_bits()
+                .checked_shr(usize::BITS - bits)
+                .unwrap_or(0);
+            if i < j {
+                x.swap(i, j);
+            }
+        }
+
+        // iterative cooley-tukey
+        let mut len = 2;
+        while len <= n {
+            let theta = sign * 2.0 * std::f64::consts::PI / len as f64;
+            for start in (0..n).step_by(len) {
+                for k in 0..len / 2 {
+                    let (s, c) = Float::sin_cos(theta * k as f64);
+                    let (ar, ai) = x[start + k];
+                    let (br, bi) = x[start + k + len / 2];
+                    let (tr, ti) = (br * c - bi * s, br * s + bi * c);
+                    x[start + k] = (ar + tr, ai + ti);
+                    x[start + k + len / 2] = (ar - tr, ai - ti);
+                }
+            }
+            len <<= 1;
+        }
+    } else {
+        x = (0..n)
+            .map(|k| {
+                let mut acc = (0.0, 0.0);
+                for (j, (re, im)) in x.iter().enumerate() {
+                    let theta = sign * 2.0 * std::f64::consts::PI * ((k * j) % n) as f64 / n as f64;
+                    let (s, c) = Float::sin_cos(theta);
+                    acc.0 += re * c - im * s;
+                    acc.1 += re * s + im * c;
+                }
+                acc
+            })
+            .collect();
+    }
+
+    let scale = if inverse { 1.0 / n as f64 } else { 1.0 };
+    x.into_iter()
+        .map(|(re, im)| Complex32::new((re * scale) as f32, (im * scale) as f32))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    fn assert_complex_close<const N: usize>(a: [Complex32; N], b: [Complex32; N]) {
+        for (a, b) in a.iter().zip(b.iter()) {
+            assert!((*a - *b).norm() < 1e-5, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn test_fft_ifft_roundtrip() {
+        let dev: TestDevice = Default::default();
+        let re: Tensor<Rank1<8>, f32, _> = dev.sample_normal();
+        let im: Tensor<Rank1<8>, f32, _> = dev.sample_normal();
+        let x = dev.tensor(std::array::from_fn::<_, 8, _>(|i| {
+            Complex32::new(re.array()[i], im.array()[i])
+        }));
+        assert_complex_close(x.fft().ifft().array(), x.array());
+
+        // not a power of 2
+        let x = dev.tensor([1.0, -2.0, 3.0, 0.5, 0.0, 4.0].map(Complex32::from));
+        assert_complex_close(x.fft().ifft().array(), x.array());
+    }
+
+    #[test]
+    fn test_fft_values() {
+        let dev: TestDevice = Default::default();
+        let x = dev.tensor([1.0, 2.0, 3.0, 4.0].map(Complex32::from));
+        assert_complex_close(
+            x.fft().array(),
+            [
+                Complex32::new(10.0, 0.0),
+                Complex32::new(-2.0, 2.0),
+                Complex32::new(-2.0, 0.0),
+                Complex32::new(-2.0, -2.0),
+            ],
+        );
+
+        // 3 isn't a power of 2, so this goes through the direct DFT
+        let x = dev.tensor([1.0, 2.0, 3.0].map(Complex32::from));
+        let w = Complex32::new(-1.5, 0.8660254);
+        assert_complex_close(x.fft().array(), [Complex32::new(6.0, 0.0), w, w.conj()]);
+    }
+
+    #[test]
+    fn test_complex_add_mul_conj() {
+        let dev: TestDevice = Default::default();
+        let a = dev.tensor([Complex32::new(1.0, 2.0), Complex32::new(-1.0, 0.5)]);
+        let b = dev.tensor([Complex32::new(0.0, 1.0), Complex32::new(2.0, 2.0)]);
+        assert_eq!(
+            (a.clone() + b.clone()).array(),
+            [Complex32::new(1.0, 3.0), Complex32::new(1.0, 2.5)]
+        );
+        assert_eq!(
+            (a.clone() * b.clone()).array(),
+            [Complex32::new(-2.0, 1.0), Complex32::new(-3.0, -1.0)]
+        );
+        assert_eq!(
+            (a * b.conj()).array(),
+            [Complex32::new(2.0, -1.0), Complex32::new(-1.0, 3.0)]
+        );
+    }
+
+    type Traced = Tensor<Rank0, Complex32, TestDevice, OwnedTape<Complex32, TestDevice>>;
+
+    /// Checks the gradient of `f` at `z` against central differences of `L = re(f(z))`,
+    /// which should be `dL/dx + i dL/dy`.
+    fn check_grad(dev: &TestDevice, f: impl Fn(Traced) -> Traced, z: Complex32) {
+        let x = dev.tensor(z);
+        let g = f(x.leaky_trace()).backward().get(&x).array();
+        let loss = |z: Complex32| f(dev.tensor(z).leaky_trace()).array().re;
+        let (h, dx, dy) = (1e-2, Complex32::new(1e-2, 0.0), Complex32::new(0.0, 1e-2));
+        let dl_dx = (loss(z + dx) - loss(z - dx)) / (2.0 * h);
+        let dl_dy = (loss(z + dy) - loss(z - dy)) / (2.0 * h);
+        assert!(
+            (g - Complex32::new(dl_dx, dl_dy)).norm() < 5e-3,
+            "{g:?} != {dl_dx} + {dl_dy}i"
+        );
+    }
+
+    #[test]
+    fn test_complex_grads_finite_difference() {
+        let dev: TestDevice = Default::default();
+        let w = Complex32::new(0.5, -1.5);
+        let c = Complex32::new(-2.0, 0.75);
+        let z = Complex32::new(1.25, 0.5);
+
+        check_grad(&dev, |z| z * dev.tensor(w), z);
+        check_grad(&dev, |z| dev.tensor(w).leaky_trace() * z, z);
+        check_grad(&dev, |z| z.conj() * dev.tensor(c), z);
+        check_grad(
+            &dev,
+            |z| {
+                let sq_norm = z.with_empty_tape() * z.with_empty_tape().conj();
+                (sq_norm + z * dev.tensor(w)).conj() * dev.tensor(c)
+            },
+            z,
+        );
+
+        // the gradient of `z * w` flowing back into `z` is `grad * conj(w)`
+        let x = dev.tensor(z);
+        let g = (x.leaky_trace() * dev.tensor(w)).backward();
+        assert_eq!(g.get(&x).array(), w.conj());
+        // the gradient of `conj(z)` is `conj(grad)`
+        let g = (x.leaky_trace().conj() * dev.tensor(c)).backward();
+        assert_eq!(g.get(&x).array(), c);
+    }
+}
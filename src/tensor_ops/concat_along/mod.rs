@@ -218,6 +218,27 @@ mod tests {
         assert_close_to_tensor!(concat_grads.get(&b), b_grads.get(&b));
     }
 
+    #[test]
+    fn test_concat_ax_0_appends_rows() {
+        let dev: TestDevice = Default::default();
+        let a = dev.tensor([[1.0, 2.0, 3.0]]).to_dtype::<TestDtype>();
+        let b = dev
+            .tensor([[4.0, 5.0, 6.0], [7.0, 8.0, 9.0]])
+            .to_dtype::<TestDtype>();
+        let a_dyn = a.leaky_trace().realize::<(usize, Const<3>)>();
+        let b_dyn = b.clone().realize::<(usize, Const<3>)>();
+        let c: Tensor<Rank2<3, 3>, TestDtype, _, _> =
+            (a_dyn, b_dyn).concat_along(Axis::<0>).realize();
+        assert_close_to_literal!(c, [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+
+        // only the first row of the output gradient flows back into `a`
+        let w = dev
+            .tensor([[1.0, -1.0, 2.0], [10.0, 20.0, 30.0], [40.0, 50.0, 60.0]])
+            .to_dtype::<TestDtype>();
+        let g = (c * w).sum().backward();
+        assert_close_to_literal!(g.get(&a), [[1.0, -1.0, 2.0]]);
+    }
+
     #[test]
     fn test_concat_ax_1() {
         let dev: TestDevice = Default::default();
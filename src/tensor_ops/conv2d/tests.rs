@@ -339,3 +339,37 @@ fn test_conv2d_grouped_forward() {
         assert_close_to_tensor!(grads.get(&w_group), w_grad_group_true);
     }
 }
+
+#[test]
+fn test_conv2d_depthwise() {
+    let dev: TestDevice = Default::default();
+    let x = dev
+        .tensor([[
+            [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]],
+            [[0.0, 1.0, 0.0], [1.0, 0.0, 1.0], [0.0, 1.0, 0.0]],
+        ]])
+        .to_dtype::<TestDtype>();
+    // one 2x2 kernel per channel
+    let w = dev
+        .tensor([[[[1.0, 0.0], [0.0, -1.0]]], [[[2.0, 1.0], [1.0, 2.0]]]])
+        .to_dtype::<TestDtype>();
+    let y = (x.leaky_trace(), w.clone()).conv2d(Const::<1>, Const::<0>, Const::<1>, Const::<2>);
+    assert_close_to_literal!(
+        y,
+        [[[[-4.0, -4.0], [-4.0, -4.0]], [[2.0, 4.0], [4.0, 2.0]]]]
+    );
+
+    // channels don't mix, so each kernel only sees its own channel
+    let grads = y.sum().backward();
+    assert_close_to_literal!(
+        grads.get(&x),
+        [[
+            [[1.0, 1.0, 0.0], [1.0, 0.0, -1.0], [0.0, -1.0, -1.0]],
+            [[2.0, 3.0, 1.0], [3.0, 6.0, 3.0], [1.0, 3.0, 2.0]],
+        ]]
+    );
+    assert_close_to_literal!(
+        grads.get(&w),
+        [[[[12.0, 16.0], [24.0, 28.0]]], [[[2.0, 2.0], [2.0, 2.0]]]]
+    );
+}
@@ -95,6 +95,21 @@ where
     }
 }
 
+impl<S: Shape, E: Dtype, D: BinaryKernel<BinaryDivKernelOp, E>> Tensor<S, E, D> {
+    /// Computes `self / rhs` and writes the result into `out`, re-using its allocation
+    /// instead of allocating a new tensor. Nothing is recorded on a tape. See [div].
+    ///
+    /// `out` must be contiguous and have the same shape as `self` and `rhs`.
+    pub fn div_into(&self, rhs: &Self, out: &mut Self) {
+        self.try_div_into(rhs, out).unwrap()
+    }
+
+    /// Fallible version of [Tensor::div_into]
+    pub fn try_div_into(&self, rhs: &Self, out: &mut Self) -> Result<(), D::Err> {
+        try_binary_op_into(BinaryDivKernelOp, self, rhs, out)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tensor::*;
@@ -128,6 +143,20 @@ mod tests {
         assert_close_to_literal!(g.get(&b), [1.0 / 3.0, 1.0 / 6.0, 0.11111112]);
     }
 
+    #[test]
+    fn test_div_local_derivatives() {
+        let dev: TestDevice = Default::default();
+        let lhs = dev.tensor([3.0, -2.0]).to_dtype::<TestDtype>();
+        let rhs = dev.tensor([2.0, 4.0]).to_dtype::<TestDtype>();
+
+        let r = lhs.leaky_trace() / rhs.clone();
+        assert_close_to_literal!(r, [1.5, -0.5]);
+        let g = r.sum().backward();
+        // d/d_lhs = 1 / rhs, d/d_rhs = -lhs / rhs^2
+        assert_close_to_literal!(g.get(&lhs), [0.5, 0.25]);
+        assert_close_to_literal!(g.get(&rhs), [-0.75, 0.125]);
+    }
+
     #[test]
     fn test_div_2d() {
         let dev: TestDevice = Default::default();
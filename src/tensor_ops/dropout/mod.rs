@@ -3,6 +3,8 @@ mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
 
+use rand::Rng;
+
 use crate::{
     shapes::*,
     tensor::{PutTape, RandomU64, SplitTape, Storage, Tape, Tensor},
@@ -64,7 +66,36 @@ impl<S: Shape, E: Dtype, D: DropoutKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T>
     /// See [dropout]
     pub fn try_dropout(self, prob: impl Into<f64>) -> Result<Self, D::Err> {
         let seed = self.device.random_u64();
-        let prob = prob.into();
+        self.try_dropout_with_seed(prob.into(), seed)
+    }
+
+    /// [dropout] with the mask sampled using `rng` instead of the device's rng, so the
+    /// same `rng` state always produces the same mask.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # use rand::{rngs::StdRng, SeedableRng};
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank1<8>, f32, _> = dev.ones();
+    /// let a = t.clone().dropout_with_rng(0.5, &mut StdRng::seed_from_u64(0));
+    /// let b = t.dropout_with_rng(0.5, &mut StdRng::seed_from_u64(0));
+    /// assert_eq!(a.array(), b.array());
+    /// ```
+    pub fn dropout_with_rng<R: Rng>(self, prob: impl Into<f64>, rng: &mut R) -> Self {
+        self.try_dropout_with_rng(prob, rng).unwrap()
+    }
+
+    /// See [Tensor::dropout_with_rng]
+    pub fn try_dropout_with_rng<R: Rng>(
+        self,
+        prob: impl Into<f64>,
+        rng: &mut R,
+    ) -> Result<Self, D::Err> {
+        let seed = rng.gen();
+        self.try_dropout_with_seed(prob.into(), seed)
+    }
+
+    fn try_dropout_with_seed(self, prob: f64, seed: u64) -> Result<Self, D::Err> {
         let op = DropoutKernelOp { seed, prob };
         let (inp, mut tape) = self.split_tape();
         let out = inp.device.forward(op, &inp)?;
@@ -82,7 +113,7 @@ impl<S: Shape, E: Dtype, D: DropoutKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T>
 
 #[cfg(test)]
 mod tests {
-    use crate::{tensor::*, tensor_ops::*, tests::*};
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
 
     #[test]
     fn test_dropout_all_0d() {
@@ -116,6 +147,22 @@ mod tests {
         assert_close_to_literal!(g.get(&t), [0.4, 0.4, 0.4, 0.4, 0.0]);
     }
 
+    #[test]
+    fn test_dropout_with_rng() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<32>, TestDtype, _> = dev.ones();
+        let a = t
+            .clone()
+            .dropout_with_rng(0.5, &mut StdRng::seed_from_u64(0));
+        let b = t
+            .clone()
+            .dropout_with_rng(0.5, &mut StdRng::seed_from_u64(0));
+        let c = t.dropout_with_rng(0.5, &mut StdRng::seed_from_u64(1));
+        assert_eq!(a.array(), b.array());
+        assert_ne!(a.array(), c.array());
+    }
+
     #[test]
     fn test_dropout_2d() {
         let dev: TestDevice = Default::default();
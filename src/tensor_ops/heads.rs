@@ -0,0 +1,115 @@
+use crate::{
+    shapes::{Axes3, Axis, Dim, Dtype},
+    tensor::{HasErr, Tape, Tensor},
+};
+
+use super::{Device, PermuteTo, ReshapeTo, TryConcatAlong};
+
+/// Merges the heads of a `(H, S, V)` attention output into a single `(S, H * V)` tensor,
+/// which is what the output projection of [crate::nn::modules::MultiHeadAttention] is applied to.
+/// The values of head `h` end up in columns `h * V..(h + 1) * V`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let heads: Tensor<Rank3<2, 3, 4>, f32, _> = dev.zeros();
+/// let _: Tensor<(Const<3>, usize), f32, _> = merge_heads(heads);
+/// ```
+pub fn merge_heads<H: Dim, S: Dim, V: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    heads: Tensor<(H, S, V), E, D, T>,
+) -> Tensor<(S, usize), E, D, T> {
+    heads.merge_heads()
+}
+
+impl<H: Dim, S: Dim, V: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(H, S, V), E, D, T> {
+    /// See [merge_heads]
+    pub fn merge_heads(self) -> Tensor<(S, usize), E, D, T> {
+        self.try_merge_heads().unwrap()
+    }
+
+    /// See [merge_heads]
+    #[allow(clippy::type_complexity)]
+    pub fn try_merge_heads(self) -> Result<Tensor<(S, usize), E, D, T>, <Self as HasErr>::Err> {
+        let (h, s, v) = self.shape;
+        self.try_permute::<_, Axes3<1, 0, 2>>()?
+            .try_reshape_like(&(s, h.size() * v.size()))
+    }
+}
+
+/// Concatenates groups of attention heads along the head axis, so group `i` occupies heads
+/// `i * H..(i + 1) * H` of the output. Gradients of the output are split back to each group.
+///
+/// Combine with [merge_heads] to fuse several attention outputs before an output projection.
+///
+/// **Panics** if `groups` is empty.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let a: Tensor<Rank3<2, 3, 4>, f32, _> = dev.zeros();
+/// let b: Tensor<Rank3<2, 3, 4>, f32, _> = dev.ones();
+/// let heads: Tensor<(usize, Const<3>, Const<4>), f32, _> = concat_heads(vec![a, b]);
+/// assert_eq!(heads.shape().0, 4);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn concat_heads<H: Dim, S: Dim, V: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    groups: std::vec::Vec<Tensor<(H, S, V), E, D, T>>,
+) -> Tensor<(usize, S, V), E, D, T> {
+    try_concat_heads(groups).unwrap()
+}
+
+/// Fallible version of [concat_heads]
+#[allow(clippy::type_complexity)]
+pub fn try_concat_heads<H: Dim, S: Dim, V: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    groups: std::vec::Vec<Tensor<(H, S, V), E, D, T>>,
+) -> Result<Tensor<(usize, S, V), E, D, T>, D::Err> {
+    let mut groups = groups.into_iter().map(|g| {
+        let (h, s, v) = g.shape;
+        g.try_reshape_like(&(h.size(), s, v))
+    });
+    let mut out = groups
+        .next()
+        .expect("concat_heads requires at least one group")?;
+    for group in groups {
+        out = (out, group?).try_concat_along(Axis::<0>)?;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_merge_heads() {
+        let dev: TestDevice = Default::default();
+        let heads = dev
+            .tensor([[[1.0, 2.0], [3.0, 4.0]], [[5.0, 6.0], [7.0, 8.0]]])
+            .to_dtype::<TestDtype>();
+        let merged = heads.merge_heads().realize::<Rank2<2, 4>>();
+        assert_close_to_literal!(merged, [[1.0, 2.0, 5.0, 6.0], [3.0, 4.0, 7.0, 8.0]]);
+    }
+
+    #[test]
+    fn test_concat_heads_gradients() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+
+        let heads = concat_heads(std::vec![a.leaky_trace(), b.leaky_trace()]);
+        assert_eq!(heads.shape().0, 4);
+        let mut expected = a.as_vec();
+        expected.extend(b.as_vec());
+        assert_eq!(heads.as_vec(), expected);
+
+        // weight each group differently so the gradients identify where they went
+        let w = dev
+            .tensor([[[1.0; 4]; 3], [[1.0; 4]; 3], [[2.0; 4]; 3], [[2.0; 4]; 3]])
+            .to_dtype::<TestDtype>()
+            .realize::<(usize, Const<3>, Const<4>)>();
+        let g = (heads * w).sum().backward();
+        assert_close_to_literal!(g.get(&a), [[[1.0; 4]; 3]; 2]);
+        assert_close_to_literal!(g.get(&b), [[[2.0; 4]; 3]; 2]);
+    }
+}
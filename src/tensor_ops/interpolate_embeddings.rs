@@ -0,0 +1,124 @@
+use crate::{
+    shapes::{Const, Dim, Dtype},
+    tensor::{PutTape, SplitTape, Tape, Tensor},
+};
+
+use super::{Device, TryMatMul};
+
+/// Resizes a `(S, M)` table of embeddings to `(S2, M)` with linear interpolation along the
+/// first axis, for example to fine tune a learned positional embedding on longer sequences
+/// than it was trained on.
+///
+/// The first and last rows are kept as is, and the rows in between are spaced evenly over
+/// the original table (like `align_corners=True` in pytorch). The result is a linear function
+/// of the table, so gradients flow back into it.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[0.0, 1.0], [2.0, 3.0]]);
+/// let r = t.interpolate_embeddings::<3>();
+/// assert_eq!(r.array(), [[0.0, 1.0], [1.0, 2.0], [2.0, 3.0]]);
+/// ```
+pub fn interpolate_embeddings<const S2: usize, S: Dim, M: Dim, E: Dtype, D: Device<E>, T>(
+    t: Tensor<(S, M), E, D, T>,
+) -> Tensor<(Const<S2>, M), E, D, T>
+where
+    T: Tape<E, D>,
+{
+    t.interpolate_embeddings::<S2>()
+}
+
+impl<S: Dim, M: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(S, M), E, D, T> {
+    /// See [interpolate_embeddings]
+    pub fn interpolate_embeddings<const S2: usize>(self) -> Tensor<(Const<S2>, M), E, D, T> {
+        self.try_interpolate_embeddings().unwrap()
+    }
+
+    /// See [interpolate_embeddings]
+    #[allow(clippy::type_complexity)]
+    pub fn try_interpolate_embeddings<const S2: usize>(
+        self,
+    ) -> Result<Tensor<(Const<S2>, M), E, D, T>, D::Err> {
+        let s = self.shape.0;
+        assert!(s.size() > 0, "can't interpolate an empty table");
+
+        // each output row is a weighted sum of the two closest input rows
+        let mut weights = std::vec![0.0; S2 * s.size()];
+        for j in 0..S2 {
+            let x = if S2 > 1 {
+                (j * (s.size() - 1)) as f64 / (S2 - 1) as f64
+            } else {
+                0.0
+            };
+            let i0 = (x.floor() as usize).min(s.size() - 1);
+            let i1 = (i0 + 1).min(s.size() - 1);
+            let frac = x - i0 as f64;
+            weights[j * s.size() + i0] += 1.0 - frac;
+            weights[j * s.size() + i1] += frac;
+        }
+        let weights = weights
+            .into_iter()
+            .map(|w| E::from_f64(w).unwrap())
+            .collect();
+
+        let (t, tape) = self.split_tape();
+        let weights = t
+            .device
+            .try_tensor_from_vec(weights, (Const::<S2>, s))?
+            .put_tape(tape);
+        weights.try_matmul(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_interpolate_embeddings_4_to_8() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[0.0, 0.0], [3.0, 30.0], [6.0, 60.0], [9.0, 90.0]])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().interpolate_embeddings::<8>();
+
+        // the table is linear in the position, so the result is too
+        let expected: [[f64; 2]; 8] = core::array::from_fn(|j| {
+            let x = j as f64 * 3.0 / 7.0;
+            [3.0 * x, 30.0 * x]
+        });
+        // values go up to 90, so allow for f32 rounding
+        assert_close_to_literal!(r, expected, 1e-4);
+
+        // endpoints are preserved exactly
+        let r_arr = r.array();
+        let t_arr = t.array();
+        assert_eq!(r_arr[0], t_arr[0]);
+        assert_eq!(r_arr[7], t_arr[3]);
+
+        // the weights of each output row add up to 1
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t).sum::<Rank1<2>, _>(), [8.0; 2]);
+    }
+
+    #[test]
+    fn test_interpolate_embeddings_runtime_len() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0, 3.0], [5.0, 6.0, 7.0]])
+            .to_dtype::<TestDtype>();
+        let t = t.realize::<(usize, Const<3>)>();
+        let r = t.interpolate_embeddings::<5>();
+        assert_close_to_literal!(
+            r,
+            [
+                [1.0, 2.0, 3.0],
+                [2.0, 3.0, 4.0],
+                [3.0, 4.0, 5.0],
+                [4.0, 5.0, 6.0],
+                [5.0, 6.0, 7.0]
+            ]
+        );
+    }
+}
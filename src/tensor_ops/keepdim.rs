@@ -0,0 +1,154 @@
+use crate::{
+    shapes::{Axis, Dtype, KeepDimShape, ReduceShape, ReduceStridesTo, Shape},
+    tensor::{Tape, Tensor},
+};
+
+use super::{BroadcastTo, Device, MaxTo, MeanTo, MinTo, ReshapeTo, SumTo};
+
+/// Reductions along a single axis that keep the reduced axis with a size of 1, like
+/// pytorch's `keepdim=True`. The result can be expanded back to the original shape with
+/// [Tensor::broadcast_axis_keepdim], e.g. to center a tensor:
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([[1.0f32, 2.0, 3.0], [4.0, 6.0, 8.0]]);
+/// let m: Tensor<Rank2<2, 1>, f32, _> = t.clone().mean_axis_keepdim::<1>();
+/// assert_eq!(m.array(), [[2.0], [6.0]]);
+/// let centered = t.clone() - m.broadcast_axis_keepdim::<1, _>(t.shape());
+/// assert_eq!(centered.array(), [[-1.0, 0.0, 1.0], [-2.0, 0.0, 2.0]]);
+/// ```
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Sum reduction along axis `I`, keeping it with size 1.
+    pub fn sum_axis_keepdim<const I: isize>(self) -> Tensor<S::KeepDim, E, D, T>
+    where
+        S: KeepDimShape<Axis<I>>,
+    {
+        self.try_sum_axis_keepdim().unwrap()
+    }
+
+    /// Fallible version of [Tensor::sum_axis_keepdim]
+    pub fn try_sum_axis_keepdim<const I: isize>(self) -> Result<Tensor<S::KeepDim, E, D, T>, D::Err>
+    where
+        S: KeepDimShape<Axis<I>>,
+    {
+        let dst = self.shape.keep_dim();
+        self.try_sum::<<S as ReduceShape<Axis<I>>>::Reduced, Axis<I>>()?
+            .try_reshape_like(&dst)
+    }
+
+    /// Mean reduction along axis `I`, keeping it with size 1.
+    pub fn mean_axis_keepdim<const I: isize>(self) -> Tensor<S::KeepDim, E, D, T>
+    where
+        S: KeepDimShape<Axis<I>>,
+    {
+        self.try_mean_axis_keepdim().unwrap()
+    }
+
+    /// Fallible version of [Tensor::mean_axis_keepdim]
+    pub fn try_mean_axis_keepdim<const I: isize>(
+        self,
+    ) -> Result<Tensor<S::KeepDim, E, D, T>, D::Err>
+    where
+        S: KeepDimShape<Axis<I>>,
+    {
+        let dst = self.shape.keep_dim();
+        self.try_mean::<<S as ReduceShape<Axis<I>>>::Reduced, Axis<I>>()?
+            .try_reshape_like(&dst)
+    }
+
+    /// Max reduction along axis `I`, keeping it with size 1.
+    pub fn max_axis_keepdim<const I: isize>(self) -> Tensor<S::KeepDim, E, D, T>
+    where
+        S: KeepDimShape<Axis<I>>,
+    {
+        self.try_max_axis_keepdim().unwrap()
+    }
+
+    /// Fallible version of [Tensor::max_axis_keepdim]
+    pub fn try_max_axis_keepdim<const I: isize>(self) -> Result<Tensor<S::KeepDim, E, D, T>, D::Err>
+    where
+        S: KeepDimShape<Axis<I>>,
+    {
+        let dst = self.shape.keep_dim();
+        self.try_max::<<S as ReduceShape<Axis<I>>>::Reduced, Axis<I>>()?
+            .try_reshape_like(&dst)
+    }
+
+    /// Min reduction along axis `I`, keeping it with size 1.
+    pub fn min_axis_keepdim<const I: isize>(self) -> Tensor<S::KeepDim, E, D, T>
+    where
+        S: KeepDimShape<Axis<I>>,
+    {
+        self.try_min_axis_keepdim().unwrap()
+    }
+
+    /// Fallible version of [Tensor::min_axis_keepdim]
+    pub fn try_min_axis_keepdim<const I: isize>(self) -> Result<Tensor<S::KeepDim, E, D, T>, D::Err>
+    where
+        S: KeepDimShape<Axis<I>>,
+    {
+        let dst = self.shape.keep_dim();
+        self.try_min::<<S as ReduceShape<Axis<I>>>::Reduced, Axis<I>>()?
+            .try_reshape_like(&dst)
+    }
+
+    /// Expands the size 1 axis `I` of a keepdim reduction back to `dst`, which is the
+    /// shape that was reduced. Gradients are summed back along axis `I`.
+    pub fn broadcast_axis_keepdim<const I: isize, Dst>(self, dst: &Dst) -> Tensor<Dst, E, D, T>
+    where
+        Dst: KeepDimShape<Axis<I>, KeepDim = S>,
+    {
+        self.try_broadcast_axis_keepdim(dst).unwrap()
+    }
+
+    /// Fallible version of [Tensor::broadcast_axis_keepdim]
+    pub fn try_broadcast_axis_keepdim<const I: isize, Dst>(
+        self,
+        dst: &Dst,
+    ) -> Result<Tensor<Dst, E, D, T>, D::Err>
+    where
+        Dst: KeepDimShape<Axis<I>, KeepDim = S>,
+    {
+        let reduced: <Dst as ReduceShape<Axis<I>>>::Reduced = dst.reduced();
+        self.try_reshape_like(&reduced)?.try_broadcast_like(dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_keepdim_shapes() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let _: Tensor<Rank3<1, 3, 4>, _, _> = t.clone().sum_axis_keepdim::<0>();
+        let _: Tensor<Rank3<2, 1, 4>, _, _> = t.clone().max_axis_keepdim::<1>();
+        let _: Tensor<Rank3<2, 3, 1>, _, _> = t.clone().min_axis_keepdim::<2>();
+
+        let r = t.clone().realize::<(usize, Const<3>, usize)>();
+        let m = r.mean_axis_keepdim::<2>();
+        assert_eq!(m.shape(), &(2, Const, Const));
+        assert_close_to_tensor!(
+            m.reshape_like(&(Const::<2>, Const::<3>)),
+            t.mean::<Rank2<2, 3>, _>()
+        );
+    }
+
+    #[test]
+    fn test_center_with_keepdim_mean() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<3, 5>, TestDtype, _> = dev.sample_normal();
+        let mean = t.leaky_trace().mean_axis_keepdim::<1>();
+        let centered = t.leaky_trace() - mean.broadcast_axis_keepdim::<1, _>(t.shape());
+        assert_close_to_literal!(
+            centered.retaped::<NoneTape>().sum::<Rank1<3>, _>(),
+            [0.0; 3]
+        );
+
+        // centering removes the mean, so the gradient of the sum is 0 everywhere
+        let g = centered.sum().backward();
+        assert_close_to_literal!(g.get(&t), [[0.0; 5]; 3]);
+    }
+}
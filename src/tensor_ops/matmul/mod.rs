@@ -523,6 +523,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_matmul_batched_transposed_rhs() {
+        let dev: TestDevice = Default::default();
+
+        // 3d: a @ b^T, with b stored as (B, N, K) like the keys in attention
+        let a: Tensor<Rank3<5, 3, 2>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank3<5, 4, 2>, TestDtype, _> = dev.sample_normal();
+        let c = a.leaky_trace().matmul(b.leaky_trace().transpose_last_two());
+        let c_array = c.array();
+        let g = c.exp().sum().backward();
+        let (a_array, b_array) = (a.array(), b.array());
+        let (g_a, g_b) = (g.get(&a).array(), g.get(&b).array());
+        for i in 0..5 {
+            let sub_a = dev.tensor(a_array[i]);
+            let sub_b = dev.tensor(b_array[i]);
+            let sub_c = sub_a.leaky_trace().matmul(sub_b.leaky_trace().transpose());
+            assert_close!(sub_c.array(), c_array[i]);
+            let sub_g = sub_c.exp().sum().backward();
+            assert_close!(sub_g.get(&sub_a).array(), g_a[i]);
+            assert_close!(sub_g.get(&sub_b).array(), g_b[i], 1e-5);
+        }
+
+        // 4d: the same, per head
+        let a: Tensor<Rank4<2, 3, 4, 5>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank4<2, 3, 6, 5>, TestDtype, _> = dev.sample_normal();
+        let c = a.leaky_trace().matmul(b.leaky_trace().transpose_last_two());
+        let c_array = c.array();
+        let g = c.exp().sum().backward();
+        let (a_array, b_array) = (a.array(), b.array());
+        let (g_a, g_b) = (g.get(&a).array(), g.get(&b).array());
+        for i in 0..2 {
+            for j in 0..3 {
+                let sub_a = dev.tensor(a_array[i][j]);
+                let sub_b = dev.tensor(b_array[i][j]);
+                let sub_c = sub_a.leaky_trace().matmul(sub_b.leaky_trace().transpose());
+                assert_close!(sub_c.array(), c_array[i][j]);
+                let sub_g = sub_c.exp().sum().backward();
+                assert_close!(sub_g.get(&sub_a).array(), g_a[i][j]);
+                assert_close!(sub_g.get(&sub_b).array(), g_b[i][j], 1e-5);
+            }
+        }
+    }
+
     #[test]
     fn test_matmul_vec_normal() {
         let dev: TestDevice = Default::default();
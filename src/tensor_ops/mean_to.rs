@@ -147,4 +147,41 @@ mod tests {
         let r2 = t.sum::<_, Axis<0>>().sum::<_, Axis<0>>() / 6.0;
         assert_close_to_tensor!(r, r2);
     }
+
+    #[test]
+    fn test_mean_axes_4d_spatial() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([
+                [
+                    [[1.0, 2.0], [3.0, 4.0]],
+                    [[0.0, 0.0], [0.0, 4.0]],
+                    [[-1.0, 1.0], [-2.0, 2.0]],
+                ],
+                [
+                    [[2.0; 2]; 2],
+                    [[-1.0, -2.0], [-3.0, -6.0]],
+                    [[0.5, 1.5], [2.5, 3.5]],
+                ],
+            ])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().mean::<Rank2<2, 3>, Axes2<2, 3>>();
+        assert_close_to_literal!(r, [[2.5, 1.0, 0.0], [2.0, -3.0, 2.0]]);
+        let w = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let g = (r * w).sum().backward();
+        assert_close_to_literal!(
+            g.get(&t),
+            [
+                [[[0.25; 2]; 2], [[0.5; 2]; 2], [[0.75; 2]; 2]],
+                [[[1.0; 2]; 2], [[1.25; 2]; 2], [[1.5; 2]; 2]],
+            ]
+        );
+
+        let s = t.leaky_trace().sum::<Rank2<2, 3>, Axes2<2, 3>>();
+        assert_close_to_literal!(s, [[10.0, 4.0, 0.0], [8.0, -12.0, 8.0]]);
+        let g = s.sum().backward();
+        assert_close_to_literal!(g.get(&t), [[[[1.0; 2]; 2]; 3]; 2]);
+    }
 }
@@ -57,6 +57,7 @@
 //! Complete list of reductions:
 //!
 //! - [MaxTo]
+//! - [AbsMaxTo]
 //! - [MeanTo]
 //! - [MinTo]
 //! - [SumTo]
@@ -150,7 +151,10 @@ mod utilities;
 pub use utilities::*;
 
 mod abs;
+mod abs_max_to;
 mod add;
+mod affine;
+mod attention_logits;
 mod attention_reshape;
 pub(crate) mod axpy;
 mod bce;
@@ -159,6 +163,7 @@ mod broadcast_to;
 mod choose;
 mod clamp;
 mod cmp;
+mod complex;
 mod concat;
 mod concat_along;
 mod cos;
@@ -166,7 +171,10 @@ mod div;
 mod dropout;
 mod exp;
 mod gelu;
+mod heads;
 mod huber_error;
+mod interpolate_embeddings;
+mod keepdim;
 mod ln;
 mod log_softmax;
 mod logsumexp_to;
@@ -177,6 +185,7 @@ mod mean_to;
 mod min_to;
 mod minimum;
 mod mul;
+mod nan_to_num;
 mod nans_to;
 mod negate;
 mod normalize;
@@ -188,16 +197,19 @@ mod recip;
 mod relu;
 mod reshape_to;
 mod roll;
+mod scatter_add;
 mod select_and_gather;
 mod sigmoid;
 mod sin;
 mod slice;
 mod softmax;
+mod split;
 mod sqrt;
 mod square;
 mod stack;
 mod stddev_to;
 mod sub;
+mod sum_of_squares;
 mod sum_to;
 mod tanh;
 mod to_dtype;
@@ -206,15 +218,18 @@ mod upscale2d;
 mod var_to;
 
 pub use abs::abs;
+pub use abs_max_to::AbsMaxTo;
 pub use add::{add, TryAdd};
+pub use affine::affine;
 pub use attention_reshape::TryAttentionReshape;
 pub use axpy::axpy;
 pub use bce::bce_with_logits;
 pub use boolean::{bool_and, bool_not, bool_or, bool_xor};
 pub use broadcast_to::BroadcastTo;
-pub use choose::ChooseFrom;
-pub use clamp::clamp;
+pub use choose::{masked_fill, ChooseFrom};
+pub use clamp::{clamp, clamp_max, clamp_min};
 pub use cmp::{eq, ge, gt, le, lt, ne, TryEq, TryGe, TryGt, TryLe, TryLt, TryNe};
+pub use complex::conj;
 #[allow(deprecated)]
 pub use concat::TryConcat;
 pub use concat_along::TryConcatAlong;
@@ -223,7 +238,9 @@ pub use div::{div, TryDiv};
 pub use dropout::dropout;
 pub use exp::exp;
 pub use gelu::gelu;
+pub use heads::{concat_heads, merge_heads, try_concat_heads};
 pub use huber_error::huber_error;
+pub use interpolate_embeddings::interpolate_embeddings;
 pub use ln::ln;
 pub use log_softmax::log_softmax;
 pub use logsumexp_to::LogSumExpTo;
@@ -234,6 +251,7 @@ pub use mean_to::MeanTo;
 pub use min_to::MinTo;
 pub use minimum::minimum;
 pub use mul::{mul, TryMul};
+pub use nan_to_num::nan_to_num;
 pub use nans_to::nans_to;
 pub use negate::negate;
 pub use normalize::normalize;
@@ -242,19 +260,22 @@ pub use pow::{powf, powi};
 pub use prelu::{leakyrelu, prelu, TryPReLU};
 pub use realize_to::RealizeTo;
 pub use recip::recip;
-pub use relu::relu;
+pub use relu::{relu, relu_in_place};
 pub use reshape_to::ReshapeTo;
 pub use roll::Roll;
+pub use scatter_add::{scatter_add, try_scatter_add};
 pub use select_and_gather::{GatherTo, SelectTo};
 pub use sigmoid::sigmoid;
 pub use sin::sin;
 pub use slice::slice;
-pub use softmax::softmax;
+pub use softmax::{softmax, softmax_with_eps};
+pub use split::{chunk, split};
 pub use sqrt::sqrt;
 pub use square::square;
 pub use stack::TryStack;
 pub use stddev_to::StddevTo;
 pub use sub::{sub, TrySub};
+pub use sum_of_squares::sum_of_squares;
 pub use sum_to::SumTo;
 pub use tanh::tanh;
 pub use to_dtype::to_dtype;
@@ -262,6 +283,7 @@ pub use tri::{lower_tri, upper_tri};
 pub use upscale2d::{Bilinear, GenericUpscale2D, NearestNeighbor, TryUpscale2D, UpscaleMethod};
 pub use var_to::VarTo;
 
+pub(crate) use attention_logits::try_attention_scores;
 pub(crate) use to_dtype::ToDtypeKernel;
 pub(crate) use upscale2d::Upscale2DKernel;
 
@@ -88,6 +88,21 @@ where
         self.try_mul(rhs).unwrap()
     }
 }
+impl<S: Shape, E: Dtype, D: BinaryKernel<BinaryMulKernelOp, E>> Tensor<S, E, D> {
+    /// Computes `self * rhs` and writes the result into `out`, re-using its allocation
+    /// instead of allocating a new tensor. Nothing is recorded on a tape. See [mul].
+    ///
+    /// `out` must be contiguous and have the same shape as `self` and `rhs`.
+    pub fn mul_into(&self, rhs: &Self, out: &mut Self) {
+        self.try_mul_into(rhs, out).unwrap()
+    }
+
+    /// Fallible version of [Tensor::mul_into]
+    pub fn try_mul_into(&self, rhs: &Self, out: &mut Self) -> Result<(), D::Err> {
+        try_binary_op_into(BinaryMulKernelOp, self, rhs, out)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{tensor::*, tensor_ops::*, tests::*};
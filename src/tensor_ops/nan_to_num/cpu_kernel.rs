@@ -0,0 +1,22 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+
+impl<F: num_traits::Float> UnaryDerivative<F> for super::NanToNumKernelOp<F> {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, x: &F) -> F {
+        if x.is_finite() {
+            *x
+        } else {
+            self.0
+        }
+    }
+    #[inline(always)]
+    fn df(&self, x: &F) -> F {
+        if x.is_finite() {
+            F::one()
+        } else {
+            F::zero()
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use super::NanToNumKernelOp as NanToNum;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+#[cfg(feature = "f16")]
+unsafe impl cudarc::driver::DeviceRepr for NanToNum<half::f16> {}
+unsafe impl cudarc::driver::DeviceRepr for NanToNum<f32> {}
+unsafe impl cudarc::driver::DeviceRepr for NanToNum<f64> {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/nan_to_num.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(
+    NanToNum<half::f16>,
+    half::f16,
+    PTX,
+    "nan_to_num_fwd_f16",
+    "nan_to_num_bwd_f16"
+);
+cuda_unary!(
+    NanToNum<f32>,
+    f32,
+    PTX,
+    "nan_to_num_fwd_f32",
+    "nan_to_num_bwd_f32"
+);
+cuda_unary!(
+    NanToNum<f64>,
+    f64,
+    PTX,
+    "nan_to_num_fwd_f64",
+    "nan_to_num_bwd_f64"
+);
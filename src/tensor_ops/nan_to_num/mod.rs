@@ -0,0 +1,62 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct NanToNumKernelOp<E>(E);
+
+/// Replaces every non finite value (NaN, inf and -inf) with `value`. The gradient
+/// passes through unchanged at finite values, and is zero where a value was replaced.
+///
+/// Use [nans_to()] to only replace NaNs.
+///
+/// **Pytorch equivalent**: `t.nan_to_num(value, value, value)`
+///
+/// Example:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([1.0, f32::NAN, f32::INFINITY, f32::NEG_INFINITY]);
+/// let r = t.nan_to_num(0.0);
+/// assert_eq!(r.array(), [1.0, 0.0, 0.0, 0.0]);
+/// ```
+pub fn nan_to_num<S: Shape, E: Dtype, D: UnaryKernel<NanToNumKernelOp<E>, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    value: impl Into<f64>,
+) -> Tensor<S, E, D, T> {
+    t.nan_to_num(value)
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<NanToNumKernelOp<E>, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [nan_to_num]
+    pub fn nan_to_num(self, value: impl Into<f64>) -> Self {
+        self.try_nan_to_num(value).unwrap()
+    }
+    /// See [nan_to_num]
+    pub fn try_nan_to_num(self, value: impl Into<f64>) -> Result<Self, D::Err> {
+        let value = E::from_f64(value.into()).unwrap();
+        try_unary_op(NanToNumKernelOp(value), self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_nan_to_num_1d() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([1.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 4.0])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().nan_to_num(-1.0);
+        assert_close_to_literal!(r, [1.0, -1.0, -1.0, -1.0, 4.0]);
+        let g = r.exp().mean().backward();
+        assert_close_to_literal!(g.get(&t), [0.54365635, 0.0, 0.0, 0.0, 10.919630]);
+    }
+}
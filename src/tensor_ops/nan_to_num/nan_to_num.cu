@@ -0,0 +1,18 @@
+#include "unary_op_macros.cuh"
+
+template<typename F>
+struct NanToNumKernelOp {
+    F x;
+};
+
+UNARY_OP(__half, nan_to_num_fwd_f16, nan_to_num_bwd_f16, NanToNumKernelOp<__half>,
+    isfiniteg(x) ? x : op.x,
+    isfiniteg(x) ? 1.0 : 0.0)
+
+UNARY_OP(float, nan_to_num_fwd_f32, nan_to_num_bwd_f32, NanToNumKernelOp<float>,
+    isfiniteg(x) ? x : op.x,
+    isfiniteg(x) ? 1.0 : 0.0)
+
+UNARY_OP(double, nan_to_num_fwd_f64, nan_to_num_bwd_f64, NanToNumKernelOp<double>,
+    isfiniteg(x) ? x : op.x,
+    isfiniteg(x) ? 1.0 : 0.0)
@@ -46,7 +46,7 @@ impl<S: Shape, E: Dtype, D: UnaryKernel<NegateKernelOp, E>, T: Tape<E, D>> std::
 
 #[cfg(test)]
 mod tests {
-    use crate::{tensor::*, tensor_ops::*, tests::*};
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
 
     #[test]
     fn test_1d_neg() {
@@ -58,4 +58,17 @@ mod tests {
         let g = r.exp().mean().backward();
         assert_close_to_literal!(g.get(&a), [-2.463019, -0.33333334, -0.0022459824]);
     }
+
+    #[test]
+    fn test_2d_neg_grad_is_negated() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let w: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+
+        let r = -(a.leaky_trace());
+        assert_close_to_tensor!(r, a.clone().negate());
+        let g_neg = (r * w.clone()).sum().backward();
+        let g = (a.leaky_trace() * w).sum().backward();
+        assert_close_to_tensor!(g_neg.get(&a), -g.get(&a));
+    }
 }
@@ -51,6 +51,65 @@ impl<S: Shape, E, D: Storage<E>, T: Tape<E, D>> PermuteTo for Tensor<S, E, D, T>
     }
 }
 
+impl<M: Dim, N: Dim, E, D: Storage<E>, T: Tape<E, D>> Tensor<(M, N), E, D, T> {
+    /// Transposes a matrix, so `(M, N)` becomes `(N, M)`. Like [PermuteTo::permute], this
+    /// doesn't move any data, and the gradient is transposed back on the backward pass.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let a: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// let b: Tensor<Rank2<3, 2>, f32, _> = a.transpose();
+    /// assert_eq!(b.array(), [[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]);
+    /// ```
+    pub fn transpose(self) -> Tensor<(N, M), E, D, T> {
+        self.try_transpose().unwrap()
+    }
+
+    /// Fallible version of [Tensor::transpose]
+    #[allow(clippy::type_complexity)]
+    pub fn try_transpose(self) -> Result<Tensor<(N, M), E, D, T>, D::Err> {
+        self.try_permute::<_, Axes2<1, 0>>()
+    }
+}
+
+impl<B: Dim, M: Dim, N: Dim, E, D: Storage<E>, T: Tape<E, D>> Tensor<(B, M, N), E, D, T> {
+    /// Swaps the last two axes of a batch of matrices, so `(B, M, N)` becomes `(B, N, M)`.
+    /// Like [PermuteTo::permute], this doesn't move any data.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let a: Tensor<Rank3<1, 2, 3>, f32, _> = dev.tensor([[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]]);
+    /// let b: Tensor<Rank3<1, 3, 2>, f32, _> = a.transpose_last_two();
+    /// assert_eq!(b.array(), [[[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]]);
+    /// ```
+    pub fn transpose_last_two(self) -> Tensor<(B, N, M), E, D, T> {
+        self.try_transpose_last_two().unwrap()
+    }
+
+    /// Fallible version of [Tensor::transpose_last_two]
+    #[allow(clippy::type_complexity)]
+    pub fn try_transpose_last_two(self) -> Result<Tensor<(B, N, M), E, D, T>, D::Err> {
+        self.try_permute::<_, Axes3<0, 2, 1>>()
+    }
+}
+
+impl<B: Dim, H: Dim, M: Dim, N: Dim, E, D: Storage<E>, T: Tape<E, D>>
+    Tensor<(B, H, M, N), E, D, T>
+{
+    /// Swaps the last two axes of a 4d tensor, so `(B, H, M, N)` becomes `(B, H, N, M)`.
+    pub fn transpose_last_two(self) -> Tensor<(B, H, N, M), E, D, T> {
+        self.try_transpose_last_two().unwrap()
+    }
+
+    /// Fallible version of [Tensor::transpose_last_two]
+    #[allow(clippy::type_complexity)]
+    pub fn try_transpose_last_two(self) -> Result<Tensor<(B, H, N, M), E, D, T>, D::Err> {
+        self.try_permute::<_, Axes4<0, 1, 3, 2>>()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::needless_range_loop)]
@@ -143,6 +202,63 @@ mod tests {
         assert_eq!(g1.get(&t).array(), g2.get(&t).array());
     }
 
+    #[test]
+    fn test_transpose_2d() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().transpose();
+        assert_close_to_literal!(r, [[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [[1.0; 3]; 2]);
+
+        // a non symmetric upstream gradient is routed back transposed
+        let w = dev
+            .tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let g = (t.leaky_trace().transpose() * w).sum().backward();
+        assert_close_to_literal!(g.get(&t), [[1.0, 3.0, 5.0], [2.0, 4.0, 6.0]]);
+    }
+
+    #[test]
+    fn test_transpose_last_two() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([
+                [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]],
+                [[7.0, 8.0, 9.0], [0.0, 1.0, 2.0]],
+            ])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().transpose_last_two();
+        assert_close_to_literal!(
+            r,
+            [
+                [[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]],
+                [[7.0, 0.0], [8.0, 1.0], [9.0, 2.0]]
+            ]
+        );
+
+        let w = dev
+            .tensor([
+                [[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]],
+                [[7.0, 8.0], [9.0, 0.0], [1.0, 2.0]],
+            ])
+            .to_dtype::<TestDtype>();
+        let g = (r * w).sum().backward();
+        assert_close_to_literal!(
+            g.get(&t),
+            [
+                [[1.0, 3.0, 5.0], [2.0, 4.0, 6.0]],
+                [[7.0, 9.0, 1.0], [8.0, 0.0, 2.0]]
+            ]
+        );
+
+        let t: Tensor<Rank4<2, 3, 4, 5>, TestDtype, _> = dev.sample_normal();
+        let r: Tensor<Rank4<2, 3, 5, 4>, TestDtype, _> = t.clone().transpose_last_two();
+        assert_eq!(r.array(), t.permute::<_, Axes4<0, 1, 3, 2>>().array());
+    }
+
     #[test]
     fn test_valid_permutations() {
         let dev: TestDevice = Default::default();
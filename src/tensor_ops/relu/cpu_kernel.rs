@@ -16,3 +16,20 @@ impl<F: num_traits::Float> UnaryDerivative<F> for super::ReLUKernelOp {
         }
     }
 }
+
+impl<F: num_traits::Float> UnaryDerivative<F> for super::InPlaceReLUKernelOp {
+    const DF_USES_FX: bool = true;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, x: &F) -> F {
+        x.max(F::zero())
+    }
+    #[inline(always)]
+    fn df(&self, fx: &F) -> F {
+        if fx > &F::zero() {
+            F::one()
+        } else {
+            F::zero()
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use super::ReLUKernelOp;
+use super::{InPlaceReLUKernelOp, ReLUKernelOp};
 use crate::tensor_ops::cuda_kernels::cuda_unary;
 
 unsafe impl cudarc::driver::DeviceRepr for ReLUKernelOp {}
+unsafe impl cudarc::driver::DeviceRepr for InPlaceReLUKernelOp {}
 
 const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/relu.ptx"));
 
@@ -9,3 +10,8 @@ const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/relu.ptx"));
 cuda_unary!(ReLUKernelOp, half::f16, PTX, "relu_fwd_f16", "relu_bwd_f16");
 cuda_unary!(ReLUKernelOp, f32, PTX, "relu_fwd_f32", "relu_bwd_f32");
 cuda_unary!(ReLUKernelOp, f64, PTX, "relu_fwd_f64", "relu_bwd_f64");
+
+#[cfg(feature = "f16")]
+cuda_unary!(df(f(x)) InPlaceReLUKernelOp, half::f16, PTX, "relu_in_place_fwd_f16", "relu_in_place_bwd_f16");
+cuda_unary!(df(f(x)) InPlaceReLUKernelOp, f32, PTX, "relu_in_place_fwd_f32", "relu_in_place_bwd_f32");
+cuda_unary!(df(f(x)) InPlaceReLUKernelOp, f64, PTX, "relu_in_place_fwd_f64", "relu_in_place_bwd_f64");
@@ -10,6 +10,10 @@ use crate::{shapes::*, tensor::*};
 #[derive(Debug, Default, Copy, Clone)]
 pub struct ReLUKernelOp;
 
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct InPlaceReLUKernelOp;
+
 /// [Rectified Linear Unit (ReLU)](https://en.wikipedia.org/wiki/Rectifier_(neural_networks)). `max(0, t)`
 ///
 /// The derivative is the [Heaviside](https://en.wikipedia.org/wiki/Heaviside_step_function) function.
@@ -39,9 +43,41 @@ impl<S: Shape, E: Dtype, D: UnaryKernel<ReLUKernelOp, E>, T: Tape<E, D>> Tensor<
     }
 }
 
+/// Same as [relu], but writes the result into the data buffer of `t` instead of allocating
+/// a new one, if the buffer isn't shared with another tensor.
+///
+/// To do this the gradient is computed from the output instead of the input, which is
+/// possible because `relu(t) > 0` exactly where `t > 0`. The forward and backward pass give
+/// the same results as [relu].
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.relu_in_place();
+/// assert_eq!(r.array(), [0.0, 0.0, 1.0, 2.0]);
+/// ```
+pub fn relu_in_place<S: Shape, E: Dtype, D: UnaryKernel<InPlaceReLUKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.relu_in_place()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<InPlaceReLUKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [relu_in_place]
+    pub fn relu_in_place(self) -> Self {
+        self.try_relu_in_place().unwrap()
+    }
+    /// See [relu_in_place]
+    pub fn try_relu_in_place(self) -> Result<Self, D::Err> {
+        try_unary_op(InPlaceReLUKernelOp, self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{tensor::*, tensor_ops::*, tests::*};
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
 
     #[test]
     fn test_relu() {
@@ -55,4 +91,37 @@ mod tests {
         let g = r.exp().mean().backward();
         assert_close_to_literal!(g.get(&x), [0.0, 0.0, 0.0, 0.54365635, 1.4778112]);
     }
+
+    #[test]
+    fn test_relu_in_place_matches_relu() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<4, 8>, TestDtype, _> = dev.sample_normal();
+        let w: Tensor<Rank2<4, 8>, TestDtype, _> = dev.sample_normal();
+
+        let r = x.leaky_trace().relu();
+        let r_in_place = x.leaky_trace().relu_in_place();
+        assert_eq!(r.array(), r_in_place.array());
+
+        let g = (r * w.clone()).exp().mean().backward();
+        let g_in_place = (r_in_place * w).exp().mean().backward();
+        assert_eq!(g.get(&x).array(), g_in_place.get(&x).array());
+    }
+
+    #[test]
+    fn test_relu_in_place_reuses_buffer() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<4, 8>, TestDtype, _> = dev.sample_normal();
+
+        // the output of `* 2.0` isn't shared with anything, so its buffer can be reused
+        let h = x.leaky_trace() * 2.0;
+        let buf = std::sync::Arc::as_ptr(&h.data);
+        let r = h.relu_in_place();
+        assert_eq!(std::sync::Arc::as_ptr(&r.data), buf);
+
+        // the allocating version keeps the input around for backward
+        let h = x.leaky_trace() * 2.0;
+        let buf = std::sync::Arc::as_ptr(&h.data);
+        let r = h.relu();
+        assert_ne!(std::sync::Arc::as_ptr(&r.data), buf);
+    }
 }
@@ -1,6 +1,7 @@
 #include "unary_op_macros.cuh"
 
 struct ReLUKernelOp {};
+struct InPlaceReLUKernelOp {};
 
 template<typename T>
 __device__ __forceinline__ T relu_fwd(T x) {
@@ -26,3 +27,16 @@ UNARY_OP(float, relu_fwd_f32, relu_bwd_f32, ReLUKernelOp,
 UNARY_OP(double, relu_fwd_f64, relu_bwd_f64, ReLUKernelOp,
         relu_fwd(x),
         relu_bwd(x))
+
+// relu(x) > 0 exactly where x > 0, so the derivative can be computed from the output
+UNARY_OP(__half, relu_in_place_fwd_f16, relu_in_place_bwd_f16, InPlaceReLUKernelOp,
+        relu_fwd(x),
+        relu_bwd(y))
+
+UNARY_OP(float, relu_in_place_fwd_f32, relu_in_place_bwd_f32, InPlaceReLUKernelOp,
+        relu_fwd(x),
+        relu_bwd(y))
+
+UNARY_OP(double, relu_in_place_fwd_f64, relu_in_place_bwd_f64, InPlaceReLUKernelOp,
+        relu_fwd(x),
+        relu_bwd(y))
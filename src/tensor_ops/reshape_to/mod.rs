@@ -40,6 +40,14 @@ pub trait ReshapeKernel<E: Dtype>: Storage<E> {
 /// let t: Tensor<Rank1<7>, f32, _> = t.reshape();
 /// ```
 ///
+/// Flattening any compile time shape to 1d, with the size inferred from the output type:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank3<2, 3, 4>, f32, _> = dev.zeros();
+/// let t: Tensor<Rank1<24>, f32, _> = t.flatten_all();
+/// ```
+///
 /// Runtime reshapes:
 /// ```rust
 /// # use dfdx::prelude::*;
@@ -64,6 +72,21 @@ pub trait ReshapeTo: HasErr + HasShape {
         <Self::Shape as AssertSameNumel<Dst>>::assert_same_numel();
         self.try_reshape_like::<Dst>(&Default::default())
     }
+    /// Flattens a tensor of any compile time shape into a 1d tensor with `N` elements.
+    /// `N` must be the number of elements in the tensor, which is checked at compile time.
+    fn flatten_all<const N: usize>(self) -> Self::WithShape<Rank1<N>>
+    where
+        Self::Shape: ConstShape,
+    {
+        self.reshape()
+    }
+    /// See [ReshapeTo::flatten_all]
+    fn try_flatten_all<const N: usize>(self) -> Result<Self::WithShape<Rank1<N>>, Self::Err>
+    where
+        Self::Shape: ConstShape,
+    {
+        self.try_reshape()
+    }
     /// Reshapes a tensor to a different runtime shape.
     fn reshape_like<Dst: Shape>(self, dst: &Dst) -> Self::WithShape<Dst> {
         self.try_reshape_like(dst).unwrap()
@@ -221,6 +244,27 @@ mod tests {
         assert_close_to_literal!(b, [[1., 2.], [3., 1.], [2., 3.]]);
     }
 
+    #[test]
+    fn test_flatten_all() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank1<24>, TestDtype, _, _> = a.leaky_trace().flatten_all();
+        assert_eq!(b.as_vec(), a.as_vec());
+
+        let w: Tensor<Rank1<24>, TestDtype, _> = dev.sample_normal();
+        let g = (b * w.clone()).sum().backward();
+        let g_a: Tensor<Rank3<2, 3, 4>, TestDtype, _> = g.get(&a);
+        assert_eq!(g_a.as_vec(), w.as_vec());
+
+        // non-contiguous inputs are flattened in logical order
+        let c: Tensor<Rank1<24>, TestDtype, _, _> =
+            a.leaky_trace().permute::<Rank3<4, 3, 2>, _>().flatten_all();
+        let g = (c * w.clone()).sum().backward();
+        let w_t: Tensor<Rank3<2, 3, 4>, TestDtype, _> =
+            w.reshape::<Rank3<4, 3, 2>>().permute().contiguous();
+        assert_eq!(g.get(&a).array(), w_t.array());
+    }
+
     #[test]
     fn test_contiguous() {
         let dev: TestDevice = Default::default();
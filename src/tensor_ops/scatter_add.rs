@@ -0,0 +1,164 @@
+use super::{
+    axpy::AxpyKernel, reshape_to::ReshapeKernel, select_and_gather::ReplaceDimKernel, ReshapeTo,
+};
+use crate::{shapes::*, tensor::*};
+
+use std::sync::Arc;
+
+/// Adds each slice of `src` into the slice of `into` given by `idx`, accumulating
+/// slices that have the same index. This is the reverse of [super::GatherTo::gather()]:
+/// the shapes of `into`, `idx`, and `src` are the same as the input, index, and output
+/// of a gather.
+///
+/// The gradient of `into` is the gradient of the output, and the gradient of each
+/// slice of `src` is the gradient of the slice it was added to.
+///
+/// Adding rows of a 2d tensor:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let into: Tensor<Rank2<3, 2>, f32, _> = dev.zeros();
+/// let src = dev.tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+/// let idx = dev.tensor([2, 0, 2]);
+/// let out = scatter_add(into, idx, src);
+/// assert_eq!(out.array(), [[3.0, 4.0], [0.0, 0.0], [6.0, 8.0]]);
+/// ```
+pub fn scatter_add<S, Src: Shape, Idx: Shape, E: Dtype, D, T, R>(
+    into: Tensor<S, E, D, T>,
+    idx: Tensor<Idx, usize, D>,
+    src: Tensor<Src, E, D, R>,
+) -> Tensor<S, E, D, T>
+where
+    S: Shape + ReplaceDimTo<Src, Idx>,
+    D: ReplaceDimKernel<E> + AxpyKernel<E> + ReshapeKernel<E>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    try_scatter_add(into, idx, src).unwrap()
+}
+
+/// Fallible version of [scatter_add()]
+pub fn try_scatter_add<S, Src: Shape, Idx: Shape, E: Dtype, D, T, R>(
+    into: Tensor<S, E, D, T>,
+    idx: Tensor<Idx, usize, D>,
+    src: Tensor<Src, E, D, R>,
+) -> Result<Tensor<S, E, D, T>, D::Err>
+where
+    S: Shape + ReplaceDimTo<Src, Idx>,
+    D: ReplaceDimKernel<E> + AxpyKernel<E> + ReshapeKernel<E>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    into.shape.check(&idx.shape);
+    assert_eq!(
+        into.shape.replace(idx.shape).concrete(),
+        src.shape.concrete()
+    );
+
+    // gradients are accumulated with axpy, so both need the layout of the output
+    let (into, into_tape) = into.try_contiguous()?.split_tape();
+    let (src, src_tape) = src.try_contiguous()?.split_tape();
+    let mut tape = into_tape.merge(src_tape);
+
+    // the backward of gather adds `src` into the gathered positions
+    let mut data = into.data.as_ref().clone();
+    ReplaceDimKernel::backward(
+        &into.device,
+        &into,
+        &mut data,
+        &idx,
+        &src,
+        src.data.as_ref(),
+    )?;
+    let out = Tensor {
+        id: unique_id(),
+        data: Arc::new(data),
+        shape: into.shape,
+        strides: into.strides,
+        device: into.device.clone(),
+        tape: NoneTape,
+    };
+
+    let into_ghost = into.ghost();
+    let src_ghost = src.ghost();
+    let out_ghost = out.ghost();
+    tape.add_backward_op(move |grads| {
+        grads.try_alloc_for(&into_ghost)?;
+        grads.try_alloc_for(&src_ghost)?;
+        grads.try_alloc_for(&out_ghost)?;
+        let (grad_into, grad_src, grad_out) =
+            grads.muts_and_ref(&into_ghost, &src_ghost, &out_ghost);
+        AxpyKernel::forward(&into.device, grad_into, E::ONE, grad_out, E::ONE)?;
+        let grad_out = Tensor {
+            id: unique_id(),
+            data: Arc::new(grad_out.clone()),
+            shape: into.shape,
+            strides: into.strides,
+            device: into.device.clone(),
+            tape: NoneTape,
+        };
+        let grad_gathered: Tensor<Src, E, D> =
+            ReplaceDimKernel::forward(&into.device, &grad_out, &idx)?;
+        AxpyKernel::forward(
+            &into.device,
+            grad_src,
+            E::ONE,
+            grad_gathered.data.as_ref(),
+            E::ONE,
+        )
+    });
+    Ok(out.put_tape(tape))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_scatter_add_duplicate_indices() {
+        let dev: TestDevice = Default::default();
+        let into: Tensor<Rank2<3, 2>, TestDtype, _> = dev
+            .tensor([[1.0, 1.0], [2.0, 2.0], [3.0, 3.0]])
+            .to_dtype::<TestDtype>();
+        let src: Tensor<Rank2<4, 2>, TestDtype, _> = dev
+            .tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0], [7.0, 8.0]])
+            .to_dtype::<TestDtype>();
+        let idx = dev.tensor([2, 0, 2, 2]);
+
+        let out = scatter_add(into.leaky_trace(), idx, src.leaky_trace());
+        assert_close_to_literal!(out, [[4.0, 5.0], [2.0, 2.0], [16.0, 19.0]]);
+
+        let w = dev
+            .tensor([[1.0, -1.0], [2.0, -2.0], [3.0, -3.0]])
+            .to_dtype::<TestDtype>();
+        let g = (out * w.clone()).sum().backward();
+        assert_close_to_tensor!(g.get(&into), w);
+        assert_close_to_literal!(
+            g.get(&src),
+            [[3.0, -3.0], [1.0, -1.0], [3.0, -3.0], [3.0, -3.0]]
+        );
+    }
+
+    #[test]
+    fn test_scatter_add_last_axis_broadcasted() {
+        let dev: TestDevice = Default::default();
+        let a = dev.tensor([1.0, 2.0, 3.0]).to_dtype::<TestDtype>();
+        let src = dev
+            .tensor([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]])
+            .to_dtype::<TestDtype>();
+        let idx = dev.tensor([[0, 2, 2, 1], [1, 1, 0, 0]]);
+
+        let into: Tensor<Rank2<2, 3>, _, _, _> = a.leaky_trace().broadcast();
+        let out = scatter_add(into, idx.clone(), src.leaky_trace());
+        assert_close_to_literal!(out, [[2.0, 6.0, 8.0], [16.0, 13.0, 3.0]]);
+
+        let w = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let g = (out * w.clone()).sum().backward();
+        assert_close_to_literal!(g.get(&a), [5.0, 7.0, 9.0]);
+        let w_gathered: Tensor<Rank2<2, 4>, TestDtype, _> = w.gather(idx);
+        assert_close_to_tensor!(g.get(&src), w_gathered);
+    }
+}
@@ -118,6 +118,46 @@ impl<Src: Shape, E: Dtype, D: RemoveDimKernel<E>, T: Tape<E, D>> SelectTo<E, D>
     }
 }
 
+impl<Src: Shape, E: Dtype, D: RemoveDimKernel<E> + TensorFromVec<usize>, T: Tape<E, D>>
+    Tensor<Src, E, D, T>
+{
+    /// Selects index `i` of the 0th axis, removing that axis from the shape. For example
+    /// this extracts row `i` of a 2d tensor. Gradients are only propagated to the selected
+    /// entries.
+    ///
+    /// This is a shorthand for [SelectTo::select] with a scalar index.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// let r: Tensor<Rank1<3>, f32, _> = t.index(1);
+    /// assert_eq!(r.array(), [4.0, 5.0, 6.0]);
+    /// ```
+    ///
+    /// **Panics** if `i` is out of bounds.
+    pub fn index<Dst: Shape>(self, i: usize) -> Tensor<Dst, E, D, T>
+    where
+        Src: RemoveDimTo<Dst, ()>,
+    {
+        self.try_index(i).unwrap()
+    }
+
+    /// Fallible version of [Tensor::index]
+    pub fn try_index<Dst: Shape>(self, i: usize) -> Result<Tensor<Dst, E, D, T>, D::Err>
+    where
+        Src: RemoveDimTo<Dst, ()>,
+    {
+        let size = self.shape.concrete()[0];
+        assert!(
+            i < size,
+            "index {i} is out of bounds for axis 0 of size {size}"
+        );
+        let idx = self.device.try_tensor_from_vec(std::vec![i], ())?;
+        self.try_select(idx)
+    }
+}
+
 /// Select multiple values from a single axis, replacing that dimension
 /// with a different one. Equivalent to `torch.gather` from pytorch.
 ///
@@ -191,6 +231,43 @@ impl<Src: Shape, E: Dtype, D: ReplaceDimKernel<E>, T: Tape<E, D>> GatherTo<E, D>
     }
 }
 
+impl<Src: Shape, E: Dtype, D: ReplaceDimKernel<E> + TensorFromVec<usize>, T: Tape<E, D>>
+    Tensor<Src, E, D, T>
+{
+    /// Gathers the rows `idx` of the 0th axis, e.g. looking up rows of an embedding table.
+    /// Rows may be selected more than once, in which case their gradients are accumulated.
+    ///
+    /// This is a shorthand for [GatherTo::gather] with a `(Const<S>,)` index.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+    /// let r: Tensor<Rank2<3, 2>, f32, _> = t.select_rows(&[2, 0, 2]);
+    /// assert_eq!(r.array(), [[5.0, 6.0], [1.0, 2.0], [5.0, 6.0]]);
+    /// ```
+    ///
+    /// **Panics** if any index is out of bounds.
+    pub fn select_rows<Dst: Shape, const S: usize>(self, idx: &[usize; S]) -> Tensor<Dst, E, D, T>
+    where
+        Src: ReplaceDimTo<Dst, Rank1<S>>,
+    {
+        self.try_select_rows(idx).unwrap()
+    }
+
+    /// Fallible version of [Tensor::select_rows]
+    pub fn try_select_rows<Dst: Shape, const S: usize>(
+        self,
+        idx: &[usize; S],
+    ) -> Result<Tensor<Dst, E, D, T>, D::Err>
+    where
+        Src: ReplaceDimTo<Dst, Rank1<S>>,
+    {
+        let idx = self.device.try_tensor_from_vec(idx.to_vec(), (Const,))?;
+        self.try_gather(idx)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -273,6 +350,38 @@ mod tests {
         assert_eq!(g.get(&t).array(), expected);
     }
 
+    #[test]
+    fn test_index_row_backward() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let r: Tensor<Rank1<4>, TestDtype, _, _> = t.leaky_trace().index(1);
+        let t_array = t.array();
+        assert_eq!(r.array(), t_array[1]);
+        let g = r.sum().backward();
+        let mut expected = [[TestDtype::zero(); 4]; 3];
+        expected[1] = [TestDtype::ONE; 4];
+        assert_eq!(g.get(&t).array(), expected);
+    }
+
+    #[test]
+    fn test_select_rows_backward() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let r: Tensor<Rank2<3, 4>, TestDtype, _, _> = t.leaky_trace().select_rows(&[2, 0, 2]);
+        let t_array = t.array();
+        assert_eq!(r.array(), [t_array[2], t_array[0], t_array[2]]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [[1.0; 4], [0.0; 4], [2.0; 4]]);
+    }
+
+    #[test]
+    #[should_panic = "index 3 is out of bounds for axis 0 of size 3"]
+    fn test_index_out_of_bounds() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let _: Tensor<Rank1<4>, TestDtype, _> = t.index(3);
+    }
+
     #[test]
     fn test_gather_1d_backward() {
         let dev: TestDevice = Default::default();
@@ -0,0 +1,66 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::*,
+};
+
+use num_traits::Float;
+use std::sync::Arc;
+
+impl<E: Dtype + Float> super::SoftmaxKernel<E> for Cpu {
+    fn forward<S: Shape>(&self, inp: &Tensor<S, E, Self>) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let row_len = inp.shape.concrete()[S::NUM_DIMS - 1];
+        let mut out = self.try_alloc_zeros::<E>(inp.shape.num_elements())?;
+        if row_len > 0 {
+            for (x, y) in inp
+                .data
+                .chunks_exact(row_len)
+                .zip(out.chunks_exact_mut(row_len))
+            {
+                let max = x.iter().fold(E::neg_infinity(), |m, &x_i| m.max(x_i));
+                let mut sum = E::zero();
+                for (y_i, &x_i) in y.iter_mut().zip(x.iter()) {
+                    *y_i = (x_i - max).exp();
+                    sum += *y_i;
+                }
+                for y_i in y.iter_mut() {
+                    *y_i /= sum;
+                }
+            }
+        }
+        Ok(Tensor {
+            id: unique_id(),
+            data: Arc::new(out),
+            shape: inp.shape,
+            strides: inp.strides,
+            device: self.clone(),
+            tape: Default::default(),
+        })
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        out: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec,
+        grad_out: &Self::Vec,
+    ) -> Result<(), Self::Err> {
+        let row_len = out.shape.concrete()[S::NUM_DIMS - 1];
+        if row_len == 0 {
+            return Ok(());
+        }
+        let rows = out
+            .data
+            .chunks_exact(row_len)
+            .zip(grad_inp.chunks_exact_mut(row_len))
+            .zip(grad_out.chunks_exact(row_len));
+        for ((y, gx), gy) in rows {
+            let dot = y
+                .iter()
+                .zip(gy.iter())
+                .fold(E::zero(), |acc, (&y_i, &gy_i)| acc + y_i * gy_i);
+            for ((gx_i, &y_i), &gy_i) in gx.iter_mut().zip(y.iter()).zip(gy.iter()) {
+                *gx_i += y_i * (gy_i - dot);
+            }
+        }
+        Ok(())
+    }
+}
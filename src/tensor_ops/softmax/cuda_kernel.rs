@@ -0,0 +1,63 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::*,
+};
+
+use cudarc::driver::{DeviceSlice, LaunchAsync};
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/softmax.ptx"));
+
+trait HasCudaKernel<E> {
+    const FNS: &'static [&'static str];
+}
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const FNS: &'static [&'static str] = &["softmax_fwd_f16", "softmax_bwd_f16"];
+}
+impl HasCudaKernel<f32> for Cuda {
+    const FNS: &'static [&'static str] = &["softmax_fwd_f32", "softmax_bwd_f32"];
+}
+impl HasCudaKernel<f64> for Cuda {
+    const FNS: &'static [&'static str] = &["softmax_fwd_f64", "softmax_bwd_f64"];
+}
+
+impl<E: Dtype> super::SoftmaxKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<S: Shape>(&self, inp: &Tensor<S, E, Self>) -> Result<Tensor<S, E, Self>, Self::Err> {
+        if !self.dev.has_func(Self::FNS[0], Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::FNS[0], Self::FNS)?;
+        }
+
+        let numel = inp.shape.num_elements();
+        let row_len = inp.shape.concrete()[S::NUM_DIMS - 1];
+        let mut out = unsafe { self.alloc_empty::<E>(numel) }?;
+        if row_len > 0 {
+            let num_rows = numel / row_len;
+            let fwd = self.dev.get_func(Self::FNS[0], Self::FNS[0]).unwrap();
+            let cfg = launch_cfg::<128>(num_rows as u32);
+            let params = (num_rows, row_len, inp.data.as_ref(), &mut out);
+            unsafe { fwd.launch(cfg, params) }?;
+        }
+        Ok(self.build_tensor(inp.shape, inp.strides, out))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        out: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec,
+        grad_out: &Self::Vec,
+    ) -> Result<(), Self::Err> {
+        let row_len = out.shape.concrete()[S::NUM_DIMS - 1];
+        if row_len == 0 {
+            return Ok(());
+        }
+        let num_rows = grad_inp.len() / row_len;
+        let bwd = self.dev.get_func(Self::FNS[0], Self::FNS[1]).unwrap();
+        let cfg = launch_cfg::<128>(num_rows as u32);
+        let params = (num_rows, row_len, out.data.as_ref(), grad_inp, grad_out);
+        unsafe { bwd.launch(cfg, params) }?;
+        Ok(())
+    }
+}
@@ -1,11 +1,30 @@
 use super::*;
 use crate::{shapes::*, tensor::*};
 
+mod cpu_kernel;
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+/// Softmax across the last axis of a tensor with standard (contiguous) strides, as a
+/// single kernel.
+pub trait SoftmaxKernel<E: Dtype>: Storage<E> {
+    fn forward<S: Shape>(&self, inp: &Tensor<S, E, Self>) -> Result<Tensor<S, E, Self>, Self::Err>;
+    fn backward<S: Shape>(
+        &self,
+        out: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec,
+        grad_out: &Self::Vec,
+    ) -> Result<(), Self::Err>;
+}
+
 /// Computes the [softmax function](https://en.wikipedia.org/wiki/Softmax_function) across
 /// `Ax`.
 ///
 /// Equivalent to `exp(log_softmax(t))`.
 ///
+/// Softmax over the last axis of a tensor with standard strides (e.g. attention scores)
+/// runs as a single fused kernel, instead of separate max, exp, sum, and div ops.
+///
 /// **Pytorch equivalent**: `t.softmax(Axes)`
 ///
 /// Example:
@@ -24,7 +43,59 @@ where
     t.softmax::<Ax>()
 }
 
+/// [softmax()] across `Ax`, where every probability is at least `eps`, for when exactly
+/// zero probabilities would break something downstream, like a `ln()`.
+///
+/// This mixes the softmax with a uniform distribution over the `N` elements of `Ax`:
+/// `softmax(t) * (1 - N * eps) + eps`, so the outputs still sum to one. `eps` must be
+/// less than `1 / N`.
+///
+/// Prefer [log_softmax()] when all that's needed is the log of the probabilities, since
+/// it is exact and never produces `-inf` for finite inputs.
+///
+/// Example:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([0.0, 0.0, -1000.0]);
+/// let p = t.softmax_with_eps::<Axis<0>>(1e-3);
+/// assert!(p.array()[2] >= 1e-3);
+/// ```
+pub fn softmax_with_eps<Ax: Axes, S, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    eps: impl Into<f64>,
+) -> Tensor<S, E, D, T>
+where
+    S: Shape + ReduceShape<Ax>,
+{
+    t.softmax_with_eps::<Ax>(eps)
+}
+
 impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [softmax_with_eps()]
+    pub fn softmax_with_eps<Ax: Axes>(self, eps: impl Into<f64>) -> Self
+    where
+        S: ReduceShape<Ax>,
+    {
+        self.try_softmax_with_eps::<Ax>(eps).unwrap()
+    }
+    /// See [softmax_with_eps()]
+    pub fn try_softmax_with_eps<Ax: Axes>(self, eps: impl Into<f64>) -> Result<Self, D::Err>
+    where
+        S: ReduceShape<Ax>,
+    {
+        let eps = eps.into();
+        let n = <S as HasAxes<Ax>>::size(self.shape()) as f64;
+        let scale = 1.0 - n * eps;
+        assert!(
+            eps >= 0.0 && scale > 0.0,
+            "eps must be in [0, 1 / {n}), found {eps}"
+        );
+        self.try_softmax::<Ax>()?
+            .try_mul(E::from_f64(scale).unwrap())?
+            .try_add(E::from_f64(eps).unwrap())
+    }
+
     /// See [softmax()]
     pub fn softmax<Ax: Axes>(self) -> Self
     where
@@ -67,6 +138,11 @@ impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
         fused
             `tme / tme.sum()`
         */
+        let last_axis = S::NUM_DIMS as isize - 1;
+        if Ax::as_array().into_iter().eq([last_axis]) && self.strides == self.shape.strides() {
+            return self.try_softmax_last_axis();
+        }
+
         let shape = *self.shape();
         let (t, tape) = self.split_tape();
         let max = t.clone().try_max::<_, Ax>()?;
@@ -82,6 +158,22 @@ impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
         let t_expsum = t_exp.retaped::<T>().try_sum::<_, Ax>()?;
         t_exp.try_div(t_expsum.try_broadcast_like(&shape)?)
     }
+
+    /// Fused softmax over the last axis. Requires standard strides.
+    fn try_softmax_last_axis(self) -> Result<Self, D::Err> {
+        let (inp, mut tape) = self.split_tape();
+        let out = SoftmaxKernel::forward(&inp.device, &inp)?;
+        let inp_ghost = inp.ghost();
+        let out_ghost = out.ghost();
+        let out_clone = out.clone();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&inp_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+            SoftmaxKernel::backward(&inp.device, &out_clone, grad_inp, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
 }
 
 #[cfg(test)]
@@ -110,6 +202,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_softmax_fused_matches_unfused() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<6, 5>, TestDtype, _> = dev.sample_normal();
+        // softmax over the first axis uses the unfused ops
+        let p_truth = t.leaky_trace().softmax::<Axis<0>>();
+        let t_tr: Tensor<Rank2<5, 6>, TestDtype, _> = dev.tensor(t.clone().permute().array());
+        let p = t_tr.leaky_trace().softmax::<Axis<1>>();
+        assert_close_to_tensor!(p, p_truth.retaped::<NoneTape>().permute());
+
+        let w: Tensor<Rank2<6, 5>, TestDtype, _> = dev.sample_normal();
+        let g_truth = (p_truth * w.clone()).exp().mean().backward();
+        let g = (p * w.permute()).exp().mean().backward();
+        assert_close_to_tensor!(g.get(&t_tr), g_truth.get(&t).permute());
+    }
+
+    #[test]
+    fn test_softmax_with_eps() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[0.0, 1.0, -100.0, 2.0], [-50.0, -50.0, 50.0, -50.0]])
+            .to_dtype::<TestDtype>();
+        let p = t.leaky_trace().softmax_with_eps::<Axis<1>>(0.01);
+        for row in p.array() {
+            assert!(row.iter().all(|&v| v >= 0.01));
+        }
+        assert_close_to_literal!(p.retaped::<NoneTape>().sum::<Rank1<2>, _>(), [1.0; 2]);
+
+        // the gradient is the softmax gradient scaled by 1 - 4 * eps
+        let w = dev
+            .tensor([[1.0, -2.0, 0.5, 3.0], [0.0, 1.0, 2.0, -1.0]])
+            .to_dtype::<TestDtype>();
+        let g = (p * w.clone()).sum().backward();
+        let g_truth = (t.leaky_trace().softmax::<Axis<1>>() * w).sum().backward();
+        assert_close_to_tensor!(g.get(&t), g_truth.get(&t) * 0.96);
+    }
+
+    #[test]
+    #[should_panic = "eps must be in [0, 1 / 4)"]
+    fn test_softmax_with_eps_too_large() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<4>, TestDtype, _> = dev.zeros();
+        let _ = t.softmax_with_eps::<Axis<0>>(0.25);
+    }
+
     #[test]
     fn test_softmax_1d() {
         let dev: TestDevice = Default::default();
@@ -194,6 +331,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_softmax_any_axis_finite_differences() {
+        let dev: TestDevice = Default::default();
+
+        // over axis 0, every column sums to 1
+        let a: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let r = a.softmax::<Axis<0>>();
+        assert_close_to_literal!(r.sum::<Rank1<3>, _>(), [1.0; 3]);
+
+        // over the middle axis (e.g. across heads) of a 3d tensor
+        let t: Tensor<Rank3<2, 3, 4>, f64, _> = dev.sample_normal();
+        let w: Tensor<Rank3<2, 3, 4>, f64, _> = dev.sample_normal();
+        let r = t.clone().softmax::<Axis<1>>();
+        assert_close_to_literal!(r.sum::<Rank2<2, 4>, _>(), [[1.0; 4]; 2]);
+
+        let loss = |t: Tensor<Rank3<2, 3, 4>, f64, _>| {
+            (t.softmax::<Axis<1>>() * w.clone())
+                .square()
+                .sum::<Rank0, _>()
+                .array()
+        };
+        let g = (t.leaky_trace().softmax::<Axis<1>>() * w.clone())
+            .square()
+            .sum()
+            .backward();
+        let grad = g.get(&t).as_vec();
+        let data = t.as_vec();
+        let eps = 1e-6;
+        for i in 0..data.len() {
+            let mut plus = data.clone();
+            plus[i] += eps;
+            let mut minus = data.clone();
+            minus[i] -= eps;
+            let fd: f64 = (loss(dev.tensor_from_vec(plus, t.shape))
+                - loss(dev.tensor_from_vec(minus, t.shape)))
+                / (2.0 * eps);
+            assert!((fd - grad[i]).abs() < 1e-6, "{fd} != {}", grad[i]);
+        }
+    }
+
     #[test]
     fn test_softmax_3d_to_1d_12() {
         let dev: TestDevice = Default::default();
@@ -0,0 +1,73 @@
+#include "cuda_utils.cuh"
+
+// One thread per row of the last axis.
+template<typename T>
+__device__ void softmax_fwd(
+    const size_t num_rows,
+    const size_t row_len,
+    const T *inp,
+    T *out
+) {
+    for (size_t row = blockIdx.x * blockDim.x + threadIdx.x; row < num_rows; row += blockDim.x * gridDim.x) {
+        const T *x = inp + row * row_len;
+        T *y = out + row * row_len;
+
+        T max = x[0];
+        for (size_t i = 1; i < row_len; i++) {
+            max = maxg(max, x[i]);
+        }
+
+        T sum = 0.0;
+        for (size_t i = 0; i < row_len; i++) {
+            y[i] = expg(x[i] - max);
+            sum += y[i];
+        }
+
+        for (size_t i = 0; i < row_len; i++) {
+            y[i] /= sum;
+        }
+    }
+}
+
+template<typename T>
+__device__ void softmax_bwd(
+    const size_t num_rows,
+    const size_t row_len,
+    const T *out,
+    T *grad_inp,
+    const T *grad_out
+) {
+    for (size_t row = blockIdx.x * blockDim.x + threadIdx.x; row < num_rows; row += blockDim.x * gridDim.x) {
+        const T *y = out + row * row_len;
+        const T *gy = grad_out + row * row_len;
+        T *gx = grad_inp + row * row_len;
+
+        T dot = 0.0;
+        for (size_t i = 0; i < row_len; i++) {
+            dot += y[i] * gy[i];
+        }
+
+        for (size_t i = 0; i < row_len; i++) {
+            gx[i] += y[i] * (gy[i] - dot);
+        }
+    }
+}
+
+#define SOFTMAX(TY, FWD, BWD) \
+extern "C" __global__ void FWD( \
+    const size_t num_rows, \
+    const size_t row_len, \
+    const TY *inp, \
+    TY *out \
+) { softmax_fwd(num_rows, row_len, inp, out); } \
+extern "C" __global__ void BWD( \
+    const size_t num_rows, \
+    const size_t row_len, \
+    const TY *out, \
+    TY *grad_inp, \
+    const TY *grad_out \
+) { softmax_bwd(num_rows, row_len, out, grad_inp, grad_out); }
+
+SOFTMAX(__half, softmax_fwd_f16, softmax_bwd_f16);
+SOFTMAX(float, softmax_fwd_f32, softmax_bwd_f32);
+SOFTMAX(double, softmax_fwd_f64, softmax_bwd_f64);
@@ -0,0 +1,189 @@
+use crate::{
+    shapes::{Const, Dim, Dtype},
+    tensor::{Tape, Tensor, WithEmptyTape},
+};
+
+use super::{Device, ReshapeTo};
+
+/// Splits the rows of a `(M, N)` tensor into a `(A, N)` tensor holding the first `A` rows,
+/// and a `(B, N)` tensor holding the remaining `B` rows. This is the inverse of concatenating
+/// along axis 0, and is useful for GLU style gating or multi output heads.
+///
+/// The gradient of each output is routed back to the rows it was sliced from. Operations
+/// recorded on the input's tape are kept in the first output's tape.
+///
+/// **Panics** if `M` is not `A + B`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<3, 2>, f32, _> = dev.tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+/// let (a, b) = t.split::<1, 2>();
+/// assert_eq!(a.array(), [[1.0, 2.0]]);
+/// assert_eq!(b.array(), [[3.0, 4.0], [5.0, 6.0]]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn split<const A: usize, const B: usize, M: Dim, N: Dim, E: Dtype, D: Device<E>, T>(
+    t: Tensor<(M, N), E, D, T>,
+) -> (
+    Tensor<(Const<A>, N), E, D, T>,
+    Tensor<(Const<B>, N), E, D, T>,
+)
+where
+    T: Tape<E, D>,
+{
+    t.split::<A, B>()
+}
+
+/// Splits the columns of a `(M, N)` tensor into `C` equal chunks of width `N / C`, for example
+/// to separate the halves of a gated unit.
+///
+/// The gradient of each chunk is routed back to the columns it was sliced from. Operations
+/// recorded on the input's tape are kept in the first chunk's tape.
+///
+/// **Panics** if `N` is not divisible by `C`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 4>, f32, _> = dev.tensor([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+/// let [a, b] = t.chunk::<2>();
+/// assert_eq!(a.as_vec(), [1.0, 2.0, 5.0, 6.0]);
+/// assert_eq!(b.as_vec(), [3.0, 4.0, 7.0, 8.0]);
+/// ```
+pub fn chunk<const C: usize, M: Dim, N: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<(M, N), E, D, T>,
+) -> [Tensor<(M, usize), E, D, T>; C] {
+    t.chunk::<C>()
+}
+
+impl<M: Dim, N: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(M, N), E, D, T> {
+    /// See [split]
+    #[allow(clippy::type_complexity)]
+    pub fn split<const A: usize, const B: usize>(
+        self,
+    ) -> (
+        Tensor<(Const<A>, N), E, D, T>,
+        Tensor<(Const<B>, N), E, D, T>,
+    ) {
+        self.try_split().unwrap()
+    }
+
+    /// See [split]
+    #[allow(clippy::type_complexity)]
+    pub fn try_split<const A: usize, const B: usize>(
+        self,
+    ) -> Result<
+        (
+            Tensor<(Const<A>, N), E, D, T>,
+            Tensor<(Const<B>, N), E, D, T>,
+        ),
+        D::Err,
+    > {
+        let (m, n) = self.shape;
+        assert_eq!(
+            m.size(),
+            A + B,
+            "split sizes must add up to the number of rows"
+        );
+        let b = self
+            .with_empty_tape()
+            .try_slice((A..A + B, ..))?
+            .try_reshape_like(&(Const, n))?;
+        let a = self.try_slice((0..A, ..))?.try_reshape_like(&(Const, n))?;
+        Ok((a, b))
+    }
+
+    /// See [chunk]
+    pub fn chunk<const C: usize>(self) -> [Tensor<(M, usize), E, D, T>; C] {
+        self.try_chunk().unwrap()
+    }
+
+    /// See [chunk]
+    #[allow(clippy::type_complexity)]
+    pub fn try_chunk<const C: usize>(self) -> Result<[Tensor<(M, usize), E, D, T>; C], D::Err> {
+        let n = self.shape.1.size();
+        assert!(
+            C > 0 && n % C == 0,
+            "{n} columns can't be split into {C} equal chunks"
+        );
+        let width = n / C;
+        let mut chunks = std::vec::Vec::with_capacity(C);
+        for i in 1..C {
+            chunks.push(
+                self.with_empty_tape()
+                    .try_slice((.., i * width..(i + 1) * width))?,
+            );
+        }
+        chunks.insert(0, self.try_slice((.., 0..width))?);
+        Ok(chunks
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("exactly C chunks were created")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_split_rows() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0], [7.0, 8.0], [9.0, 10.0]])
+            .to_dtype::<TestDtype>();
+        let (a, b) = t.leaky_trace().split::<2, 3>();
+        assert_close_to_literal!(a, [[1.0, 2.0], [3.0, 4.0]]);
+        assert_close_to_literal!(b, [[5.0, 6.0], [7.0, 8.0], [9.0, 10.0]]);
+
+        // scale each output differently so the gradient shows where each row went
+        let g = ((a * 2.0).sum() + (b * -3.0).sum()).backward();
+        assert_close_to_literal!(
+            g.get(&t),
+            [[2.0; 2], [2.0; 2], [-3.0; 2], [-3.0; 2], [-3.0; 2]]
+        );
+    }
+
+    #[test]
+    fn test_chunk_columns() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([
+                [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+                [7.0, 8.0, 9.0, 10.0, 11.0, 12.0],
+            ])
+            .to_dtype::<TestDtype>();
+        let [a, b, c] = t.leaky_trace().chunk::<3>();
+        assert_eq!(a.shape(), &(Const::<2>, 2));
+        let [a, b, c] = [a, b, c].map(|x| x.realize::<Rank2<2, 2>>());
+        assert_close_to_literal!(a, [[1.0, 2.0], [7.0, 8.0]]);
+        assert_close_to_literal!(b, [[3.0, 4.0], [9.0, 10.0]]);
+        assert_close_to_literal!(c, [[5.0, 6.0], [11.0, 12.0]]);
+
+        // recombining the chunks gives back the gradient of the whole tensor
+        let g = ((a * 1.0).sum() + (b * 2.0).sum() + (c * 3.0).sum()).backward();
+        assert_close_to_literal!(
+            g.get(&t),
+            [
+                [1.0, 1.0, 2.0, 2.0, 3.0, 3.0],
+                [1.0, 1.0, 2.0, 2.0, 3.0, 3.0]
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic = "6 columns can't be split into 4 equal chunks"]
+    fn test_chunk_uneven() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 6>, TestDtype, _> = dev.zeros();
+        let _ = t.chunk::<4>();
+    }
+
+    #[test]
+    #[should_panic = "split sizes must add up to the number of rows"]
+    fn test_split_wrong_sizes() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<5, 2>, TestDtype, _> = dev.zeros();
+        let _ = t.split::<2, 2>();
+    }
+}
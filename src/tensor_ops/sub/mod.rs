@@ -3,7 +3,7 @@ mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
 
-use super::ops::*;
+use super::{axpy::AxpyKernel, ops::*};
 use crate::{shapes::*, tensor::*};
 
 #[repr(C)]
@@ -90,8 +90,50 @@ where
     }
 }
 
+impl<S: Shape, E: Dtype, D: BinaryKernel<BinarySubKernelOp, E>> Tensor<S, E, D> {
+    /// Computes `self - rhs` and writes the result into `out`, re-using its allocation
+    /// instead of allocating a new tensor. Nothing is recorded on a tape. See [sub].
+    ///
+    /// `out` must be contiguous and have the same shape as `self` and `rhs`.
+    pub fn sub_into(&self, rhs: &Self, out: &mut Self) {
+        self.try_sub_into(rhs, out).unwrap()
+    }
+
+    /// Fallible version of [Tensor::sub_into]
+    pub fn try_sub_into(&self, rhs: &Self, out: &mut Self) -> Result<(), D::Err> {
+        try_binary_op_into(BinarySubKernelOp, self, rhs, out)
+    }
+}
+
+impl<S: Shape, E: Dtype, D> std::ops::SubAssign for Tensor<S, E, D>
+where
+    D: AxpyKernel<E> + BinaryKernel<BinarySubKernelOp, E>,
+{
+    /// Subtracts `rhs` from `self` in place with [Tensor::axpy], without a tape. If the strides of
+    /// `self` and `rhs` are different (e.g. `rhs` is broadcasted), a new tensor is allocated.
+    fn sub_assign(&mut self, rhs: Self) {
+        *self -= &rhs;
+    }
+}
+
+impl<S: Shape, E: Dtype, D> std::ops::SubAssign<&Tensor<S, E, D>> for Tensor<S, E, D>
+where
+    D: AxpyKernel<E> + BinaryKernel<BinarySubKernelOp, E>,
+{
+    /// Subtracts `rhs` from `self` in place with [Tensor::axpy], without a tape. If the strides of
+    /// `self` and `rhs` are different (e.g. `rhs` is broadcasted), a new tensor is allocated.
+    fn sub_assign(&mut self, rhs: &Tensor<S, E, D>) {
+        if self.strides == rhs.strides {
+            self.axpy(1.0, rhs, -1.0);
+        } else {
+            *self = self.clone() - rhs.clone();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::shapes::*;
     use crate::tensor::*;
     use crate::tensor_ops::*;
     use crate::tests::*;
@@ -169,4 +211,22 @@ mod tests {
         let g = r.exp().sum().backward();
         assert_close_to_literal!(g.get(&x), [[0.36787945; 2]; 3]);
     }
+
+    #[test]
+    fn test_sub_assign() {
+        let dev: TestDevice = Default::default();
+        let mut a = dev.tensor([[1.0, 2.0], [3.0, 4.0]]).to_dtype::<TestDtype>();
+        let b = dev
+            .tensor([[0.5, -1.0], [2.0, 0.0]])
+            .to_dtype::<TestDtype>();
+        a -= &b;
+        assert_close_to_literal!(a, [[0.5, 3.0], [1.0, 4.0]]);
+        a -= b;
+        assert_close_to_literal!(a, [[0.0, 4.0], [-1.0, 4.0]]);
+
+        // a broadcasted rhs falls back to allocating a new tensor
+        let c = dev.tensor([1.0, -2.0]).to_dtype::<TestDtype>();
+        a -= c.broadcast::<Rank2<2, 2>, Axis<0>>();
+        assert_close_to_literal!(a, [[-1.0, 6.0], [-2.0, 6.0]]);
+    }
 }
@@ -0,0 +1,36 @@
+use crate::{
+    shapes::{Dtype, Rank0, Shape},
+    tensor::{Cpu, Tensor, ZerosTensor},
+};
+
+impl<E: Dtype> super::SumOfSquaresKernel<E> for Cpu {
+    fn forward<S: Shape>(
+        &self,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<Rank0, E, Self>, Self::Err> {
+        let mut out = self.try_zeros_like(&())?;
+        // broadcasted axes are not stored, so each value counts `scale` times
+        let scale = E::from_usize(inp.shape.num_elements() / inp.data.len()).unwrap();
+        let mut tmp: E = Default::default();
+        for v in inp.data.iter() {
+            tmp += *v * *v;
+        }
+        std::sync::Arc::get_mut(&mut out.data).unwrap()[0] = tmp * scale;
+        Ok(out)
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        inp: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec,
+        grad_out: &Self::Vec,
+    ) -> Result<(), Self::Err> {
+        debug_assert_eq!(grad_out.len(), 1);
+        let scale = E::from_usize(inp.shape.num_elements() / inp.data.len()).unwrap();
+        let v = grad_out[0] * scale;
+        for (i, x) in grad_inp.iter_mut().zip(inp.data.iter()) {
+            *i += (*x + *x) * v;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    shapes::*,
+    tensor::{launch_cfg, Cuda, Tensor},
+};
+
+use cudarc::driver::{DeviceRepr, DeviceSlice, LaunchAsync, ValidAsZeroBits};
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/sum_of_squares.ptx"));
+
+trait HasCudaKernel<E> {
+    const MOD: &'static str;
+    const FNS: &'static [&'static str];
+}
+
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const MOD: &'static str = "sum_of_squares_f16";
+    const FNS: &'static [&'static str] = &["sum_of_squares_fwd_f16", "sum_of_squares_bwd_f16"];
+}
+
+impl HasCudaKernel<f32> for Cuda {
+    const MOD: &'static str = "sum_of_squares_f32";
+    const FNS: &'static [&'static str] = &["sum_of_squares_fwd_f32", "sum_of_squares_bwd_f32"];
+}
+
+impl HasCudaKernel<f64> for Cuda {
+    const MOD: &'static str = "sum_of_squares_f64";
+    const FNS: &'static [&'static str] = &["sum_of_squares_fwd_f64", "sum_of_squares_bwd_f64"];
+}
+
+impl<E: Dtype + ValidAsZeroBits + DeviceRepr> super::SumOfSquaresKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<S: Shape>(
+        &self,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<Rank0, E, Self>, Self::Err> {
+        if !self.dev.has_func(Self::MOD, Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::MOD, Self::FNS)?;
+        }
+        let fwd_fn = self.dev.get_func(Self::MOD, Self::FNS[0]).unwrap();
+
+        let numel = inp.data.len();
+        let scale = E::from_usize(inp.shape.num_elements() / numel).unwrap();
+
+        let mut storage = unsafe { self.alloc_empty::<E>(1) }?;
+        self.dev.memset_zeros(&mut storage)?;
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (numel, scale, inp.data.as_ref(), &mut storage);
+        unsafe { fwd_fn.launch(cfg, params) }?;
+        Ok(self.build_tensor((), (), storage))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        inp: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec,
+        grad_out: &Self::Vec,
+    ) -> Result<(), Self::Err> {
+        let bwd_fn = self.dev.get_func(Self::MOD, Self::FNS[1]).unwrap();
+
+        let numel = inp.data.len();
+        let scale = E::from_usize(2 * inp.shape.num_elements() / numel).unwrap();
+
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (numel, scale, inp.data.as_ref(), grad_inp, grad_out);
+        unsafe { bwd_fn.launch(cfg, params) }?;
+        Ok(())
+    }
+}
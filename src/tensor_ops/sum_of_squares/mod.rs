@@ -0,0 +1,97 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use crate::{shapes::*, tensor::*};
+
+pub trait SumOfSquaresKernel<E: Dtype>: Storage<E> {
+    fn forward<S: Shape>(
+        &self,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<Rank0, E, Self>, Self::Err>;
+    fn backward<S: Shape>(
+        &self,
+        inp: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec,
+        grad_out: &Self::Vec,
+    ) -> Result<(), Self::Err>;
+}
+
+/// Sum of the squares of all elements, i.e. the squared L2 norm. This is the same as
+/// `t.square().sum()`, but without allocating the squared tensor.
+///
+/// The derivative is `2 * t`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([[1.0, -2.0], [3.0, 0.0]]);
+/// let r = t.sum_of_squares();
+/// assert_eq!(r.array(), 14.0);
+/// ```
+pub fn sum_of_squares<S: Shape, E: Dtype, D: SumOfSquaresKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<Rank0, E, D, T> {
+    t.sum_of_squares()
+}
+
+impl<S: Shape, E: Dtype, D: SumOfSquaresKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [sum_of_squares]
+    pub fn sum_of_squares(self) -> Tensor<Rank0, E, D, T> {
+        self.try_sum_of_squares().unwrap()
+    }
+    /// See [sum_of_squares]
+    pub fn try_sum_of_squares(self) -> Result<Tensor<Rank0, E, D, T>, D::Err> {
+        let (inp, mut tape) = self.split_tape();
+        let out = inp.device.forward(&inp)?;
+        let inp_ghost = inp.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&inp_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+            inp.device.backward(&inp, grad_inp, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_sum_of_squares() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[-2.0, -1.0, 0.5], [1.0, 2.0, 3.0]])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().sum_of_squares();
+        assert_close_to_literal!(r, 19.25);
+        // NOTE: .exp() to make sure its using result grad properly
+        let g = r.exp().backward();
+        let e = 19.25f64.exp();
+        assert_close_to_literal!(
+            g.get(&x),
+            [[-4.0 * e, -2.0 * e, e], [2.0 * e, 4.0 * e, 6.0 * e]],
+            e * 1e-4
+        );
+    }
+
+    #[test]
+    fn test_sum_of_squares_matches_square_sum() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank1<5>, TestDtype, _> = dev.sample_normal();
+        let r = x
+            .leaky_trace()
+            .broadcast::<Rank2<3, 5>, _>()
+            .sum_of_squares();
+        let r2 = x.leaky_trace().broadcast::<Rank2<3, 5>, _>().square().sum();
+        assert_close_to_tensor!(r, r2);
+        let g = r.backward();
+        let g2 = r2.backward();
+        assert_close_to_tensor!(g.get(&x), g2.get(&x));
+    }
+}
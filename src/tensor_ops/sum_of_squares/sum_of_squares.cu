@@ -0,0 +1,58 @@
+#include "cuda_utils.cuh"
+
+template<typename T>
+__device__ void sum_of_squares_fwd(
+    const size_t numel,
+    const T scale,
+    const T *inp,
+    T *out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+
+    if (i >= numel) {
+        return;
+    }
+
+    chunk_sum(numel, inp[i] * inp[i] * scale, out);
+}
+
+// scale is expected to already include the factor of 2
+template<typename T>
+__device__ void sum_of_squares_bwd(
+    const size_t numel,
+    const T scale,
+    const T *inp,
+    T *grad_inp,
+    const T *grad_out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+
+    if (i >= numel) {
+        return;
+    }
+
+    grad_inp[i] += inp[i] * grad_out[0] * scale;
+}
+
+#define SUM_OF_SQUARES(TYPENAME, FWD, BWD) \
+extern "C" __global__ void FWD( \
+    const size_t numel, \
+    const TYPENAME scale, \
+    const TYPENAME *inp, \
+    TYPENAME *out \
+) { \
+    sum_of_squares_fwd(numel, scale, inp, out); \
+} \
+extern "C" __global__ void BWD( \
+    const size_t numel, \
+    const TYPENAME scale, \
+    const TYPENAME *inp, \
+    TYPENAME *grad_inp, \
+    const TYPENAME *grad_out \
+) { \
+    sum_of_squares_bwd(numel, scale, inp, grad_inp, grad_out); \
+}
+
+SUM_OF_SQUARES(__half, sum_of_squares_fwd_f16, sum_of_squares_bwd_f16);
+SUM_OF_SQUARES(float, sum_of_squares_fwd_f32, sum_of_squares_bwd_f32);
+SUM_OF_SQUARES(double, sum_of_squares_fwd_f64, sum_of_squares_bwd_f64);
@@ -1,5 +1,6 @@
-use crate::shapes::Rank0;
+use crate::shapes::{Dtype, Rank0, Shape};
 use crate::tensor::*;
+use crate::tensor_ops::axpy::AxpyKernel;
 
 /// Runs backprop algorithm with all operations contained in the tape that `t` has.
 ///
@@ -28,3 +29,102 @@ impl<E: 'static + Clone, D: OneFillStorage<E>> Backward<E, D>
         Ok(grads)
     }
 }
+
+impl<E: 'static + Clone, D: OneFillStorage<E>> Backward<E, D>
+    for Tensor<Rank0, E, D, Option<OwnedTape<E, D>>>
+{
+    fn try_backward(self) -> Result<Gradients<E, D>, Self::Err> {
+        let (t, tape) = self.split_tape();
+        match tape {
+            Some(tape) => t.put_tape(tape).try_backward(),
+            None => Ok(Gradients::leaky()),
+        }
+    }
+}
+
+impl<S: Shape, E: Dtype, D: AxpyKernel<E>> Tensor<S, E, D, OwnedTape<E, D>> {
+    /// Runs backprop from a tensor of any shape, using `seed` as the gradient
+    /// of `self` (i.e. computes a vector-Jacobian product).
+    ///
+    /// Seeding with ones is equivalent to calling `.sum().backward()`:
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let a = dev.tensor([1.0, 2.0, 3.0]);
+    /// let g = a.leaky_trace().square().backward_with(&dev.ones());
+    /// assert_eq!(g.get(&a).array(), [2.0, 4.0, 6.0]);
+    /// ```
+    ///
+    /// **Panics** if `seed` does not have the same shape & strides as `self`.
+    pub fn backward_with(self, seed: &Tensor<S, E, D>) -> Gradients<E, D> {
+        self.try_backward_with(seed).unwrap()
+    }
+
+    /// Fallible version of [Tensor::backward_with]
+    pub fn try_backward_with(self, seed: &Tensor<S, E, D>) -> Result<Gradients<E, D>, D::Err> {
+        assert_eq!(self.shape, seed.shape);
+        assert_eq!(
+            self.strides, seed.strides,
+            "Strides must be equal for backward_with"
+        );
+        let (t, mut tape) = self.split_tape();
+        let t_ghost = t.ghost();
+        let seed = seed.clone();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&t_ghost)?;
+            let zero = E::from_f32(0.0).unwrap();
+            let one = E::from_f32(1.0).unwrap();
+            t.device
+                .forward(grads.get_mut(&t_ghost), zero, seed.data.as_ref(), one)
+        });
+        let mut grads = tape.execute()?;
+        grads.drop_non_leafs();
+        Ok(grads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_backward_with_ones_is_sum() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let g1 = a.leaky_trace().exp().backward_with(&dev.ones());
+        let g2 = a.leaky_trace().exp().sum().backward();
+        assert_close_to_tensor!(g1.get(&a), g2.get(&a));
+    }
+
+    #[test]
+    fn test_backward_with_seed() {
+        let dev: TestDevice = Default::default();
+        let a = dev.tensor([1.0, 2.0, 3.0]).to_dtype::<TestDtype>();
+        let seed = dev.tensor([1.0, 0.0, -2.0]).to_dtype::<TestDtype>();
+        let g = a.leaky_trace().square().backward_with(&seed);
+        assert_close_to_literal!(g.get(&a), [2.0, 0.0, -12.0]);
+    }
+
+    #[test]
+    fn test_requires_grad() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+
+        let g = a.clone().requires_grad(true).square().sum().backward();
+        assert_close_to_tensor!(g.get(&a), a.clone() * 2.0);
+
+        let g = a.clone().requires_grad(false).square().sum().backward();
+        assert!(g.get_ref_checked(&a).is_none());
+
+        // the same code path can be used for both
+        for requires_grad in [false, true] {
+            let y = (a.clone().requires_grad(requires_grad) * b.clone()).sum();
+            let g = y.backward();
+            assert_eq!(g.get_ref_checked(&a).is_some(), requires_grad);
+            if requires_grad {
+                assert_close_to_tensor!(g.get(&a), b);
+            }
+        }
+    }
+}
@@ -161,6 +161,22 @@ impl<E: Dtype, Op: BinaryDerivative<E>> BinaryKernel<Op, E> for Cpu {
             _ => unreachable!(),
         }
     }
+    fn forward_into<S: Shape>(
+        &self,
+        op: Op,
+        lhs: &Tensor<S, E, Self>,
+        rhs: &Tensor<S, E, Self>,
+        out: &mut Tensor<S, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let mut lhs_iter = lhs.iter();
+        let mut rhs_iter = rhs.iter();
+        for o in out.buf_iter_mut() {
+            let l = lhs_iter.next().unwrap();
+            let r = rhs_iter.next().unwrap();
+            *o = op.f(l, r);
+        }
+        Ok(())
+    }
     fn backward<S: Shape>(
         &self,
         op: Op,
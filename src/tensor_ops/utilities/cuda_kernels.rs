@@ -316,6 +316,41 @@ impl<E: Dtype, K: BinaryOpCudaKernel<E> + DeviceRepr + Clone> BinaryKernel<K, E>
         }
     }
 
+    fn forward_into<S: Shape>(
+        &self,
+        op: K,
+        lhs: &Tensor<S, E, Self>,
+        rhs: &Tensor<S, E, Self>,
+        out: &mut Tensor<S, E, Self>,
+    ) -> Result<(), Self::Err> {
+        if !self.dev.has_func(K::MODULE_NAME, K::FWD_FN_NAME) {
+            self.dev
+                .load_ptx(K::PTX_SRC.into(), K::MODULE_NAME, &K::ALL_FN_NAMES)?;
+        }
+        let fwd_fn = self.dev.get_func(K::MODULE_NAME, K::FWD_FN_NAME).unwrap();
+
+        let numel = lhs.shape.num_elements();
+        let cfg = launch_cfg::<128>(numel as u32);
+
+        let mut info: Vec<usize> = Vec::with_capacity(3 * S::NUM_DIMS);
+        info.extend(lhs.shape.concrete());
+        info.extend(lhs.strides);
+        info.extend(rhs.strides);
+        let info = self.dev.htod_copy(info)?;
+
+        let params = (
+            op,
+            numel,                        // const size_t numel,
+            S::NUM_DIMS,                  // const size_t num_dims,
+            &info,                        // const size_t *info,
+            lhs.data.as_ref(),            // const float *lhs,
+            rhs.data.as_ref(),            // const float *rhs,
+            Arc::make_mut(&mut out.data), // float *out,
+        );
+        unsafe { fwd_fn.launch(cfg, params) }?;
+        Ok(())
+    }
+
     // NOTE: if it becomes possible for grad_out to be broadcasted, (i.e. if #366 is resolved), we
     // need to pass an elems_per_thread argument to the backward cuda kernels, as we do in sum_to.
     fn backward<S: Shape>(
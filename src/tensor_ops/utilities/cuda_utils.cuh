@@ -107,6 +107,9 @@ extern "C" __global__ void fill_with_f64(double *buf, double value, const size_t
 __device__ __forceinline__ bool isnang(float a) { return isnan(a); }
 __device__ __forceinline__ bool isnang(double a) { return isnan(a); }
 __device__ __forceinline__ bool isnang(__half a) { return __hisnan(a); }
+__device__ __forceinline__ bool isfiniteg(float a) { return isfinite(a); }
+__device__ __forceinline__ bool isfiniteg(double a) { return isfinite(a); }
+__device__ __forceinline__ bool isfiniteg(__half a) { return !__hisnan(a) && !__hisinf(a); }
 __device__ __forceinline__ float recipg(float a) { return 1.0 / a; }
 __device__ __forceinline__ double recipg(double a) { return 1.0 / a; }
 __device__ __forceinline__ __half recipg(__half a) { __half one = 1.0; return one / a; }
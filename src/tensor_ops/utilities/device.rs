@@ -29,9 +29,11 @@ pub trait Device<E: Dtype>:
     + crate::tensor::SampleTensor<E>
     + crate::tensor::OneFillStorage<E>
     + crate::tensor::ZeroFillStorage<E>
+    + crate::tensor::FillStorage<E>
 
     // broadcast & reduces
     + super::super::sum_to::SumKernel<E>
+    + super::super::sum_of_squares::SumOfSquaresKernel<E>
     + super::super::max_to::MaxReduceKernel<E>
     + super::super::min_to::MinReduceKernel<E>
     + super::super::reshape_to::ReshapeKernel<E>
@@ -43,6 +45,10 @@ pub trait Device<E: Dtype>:
     + super::super::slice::SliceKernel<E>
     + super::super::roll::RollKernel<E>
 
+    // fused ops
+    + super::super::softmax::SoftmaxKernel<E>
+    + super::super::attention_logits::AttentionLogitsKernel<E>
+
     // matmuls
     + super::super::matmul::MatMatKernel<E>
     + super::super::matmul::MatMatBrKernel<E>
@@ -83,9 +89,11 @@ pub trait Device<E: Dtype>:
     + super::super::dropout::DropoutKernel<E>
     + UnaryKernel<super::super::exp::ExpKernelOp, E>
     + UnaryKernel<super::super::ln::LnKernelOp, E>
+    + UnaryKernel<super::super::nan_to_num::NanToNumKernelOp<E>, E>
     + UnaryKernel<super::super::nans_to::NansToKernelOp<E>, E>
     + UnaryKernel<super::super::negate::NegateKernelOp, E>
     + UnaryKernel<super::super::relu::ReLUKernelOp, E>
+    + UnaryKernel<super::super::relu::InPlaceReLUKernelOp, E>
     + UnaryKernel<super::super::gelu::GeLUKernelOp, E>
     + UnaryKernel<super::super::sigmoid::SigmoidKernelOp, E>
     + UnaryKernel<super::super::sin::SinKernelOp, E>
@@ -30,6 +30,15 @@ pub trait BinaryKernel<Op, E: Dtype>: Storage<E> {
         lhs: Cow<Tensor<S, E, Self>>,
         rhs: Cow<Tensor<S, E, Self>>,
     ) -> Result<Tensor<S, E, Self>, Self::Err>;
+    /// Writes the result of the forward pass into `out`, which has the same shape as
+    /// `lhs` & `rhs` and is contiguous.
+    fn forward_into<S: Shape>(
+        &self,
+        op: Op,
+        lhs: &Tensor<S, E, Self>,
+        rhs: &Tensor<S, E, Self>,
+        out: &mut Tensor<S, E, Self>,
+    ) -> Result<(), Self::Err>;
     fn backward<S: Shape>(
         &self,
         op: Op,
@@ -139,3 +148,19 @@ pub(crate) fn try_binary_op<
         Ok(out.put_tape(tape))
     }
 }
+
+pub(crate) fn try_binary_op_into<Op, S: Shape, E: Dtype, D: BinaryKernel<Op, E>>(
+    op: Op,
+    lhs: &Tensor<S, E, D>,
+    rhs: &Tensor<S, E, D>,
+    out: &mut Tensor<S, E, D>,
+) -> Result<(), D::Err> {
+    assert_eq!(lhs.shape(), rhs.shape());
+    assert_eq!(lhs.shape(), out.shape());
+    assert_eq!(
+        out.strides,
+        out.shape.strides(),
+        "Output of an `_into` op must be contiguous"
+    );
+    lhs.device.forward_into(op, lhs, rhs, out)
+}
@@ -0,0 +1,79 @@
+/// Stops training when a metric hasn't improved for `patience` steps in a row.
+///
+/// Lower metrics are better, like a validation loss. To track a metric where higher is
+/// better (e.g. accuracy), pass its negation. A metric only counts as an improvement if it
+/// is lower than the best metric so far by more than `min_delta`.
+///
+/// ```rust
+/// # use dfdx::train::EarlyStopping;
+/// let mut early_stopping = EarlyStopping::new(2, 0.0);
+/// assert!(!early_stopping.should_stop(1.0));
+/// assert!(!early_stopping.should_stop(0.5));
+/// assert!(!early_stopping.should_stop(0.6));
+/// assert!(early_stopping.should_stop(0.5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EarlyStopping {
+    /// The number of steps without improvement before stopping.
+    pub patience: usize,
+
+    /// The amount the metric has to decrease by to count as an improvement.
+    pub min_delta: f32,
+
+    /// The best metric seen so far.
+    pub best: f32,
+
+    /// The number of steps since the last improvement.
+    pub num_bad_steps: usize,
+}
+
+impl EarlyStopping {
+    pub fn new(patience: usize, min_delta: f32) -> Self {
+        Self {
+            patience,
+            min_delta,
+            best: f32::INFINITY,
+            num_bad_steps: 0,
+        }
+    }
+
+    /// Records `metric`, and returns `true` if there have been `patience` steps in a row
+    /// without improvement.
+    pub fn should_stop(&mut self, metric: f32) -> bool {
+        if metric < self.best - self.min_delta {
+            self.best = metric;
+            self.num_bad_steps = 0;
+        } else {
+            self.num_bad_steps += 1;
+        }
+        self.num_bad_steps >= self.patience
+    }
+
+    /// Forgets the best metric and the number of bad steps.
+    pub fn reset(&mut self) {
+        self.best = f32::INFINITY;
+        self.num_bad_steps = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_early_stopping_patience() {
+        let mut es = EarlyStopping::new(3, 0.1);
+        let metrics = [1.0, 0.8, 0.75, 0.85, 0.6, 0.55, 0.7, 0.65];
+        let stops: Vec<bool> = metrics.iter().map(|&m| es.should_stop(m)).collect();
+        // 0.75 and 0.55 are within min_delta of the best, so they don't count as improvements
+        assert_eq!(
+            stops,
+            [false, false, false, false, false, false, false, true]
+        );
+        assert_eq!(es.best, 0.6);
+        assert_eq!(es.num_bad_steps, 3);
+
+        es.reset();
+        assert!(!es.should_stop(10.0));
+    }
+}
@@ -0,0 +1,4 @@
+//! Utilities for writing training loops, like [EarlyStopping].
+mod early_stopping;
+
+pub use early_stopping::EarlyStopping;
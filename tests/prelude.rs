@@ -0,0 +1,75 @@
+//! Checks that the common parts of the API can be used with only `dfdx::prelude::*`.
+
+use dfdx::prelude::*;
+
+type Model = (
+    (Linear<4, 8>, ReLU),
+    Residual<(LayerNorm1D<8>, Linear<8, 8>, GeLU)>,
+    Dropout,
+    Linear<8, 2>,
+);
+
+fn count_params<M: TensorCollection<f32, Cpu> + NumParams<f32, Cpu>>(model: &M) -> usize {
+    model.num_trainable_params()
+}
+
+#[test]
+fn test_prelude_nn_and_optim() {
+    let dev: Cpu = Default::default();
+    let mut model = dev.build_module::<Model, f32>();
+    assert_eq!(
+        count_params(&model),
+        4 * 8 + 8 + 8 + 8 + 8 * 8 + 8 + 8 * 2 + 2
+    );
+    model.reset_params();
+
+    let mut grads = model.alloc_grads();
+    let mut sgd = Sgd::new(&model, SgdConfig::default());
+    let mut adam = Adam::new(&model, AdamConfig::default());
+    let mut rmsprop = RMSprop::new(&model, RMSpropConfig::default());
+
+    let x: Tensor<Rank2<3, 4>, f32, _> = dev.sample_normal();
+    let y: Tensor<Rank2<3, 2>, f32, _> = dev.sample_normal();
+    let pred = model.forward_mut(x.trace(grads));
+    grads = mse_loss(pred, y.clone()).backward();
+    sgd.update(&mut model, &grads).unwrap();
+    adam.update(&mut model, &grads).unwrap();
+    rmsprop.update(&mut model, &grads).unwrap();
+    model.zero_grads(&mut grads);
+
+    let _ = cross_entropy_with_logits_loss(model.forward(x.clone()), y.softmax::<Axis<1>>());
+
+    let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    let tokens: Tensor<Rank3<1, 5, 8>, f32, _> = dev.sample_normal();
+    let _ = mha.forward(tokens.clone());
+    let encoder = dev.build_module::<TransformerEncoder<8, 2, 16, 1>, f32>();
+    let _ = encoder.forward(tokens);
+}
+
+#[test]
+fn test_prelude_tensor_ops() {
+    let dev: Cpu = Default::default();
+    let a: Tensor<Rank3<2, 3, 4>, f32, _> = dev.sample_normal();
+    let b: Tensor<Rank3<2, 4, 5>, f32, _> = dev.sample_normal();
+
+    // batched matmul
+    let c: Tensor<Rank3<2, 3, 5>, f32, _> = a.clone().matmul(b);
+    let _ = softmax::<Axis<2>, _, _, _, _>(c.clone());
+    let _ = c.clone().log_softmax::<Axis<2>>();
+
+    // broadcasting arithmetic
+    let bias: Tensor<Rank1<5>, f32, _> = dev.ones();
+    let _ = c.clone() + bias.broadcast();
+    let _ = c.clone() * 2.0 - 1.0;
+
+    // reductions & shape ops
+    let _: Tensor<Rank1<3>, f32, _> = c.clone().mean::<_, Axes2<0, 2>>();
+    let _: Tensor<Rank2<2, 3>, f32, _> = c.clone().max();
+    let _: Tensor<Rank3<5, 3, 2>, f32, _> = c.clone().permute();
+    let _: Tensor<Rank2<6, 5>, f32, _> = c.clone().reshape();
+
+    // unary ops
+    let _ = c.clone().relu().exp().ln().sqrt().tanh().sigmoid().abs();
+    let _ = c.clone().clamp(-1.0, 1.0).square().powf(0.5);
+    let _ = c.dropout(0.5);
+}